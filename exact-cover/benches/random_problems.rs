@@ -0,0 +1,28 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use exact_cover::{DancingCellsBackend, ExactCoverProblem, GeneralBackend, SolverBackend};
+
+fn bench_random_problems(c: &mut Criterion) {
+    let sparse_problem = ExactCoverProblem::random(60, 600, 0.05, 1);
+    c.bench_function("count solutions of a sparse random problem", |b| {
+        b.iter(|| sparse_problem.count_solutions_up_to(1000))
+    });
+
+    let dense_problem = ExactCoverProblem::random(30, 300, 0.2, 1);
+    c.bench_function("count solutions of a dense random problem", |b| {
+        b.iter(|| dense_problem.count_all_solutions())
+    });
+
+    c.bench_function("build the ZDD of a dense random problem", |b| {
+        b.iter(|| dense_problem.build_zdd().count_solutions())
+    });
+
+    let backends: [&dyn SolverBackend; 2] = [&GeneralBackend, &DancingCellsBackend];
+    for backend in backends {
+        c.bench_function(&format!("count solutions of a sparse random problem with the {} backend", backend.name()), |b| {
+            b.iter(|| sparse_problem.count_solutions_with_backend(backend, 1000))
+        });
+    }
+}
+
+criterion_group!(benches, bench_random_problems);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+//! Find Langford pairings: sequences of the numbers 1 to n, each appearing twice, in which the two copies of every
+//! number k are k numbers apart.
+//!
+//! Usage: cargo run -p exact-cover --example langford -- <n> [--count]
+//!
+//! Pairings only exist when n is 0 or 3 modulo 4. With --count, the number of pairings is printed instead of the first
+//! one found, counting a pairing and its reverse as different pairings.
+
+use std::collections::HashMap;
+
+use exact_cover::ExactCoverProblem;
+
+/**
+ * Encode the pairings as an exact cover problem: every number must be placed once and every one of the 2n positions
+ * filled once. An option places number k at positions i and i + k + 1, which it carries as payload.
+ */
+fn convert_to_exact_cover_problem(n: usize) -> ExactCoverProblem {
+    let mut required_items: Vec<String> = (1..=n).map(|k| format!("n{}", k)).collect();
+    required_items.extend((0..2 * n).map(|i| format!("p{}", i)));
+
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+    let mut payloads: HashMap<String, (usize, usize, usize)> = HashMap::new();
+    for k in 1..=n {
+        for i in 0..2 * n {
+            let j = i + k + 1;
+            if j >= 2 * n {
+                break;
+            }
+            let option_name = format!("{}@{}", k, i);
+            for item_name in [format!("n{}", k), format!("p{}", i), format!("p{}", j)] {
+                covered_by.entry(item_name).or_default().push(option_name.clone());
+            }
+            payloads.insert(option_name, (k, i, j));
+        }
+    }
+
+    return ExactCoverProblem::new(required_items, vec![], vec![], covered_by)
+        .and_then(|problem| problem.with_option_payloads(payloads))
+        .expect("every number and position is declared once");
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let count = args.iter().any(|arg| arg == "--count");
+    let n: usize = match args.iter().find(|arg| *arg != "--count").and_then(|arg| arg.parse::<usize>().ok()) {
+        Some(n) if n > 0 => n,
+        _ => {
+            eprintln!("Usage: langford <n> [--count]");
+            std::process::exit(1);
+        }
+    };
+
+    let problem = convert_to_exact_cover_problem(n);
+    if count {
+        println!("Langford pairings of 1 to {}: {}", n, problem.count_all_solutions());
+        return;
+    }
+
+    let Some(solution) = problem.solve() else {
+        println!("There is no Langford pairing of 1 to {}", n);
+        return;
+    };
+    let mut sequence = vec![0; 2 * n];
+    for (k, i, j) in problem.solution_payloads::<(usize, usize, usize)>(&solution) {
+        sequence[*i] = *k;
+        sequence[*j] = *k;
+    }
+    println!("{}", sequence.iter().map(|k| k.to_string()).collect::<Vec<String>>().join(" "));
+}
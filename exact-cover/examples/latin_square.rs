@@ -0,0 +1,66 @@
+//! Fill an n by n Latin square: a grid in which every row and column contains each of the symbols 1 to n once.
+//!
+//! Usage: cargo run -p exact-cover --example latin_square -- <n> [--count]
+//!
+//! With --count, the number of Latin squares is printed instead of the first one found. It grows very quickly, so
+//! counting is only feasible up to n = 5.
+
+use std::collections::HashMap;
+
+use exact_cover::ExactCoverProblem;
+
+/**
+ * Encode the Latin square as an exact cover problem, like Sudoku without the blocks: every cell must have a symbol,
+ * and every symbol must appear once in every row and column. An option puts a symbol in a cell and carries the row,
+ * column and symbol as payload.
+ */
+fn convert_to_exact_cover_problem(n: usize) -> ExactCoverProblem {
+    let mut required_items: Vec<String> = Vec::new();
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+    let mut payloads: HashMap<String, (usize, usize, usize)> = HashMap::new();
+    for row in 0..n {
+        for col in 0..n {
+            required_items.push(format!("r{}c{}", row, col));
+            required_items.push(format!("r{}s{}", row, col + 1));
+            required_items.push(format!("c{}s{}", col, row + 1));
+            for symbol in 1..=n {
+                let option_name = format!("r{}c{}s{}", row, col, symbol);
+                for item_name in [format!("r{}c{}", row, col), format!("r{}s{}", row, symbol), format!("c{}s{}", col, symbol)] {
+                    covered_by.entry(item_name).or_default().push(option_name.clone());
+                }
+                payloads.insert(option_name, (row, col, symbol));
+            }
+        }
+    }
+
+    return ExactCoverProblem::new(required_items, vec![], vec![], covered_by)
+        .and_then(|problem| problem.with_option_payloads(payloads))
+        .expect("every cell, row symbol and column symbol is declared once");
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let count = args.iter().any(|arg| arg == "--count");
+    let n: usize = match args.iter().find(|arg| *arg != "--count").and_then(|arg| arg.parse::<usize>().ok()) {
+        Some(n) if n > 0 => n,
+        _ => {
+            eprintln!("Usage: latin_square <n> [--count]");
+            std::process::exit(1);
+        }
+    };
+
+    let problem = convert_to_exact_cover_problem(n);
+    if count {
+        println!("Latin squares of order {}: {}", n, problem.count_all_solutions());
+        return;
+    }
+
+    let solution = problem.solve().expect("a Latin square of every order exists");
+    let mut square = vec![vec![0; n]; n];
+    for (row, col, symbol) in problem.solution_payloads::<(usize, usize, usize)>(&solution) {
+        square[*row][*col] = *symbol;
+    }
+    for row in square {
+        println!("{}", row.iter().map(|symbol| symbol.to_string()).collect::<Vec<String>>().join(" "));
+    }
+}
@@ -0,0 +1,115 @@
+//! Tile a rectangle of 60 cells with the 12 pentominoes.
+//!
+//! Usage: cargo run -p exact-cover --example pentominoes -- [rows] [--count]
+//!
+//! The rectangle has 6 rows by default and 3, 4, 5 or 6 rows are possible. With --count, the number of tilings is
+//! printed instead of the first one found, counting rotations and reflections of a tiling as different tilings.
+
+use std::collections::{BTreeSet, HashMap};
+
+use exact_cover::ExactCoverProblem;
+
+/// The pentominoes by their usual letter, as (row, col) cells of one orientation
+const PENTOMINOES: [(char, [(i8, i8); 5]); 12] = [
+    ('F', [(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)]),
+    ('I', [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]),
+    ('L', [(0, 0), (1, 0), (2, 0), (3, 0), (3, 1)]),
+    ('N', [(0, 1), (1, 1), (2, 0), (2, 1), (3, 0)]),
+    ('P', [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)]),
+    ('T', [(0, 0), (0, 1), (0, 2), (1, 1), (2, 1)]),
+    ('U', [(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]),
+    ('V', [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]),
+    ('W', [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2)]),
+    ('X', [(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)]),
+    ('Y', [(0, 1), (1, 0), (1, 1), (2, 1), (3, 1)]),
+    ('Z', [(0, 0), (0, 1), (1, 1), (2, 1), (2, 2)]),
+];
+
+/**
+ * The distinct rotations and reflections of a piece, each shifted so that its smallest row and column are 0.
+ */
+fn orientations(cells: &[(i8, i8); 5]) -> BTreeSet<Vec<(i8, i8)>> {
+    let mut orientations: BTreeSet<Vec<(i8, i8)>> = BTreeSet::new();
+    let mut cells: Vec<(i8, i8)> = cells.to_vec();
+    for _ in 0..2 {
+        for _ in 0..4 {
+            cells = cells.iter().map(|(row, col)| (*col, -row)).collect();
+            let min_row = cells.iter().map(|(row, _)| *row).min().unwrap();
+            let min_col = cells.iter().map(|(_, col)| *col).min().unwrap();
+            let mut normalized: Vec<(i8, i8)> = cells.iter().map(|(row, col)| (row - min_row, col - min_col)).collect();
+            normalized.sort();
+            orientations.insert(normalized);
+        }
+        cells = cells.iter().map(|(row, col)| (*row, -col)).collect();
+    }
+    return orientations;
+}
+
+/**
+ * Encode the tiling as an exact cover problem: every piece must be used once and every cell covered once. An option
+ * places one orientation of a piece at one position and carries the piece and its cells as payload.
+ */
+fn convert_to_exact_cover_problem(rows: i8, cols: i8) -> ExactCoverProblem {
+    let mut required_items: Vec<String> = PENTOMINOES.iter().map(|(piece, _)| piece.to_string()).collect();
+    for row in 0..rows {
+        for col in 0..cols {
+            required_items.push(format!("{},{}", row, col));
+        }
+    }
+
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+    let mut payloads: HashMap<String, (char, Vec<(i8, i8)>)> = HashMap::new();
+    for (piece, cells) in PENTOMINOES.iter() {
+        for (orientation_idx, orientation) in orientations(cells).iter().enumerate() {
+            for row in 0..rows {
+                for col in 0..cols {
+                    let placed: Vec<(i8, i8)> = orientation.iter().map(|(i, j)| (row + i, col + j)).collect();
+                    if placed.iter().any(|(i, j)| *i >= rows || *j >= cols) {
+                        continue;
+                    }
+                    let option_name = format!("{}{}@{},{}", piece, orientation_idx, row, col);
+                    covered_by.entry(piece.to_string()).or_default().push(option_name.clone());
+                    for (i, j) in placed.iter() {
+                        covered_by.entry(format!("{},{}", i, j)).or_default().push(option_name.clone());
+                    }
+                    payloads.insert(option_name, (*piece, placed));
+                }
+            }
+        }
+    }
+
+    return ExactCoverProblem::new(required_items, vec![], vec![], covered_by)
+        .and_then(|problem| problem.with_option_payloads(payloads))
+        .expect("every cell and piece is declared once");
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let count = args.iter().any(|arg| arg == "--count");
+    let rows: i8 = match args.iter().find(|arg| *arg != "--count").map(|arg| arg.parse::<i8>()) {
+        None => 6,
+        Some(Ok(rows)) if (3..=6).contains(&rows) => rows,
+        Some(_) => {
+            eprintln!("The number of rows must be 3, 4, 5 or 6");
+            std::process::exit(1);
+        }
+    };
+    let cols = 60 / rows;
+
+    let problem = convert_to_exact_cover_problem(rows, cols);
+    if count {
+        println!("Tilings of the {}x{} rectangle: {}", rows, cols, problem.count_all_solutions());
+        return;
+    }
+
+    let solution = problem.solve().expect("every rectangle of 3 to 6 rows can be tiled");
+    let mut grid = vec![vec!['.'; cols as usize]; rows as usize];
+    for (piece, cells) in problem.solution_payloads::<(char, Vec<(i8, i8)>)>(&solution) {
+        for (row, col) in cells.iter() {
+            grid[*row as usize][*col as usize] = *piece;
+        }
+    }
+    for row in grid {
+        println!("{}", row.into_iter().collect::<String>());
+    }
+}
@@ -0,0 +1,136 @@
+use std::ops::ControlFlow;
+
+use crate::dancing_cells::DancingCellsProblem;
+use crate::{ExactCoverProblem, ExactCoverSolution, SearchError, SearchStats};
+
+/**
+ * An engine that searches for the solutions of exact cover problems, so that the engine can be chosen or benchmarked
+ * per problem. Every backend finds the same solutions, but possibly in a different order.
+ *
+ * ```
+ * use std::collections::HashMap;
+ * use exact_cover::{DancingCellsBackend, ExactCoverProblem, GeneralBackend, SolverBackend};
+ *
+ * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+ * #     ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+ * #     ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+ * # ]);
+ * # let required_items = vec!["A".to_string(), "B".to_string()];
+ * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+ *
+ * let backends: [&dyn SolverBackend; 2] = [&GeneralBackend, &DancingCellsBackend];
+ * for backend in backends {
+ *     assert_eq!(problem.count_solutions_with_backend(backend, u64::MAX).unwrap(), 2, "{}", backend.name());
+ * }
+ * ```
+ */
+pub trait SolverBackend {
+    /**
+     * A short name of the backend, e.g. for benchmark reports.
+     */
+    fn name(&self) -> &'static str;
+
+    /**
+     * Call visit with the selected options of every solution of the problem until it returns Break, and return the
+     * statistics of the search. Only the general engine counts forced options and estimates its memory use.
+     */
+    fn visit_solutions(&self, problem: &ExactCoverProblem, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> Result<SearchStats, SearchError>;
+}
+
+/**
+ * The general engine, which keeps the available options of each item in hash sets and the items in a priority queue.
+ * It is the only backend that respects the memory limit and forced option propagation.
+ */
+pub struct GeneralBackend;
+
+/**
+ * The engine for problems with at most 128 items, which represents every option as a bitmask of its items. Larger
+ * problems are solved with the general engine instead.
+ */
+pub struct BitsetBackend;
+
+/**
+ * The engine based on Knuth's dancing cells, which keeps the options of each item in sparse sets of array indices and
+ * undoes changes in reverse order instead of copying state.
+ */
+pub struct DancingCellsBackend;
+
+impl SolverBackend for GeneralBackend {
+    fn name(&self) -> &'static str {
+        return "general";
+    }
+
+    fn visit_solutions(&self, problem: &ExactCoverProblem, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> Result<SearchStats, SearchError> {
+        let mut state = problem.new_search_state();
+        problem.select_required_options(&mut state);
+        if problem.propagate_forced_options {
+            problem.select_forced_options(&mut state);
+        }
+        problem._visit_solutions(&mut state, visit)?;
+        return Ok(state.stats);
+    }
+}
+
+impl SolverBackend for BitsetBackend {
+    fn name(&self) -> &'static str {
+        return "bitset";
+    }
+
+    fn visit_solutions(&self, problem: &ExactCoverProblem, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> Result<SearchStats, SearchError> {
+        return match problem.bitset.as_ref() {
+            Some(bitset) => Ok(bitset.for_each_solution(&mut problem.filter_visit(visit))),
+            None => GeneralBackend.visit_solutions(problem, visit),
+        };
+    }
+}
+
+impl SolverBackend for DancingCellsBackend {
+    fn name(&self) -> &'static str {
+        return "dancing cells";
+    }
+
+    fn visit_solutions(&self, problem: &ExactCoverProblem, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> Result<SearchStats, SearchError> {
+        return Ok(DancingCellsProblem::new(problem).for_each_solution(&mut problem.filter_visit(visit)));
+    }
+}
+
+impl ExactCoverProblem {
+    /**
+     * Solve the exact cover problem with the given backend instead of choosing one automatically.
+     */
+    pub fn try_solve_with_backend(&self, backend: &dyn SolverBackend) -> Result<Option<ExactCoverSolution>, SearchError> {
+        return self.try_solve_with_backend_and_stats(backend).map(|(solution, _)| solution);
+    }
+
+    /**
+     * Solve the exact cover problem with the given backend like try_solve_with_backend, and also return the statistics
+     * of the search, e.g. to compare the number of nodes each backend needs.
+     */
+    pub fn try_solve_with_backend_and_stats(&self, backend: &dyn SolverBackend) -> Result<(Option<ExactCoverSolution>, SearchStats), SearchError> {
+        let mut solution: Option<ExactCoverSolution> = None;
+        let stats = backend.visit_solutions(self, &mut |selected_options| {
+            solution = Some(ExactCoverSolution { selected_options: selected_options.to_vec() });
+            ControlFlow::Break(())
+        })?;
+        return Ok((solution, stats));
+    }
+
+    /**
+     * Count the solutions with the given backend, stopping once the given limit is reached.
+     */
+    pub fn count_solutions_with_backend(&self, backend: &dyn SolverBackend, limit: u64) -> Result<u64, SearchError> {
+        let mut num_solutions: u64 = 0;
+        if limit == 0 {
+            return Ok(num_solutions);
+        }
+        backend.visit_solutions(self, &mut |_| {
+            num_solutions += 1;
+            if num_solutions < limit {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        })?;
+        return Ok(num_solutions);
+    }
+}
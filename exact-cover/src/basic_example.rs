@@ -0,0 +1,1790 @@
+use std::collections::HashMap;
+
+use crate::{ExactCoverProblem, ExactCoverSolution};
+
+/**
+ * A basic example problem which can be solved with exact cover.
+ */
+pub(crate) struct BasicExampleProblem<'a> {
+    required_items: Vec<&'a str>,
+    optional_items: Vec<&'a str>,
+    options: Vec<&'a str>,
+}
+
+/**
+ * A basic example solution.
+ */
+pub(crate) struct BasicExampleSolution {
+    selected_options: Vec<String>,
+}
+
+/**
+ * Convert a basic example problem to an exact cover problem.
+ */
+fn convert_to_exact_cover_problem<'a>(basic_example: &'a BasicExampleProblem<'a>) -> ExactCoverProblem {
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+
+    let mut required_items: Vec<String> = Vec::new();
+    for item in &basic_example.required_items {
+        covered_by.insert(item.to_string(), Vec::new());
+        required_items.push(item.to_string());
+    }
+    let mut optional_items: Vec<String> = Vec::new();
+    for item in &basic_example.optional_items {
+        covered_by.insert(item.to_string(), Vec::new());
+        optional_items.push(item.to_string());
+    }
+
+    for option_name in &basic_example.options {
+        for item_name in get_items_which_can_be_covered_by_option(option_name) {
+            covered_by.get_mut(item_name).unwrap().push(option_name.to_string());
+        }
+    }
+
+    return ExactCoverProblem::new(required_items, optional_items, vec![], covered_by)
+        .expect("the basic example declares every item exactly once");
+}
+
+fn get_items_which_can_be_covered_by_option(option_name: &str) -> Vec<&str> {
+    return option_name.split("").filter(|item_name| !item_name.is_empty()).collect();
+}
+
+/**
+ * Convert an exact cover solution to a basic example solution.
+ */
+fn convert_to_basic_example_solution(solution: ExactCoverSolution) -> BasicExampleSolution {
+    return BasicExampleSolution {
+        selected_options: solution.selected_options.iter().map(|option| option.to_string()).collect(),
+    };
+}
+
+/**
+ * Solve a basic example problem with exact cover.
+ */
+pub(crate) fn solve_basic_example_with_exact_cover<'a>(basic_example_problem: &'a BasicExampleProblem<'a>) -> Option<BasicExampleSolution> {
+    let exact_cover_problem = convert_to_exact_cover_problem(basic_example_problem);
+
+    let solution = exact_cover_problem.solve();
+
+    solution.map(convert_to_basic_example_solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use crate::{BitsetBackend, ChromeTrace, Contradiction, DancingCellsBackend, EncodingIssue, ExactCoverResult, GeneralBackend, InvalidProblemError, MemoryStats, Observer, ProblemStats, SearchError, SearchStats, SimplificationReport, SolutionMultiplicity, SolverBackend, SymmetryCounts, UnsatisfiableCore};
+    use crate::test_utils::assert_eq_ignore_order;
+
+    use super::*;
+
+    #[test]
+    fn test_zero_items() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec![],
+            optional_items: vec![],
+            options: vec![],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        let right: Vec<&str> = vec![];
+        assert_eq!(selected_options, right);
+    }
+
+    #[test]
+    fn test_zero_options() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec![],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_none());
+    }
+
+    #[test]
+    fn test_only_optional_items_and_zero_options() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec![],
+            optional_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            options: vec![],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        let right: Vec<&str> = vec![];
+        assert_eq!(selected_options, right);
+    }
+
+    #[test]
+    fn test_one_item() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A"],
+            optional_items: vec![],
+            options: vec!["A"],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        assert_eq!(selected_options, vec!["A"]);
+    }
+
+    #[test]
+    fn test_choose_all_two_options() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B"],
+            optional_items: vec![],
+            options: vec!["A", "B"],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        assert_eq_ignore_order(&selected_options, &["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn test_choose_two_of_three_options_for_three_items() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "AC", "C"],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        assert_eq_ignore_order(&selected_options, &["AB".to_string(), "C".to_string()])
+    }
+
+    #[test]
+    fn test_no_solution_for_three_items() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "BC", "AC"],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_none());
+    }
+
+    #[test]
+    fn test_basic_example() {
+        // Example from https://en.wikipedia.org/wiki/Exact_cover#Detailed_example
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["1", "2", "3", "4", "5", "6", "7"],
+            optional_items: vec![],
+            options: vec![
+                "147",
+                "14",
+                "457",
+                "356",
+                "2367",
+                "27",
+            ],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        assert_eq_ignore_order(&selected_options, &["14".to_string(), "356".to_string(), "27".to_string()]);
+    }
+
+    #[test]
+    fn test_basic_example_no_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["1", "2", "3", "4", "5", "6", "7"],
+            optional_items: vec![],
+            options: vec![
+                "147",
+                "14",
+                "457",
+                "356",
+                "2367",
+                "26",
+            ],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_none());
+    }
+
+    #[test]
+    fn test_knuth_basic_example() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec![
+                "CEF",
+                "ADG",
+                "BCF",
+                "AD",
+                "BG",
+                "DEG",
+            ],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        assert_eq_ignore_order(&selected_options, &["CEF".to_string(), "AD".to_string(), "BG".to_string()]);
+    }
+
+    #[test]
+    fn test_knuth_basic_example_with_optional_h() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec!["H"],
+            options: vec![
+                "CEF",
+                "ADG",
+                "BCF",
+                "AD",
+                "BG",
+                "DEG",
+            ],
+        };
+
+        let solution = solve_basic_example_with_exact_cover(&basic_example);
+
+        assert!(solution.is_some());
+        let selected_options = solution.unwrap().selected_options;
+        assert_eq_ignore_order(&selected_options, &["CEF".to_string(), "AD".to_string(), "BG".to_string()]);
+    }
+
+    #[test]
+    fn test_deep_search_does_not_overflow_stack() {
+        // Every item has its own option, so the search has to go one level deeper for every item
+        let num_items = 20_000;
+        let required_items: Vec<String> = (0..num_items).map(|i| format!("i{}", i)).collect();
+        let covered_by: HashMap<String, Vec<String>> = (0..num_items)
+            .map(|i| (format!("i{}", i), vec![format!("o{}", i)]))
+            .collect();
+        let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+
+        let solution = exact_cover_problem.solve();
+
+        assert!(solution.is_some());
+        assert_eq!(solution.unwrap().selected_options.len(), num_items);
+    }
+
+    #[test]
+    fn test_problem_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ExactCoverProblem>();
+
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let counts: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| exact_cover_problem.count_all_solutions()))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        assert_eq!(counts, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_is_optional() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B"],
+            optional_items: vec!["C"],
+            options: vec!["AC", "B"],
+        };
+
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        assert!(!exact_cover_problem.is_optional("A"));
+        assert!(exact_cover_problem.is_optional("C"));
+        assert!(!exact_cover_problem.is_optional("D"));
+    }
+
+    #[test]
+    fn test_item_declared_twice() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([("A".to_string(), vec!["A".to_string()])]);
+
+        let result = ExactCoverProblem::new(vec!["A".to_string()], vec!["A".to_string()], vec![], covered_by);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::DuplicateItem("A".to_string())));
+    }
+
+    #[test]
+    fn test_undeclared_item() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string()]),
+            ("B".to_string(), vec!["AB".to_string()]),
+        ]);
+
+        let result = ExactCoverProblem::new(vec!["A".to_string()], vec![], vec![], covered_by);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::UndeclaredItem("B".to_string())));
+    }
+
+    #[test]
+    fn test_unknown_required_option() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([("A".to_string(), vec!["A".to_string()])]);
+
+        let result = ExactCoverProblem::new(vec!["A".to_string()], vec![], vec!["B".to_string()], covered_by);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::UnknownRequiredOption("B".to_string())));
+    }
+
+    #[test]
+    fn test_conflicting_required_options() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["AB".to_string(), "BC".to_string()]),
+            ("C".to_string(), vec!["BC".to_string(), "C".to_string()]),
+        ]);
+        let required_items = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        let required_options = vec!["AB".to_string(), "BC".to_string()];
+
+        let result = ExactCoverProblem::new(required_items, vec![], required_options, covered_by);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::RequiredOptionConflict {
+            option_a: "AB".to_string(),
+            option_b: "BC".to_string(),
+            item: "B".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_declared_item_without_options() {
+        let result = ExactCoverProblem::new(vec!["A".to_string()], vec!["B".to_string()], vec![], HashMap::new());
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().solve().is_none());
+    }
+
+    #[test]
+    fn test_max_cover_of_solvable_problem() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let solution = exact_cover_problem.solve_max_cover();
+
+        assert_eq!(solution.uncovered_items, Vec::<String>::new());
+        assert_eq_ignore_order(&solution.selected_options, &["CEF".to_string(), "AD".to_string(), "BG".to_string()]);
+    }
+
+    #[test]
+    fn test_max_cover_of_three_items() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "BC", "AC"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let solution = exact_cover_problem.solve_max_cover();
+
+        assert_eq!(solution.selected_options.len(), 1);
+        assert_eq!(solution.uncovered_items.len(), 1);
+        assert!(!solution.selected_options[0].contains(solution.uncovered_items[0].as_str()));
+    }
+
+    #[test]
+    fn test_max_cover_of_basic_example_without_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["1", "2", "3", "4", "5", "6", "7"],
+            optional_items: vec![],
+            options: vec!["147", "14", "457", "356", "2367", "26"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let solution = exact_cover_problem.solve_max_cover();
+
+        // The best selections (e.g. 147 and 356) cover six of the seven items
+        assert_eq!(solution.uncovered_items.len(), 1);
+        let mut covered: Vec<char> = solution.selected_options.iter().flat_map(|option| option.chars()).collect();
+        covered.extend(solution.uncovered_items.iter().flat_map(|item| item.chars()));
+        assert_eq_ignore_order(&covered, &['1', '2', '3', '4', '5', '6', '7']);
+    }
+
+    #[test]
+    fn test_max_cover_with_item_without_options() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let solution = exact_cover_problem.solve_max_cover();
+
+        assert_eq!(solution.selected_options, vec!["AB"]);
+        assert_eq!(solution.uncovered_items, vec!["C"]);
+    }
+
+    #[test]
+    fn test_explain_satisfiable_problem() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "C"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let explanation = exact_cover_problem.explain_unsatisfiability();
+
+        assert_eq!(explanation, None);
+    }
+
+    #[test]
+    fn test_explain_uncoverable_item() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AC".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["BC".to_string(), "B".to_string()]),
+            ("C".to_string(), vec!["AC".to_string(), "BC".to_string()]),
+            ("D".to_string(), vec!["D".to_string()]),
+        ]);
+        let required_items = vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()];
+        let required_options = vec!["A".to_string(), "B".to_string(), "D".to_string()];
+        let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], required_options, covered_by).unwrap();
+
+        let explanation = exact_cover_problem.explain_unsatisfiability();
+
+        assert_eq!(explanation, Some(UnsatisfiableCore {
+            required_options: vec!["A".to_string(), "B".to_string()],
+            contradiction: Some(Contradiction::Uncoverable("C".to_string())),
+        }));
+    }
+
+    #[test]
+    fn test_zdd_of_knuth_basic_example() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let zdd = exact_cover_problem.build_zdd();
+
+        assert_eq!(zdd.count_solutions(), 1);
+        let solutions: Vec<ExactCoverSolution> = zdd.solutions().collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq_ignore_order(&solutions[0].selected_options, &["CEF".to_string(), "AD".to_string(), "BG".to_string()]);
+    }
+
+    #[test]
+    fn test_zdd_with_several_solutions() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let zdd = exact_cover_problem.build_zdd();
+
+        assert_eq!(zdd.count_solutions(), exact_cover_problem.count_all_solutions() as u128);
+        let mut solutions: Vec<Vec<String>> = zdd.solutions()
+            .map(|solution| {
+                let mut selected_options = solution.selected_options;
+                selected_options.sort();
+                selected_options
+            })
+            .collect();
+        solutions.sort();
+        let to_strings = |options: &[&str]| options.iter().map(|option| option.to_string()).collect::<Vec<String>>();
+        assert_eq!(solutions, vec![
+            to_strings(&["A", "B", "C", "DE"]),
+            to_strings(&["A", "B", "CD"]),
+            to_strings(&["A", "BD", "C"]),
+            to_strings(&["AB", "C", "DE"]),
+            to_strings(&["AB", "CD"]),
+            to_strings(&["AC", "B", "DE"]),
+            to_strings(&["AC", "BD"]),
+        ]);
+    }
+
+    #[test]
+    fn test_zdd_without_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "BC", "AC"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let zdd = exact_cover_problem.build_zdd();
+
+        assert_eq!(zdd.count_solutions(), 0);
+        assert_eq!(zdd.solutions().count(), 0);
+    }
+
+    #[test]
+    fn test_zdd_includes_required_options() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+            ("C".to_string(), vec!["C".to_string(), "CD".to_string()]),
+            ("D".to_string(), vec!["D".to_string(), "CD".to_string()]),
+        ]);
+        let required_items = vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()];
+        let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], vec!["AB".to_string()], covered_by).unwrap();
+
+        let zdd = exact_cover_problem.build_zdd();
+
+        assert_eq!(zdd.count_solutions(), 2);
+        assert!(zdd.solutions().all(|solution| solution.selected_options.contains(&"AB".to_string())));
+    }
+
+    #[test]
+    fn test_zdd_counts_domino_tilings() {
+        // Every cell of an 8x8 board is an item and every domino an option; there are 12,988,816 tilings, which would
+        // take a long time to enumerate one by one
+        let size = 8;
+        let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+        for row in 0..size {
+            for col in 0..size {
+                covered_by.insert(format!("r{}c{}", row, col), Vec::new());
+            }
+        }
+        for row in 0..size {
+            for col in 0..size {
+                for (other_row, other_col) in [(row + 1, col), (row, col + 1)] {
+                    if other_row < size && other_col < size {
+                        let option_name = format!("r{}c{}-r{}c{}", row, col, other_row, other_col);
+                        covered_by.get_mut(&format!("r{}c{}", row, col)).unwrap().push(option_name.clone());
+                        covered_by.get_mut(&format!("r{}c{}", other_row, other_col)).unwrap().push(option_name);
+                    }
+                }
+            }
+        }
+        let required_items: Vec<String> = covered_by.keys().cloned().collect();
+        let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+
+        let zdd = exact_cover_problem.build_zdd();
+
+        assert_eq!(zdd.count_solutions(), 12_988_816);
+        assert!(zdd.num_nodes() < 1_000_000);
+    }
+
+    #[test]
+    fn test_general_engine_counts_past_i32_max() {
+        // Visiting 2^31 solutions one by one takes hours, so the count resumes just below i32::MAX instead
+        let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+        for item in 0..3 {
+            covered_by.insert(format!("i{}", item), vec![format!("i{}a", item), format!("i{}b", item)]);
+        }
+        let required_items: Vec<String> = covered_by.keys().cloned().collect();
+        let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap()
+            .with_bitset_backend(false);
+        let start = i32::MAX as u64 - 2;
+
+        for (limit, expected) in [(i32::MAX as u64 + 3, i32::MAX as u64 + 3), (u64::MAX, i32::MAX as u64 + 6)] {
+            let mut state = exact_cover_problem.new_search_state();
+            let result = ExactCoverResult { last_solution: None, num_solutions: start, error: None };
+
+            let result = exact_cover_problem._solve_into(&mut state, result, limit);
+
+            assert_eq!(result.num_solutions, expected);
+            assert_eq!(result.error, None);
+        }
+        assert_eq!(exact_cover_problem.count_solutions_up_to(i32::MAX as u64 + 1), 8);
+    }
+
+    #[test]
+    fn test_zdd_counts_more_solutions_than_fit_in_32_bits() {
+        // Every item has two options of its own, so there are 2^n solutions: more than i32::MAX for 32 items and more
+        // than u64::MAX for 70
+        for (num_items, expected) in [(32, 1u128 << 32), (70, 1u128 << 70)] {
+            let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+            for item in 0..num_items {
+                covered_by.insert(format!("i{}", item), vec![format!("i{}a", item), format!("i{}b", item)]);
+            }
+            let required_items: Vec<String> = covered_by.keys().cloned().collect();
+            let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+
+            let zdd = exact_cover_problem.build_zdd();
+
+            assert_eq!(zdd.count_solutions(), expected, "{} items", num_items);
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let stats = exact_cover_problem.stats();
+
+        assert_eq!(stats, ProblemStats {
+            num_items: 5,
+            num_optional_items: 1,
+            num_options: 8,
+            num_required_options: 0,
+            min_options_per_item: 1,
+            max_options_per_item: 3,
+            avg_options_per_item: 2.6,
+            density: 0.325,
+        });
+        assert_eq!(stats.to_string(), "Items: 5 (1 optional)\n\
+                                       Options: 8 (0 required)\n\
+                                       Options per item: min 1, max 3, avg 2.60\n\
+                                       Density: 0.3250");
+    }
+
+    #[test]
+    fn test_for_each_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let mut num_solutions = 0;
+        let result: ControlFlow<()> = exact_cover_problem.for_each_solution(|selected_options| {
+            assert!(!selected_options.is_empty());
+            num_solutions += 1;
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, ControlFlow::Continue(()));
+        assert_eq!(num_solutions, 7);
+    }
+
+    #[test]
+    fn test_for_each_solution_stops_early() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let mut num_solutions = 0;
+        let result = exact_cover_problem.for_each_solution(|selected_options| {
+            num_solutions += 1;
+            if num_solutions == 3 {
+                return ControlFlow::Break(selected_options.len());
+            }
+            ControlFlow::Continue(())
+        });
+
+        assert!(matches!(result, ControlFlow::Break(2..=4)));
+        assert_eq!(num_solutions, 3);
+    }
+
+    #[test]
+    fn test_memory_limit_exceeded() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_memory_limit(100);
+
+        let result = exact_cover_problem.try_solve();
+
+        assert_eq!(result.err(), Some(SearchError::MemoryLimitExceeded(100)));
+        assert!(exact_cover_problem.solve().is_none());
+    }
+
+    #[test]
+    fn test_memory_limit_not_exceeded() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_memory_limit(1_000_000);
+
+        let result = exact_cover_problem.try_solve();
+
+        assert!(result.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_random_problem_is_reproducible() {
+        let problem = ExactCoverProblem::random(10, 20, 0.3, 42);
+        let same_problem = ExactCoverProblem::random(10, 20, 0.3, 42);
+
+        assert_eq!(problem.stats(), same_problem.stats());
+        assert_eq!(problem.stats().num_items, 10);
+        assert_eq!(problem.stats().num_options, 20);
+    }
+
+    #[test]
+    fn test_random_problems_agree_between_solvers() {
+        // Count the solutions of many small random problems in several independent ways
+        for seed in 0..50 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let num_solutions = problem.count_all_solutions();
+
+            let mut num_visited = 0;
+            let _: ControlFlow<()> = problem.for_each_solution(|_| {
+                num_visited += 1;
+                ControlFlow::Continue(())
+            });
+
+            assert_eq!(num_visited, num_solutions, "seed {}", seed);
+            assert_eq!(problem.build_zdd().count_solutions(), num_solutions as u128, "seed {}", seed);
+            assert_eq!(problem.solve().is_some(), num_solutions > 0, "seed {}", seed);
+            assert_eq!(problem.explain_unsatisfiability().is_none(), num_solutions > 0, "seed {}", seed);
+            let problem = problem.with_forced_option_propagation(true);
+            assert_eq!(problem.count_all_solutions(), num_solutions, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_solve_streaming() {
+        let problem = ExactCoverProblem::random(12, 30, 0.2, 0);
+        let num_solutions = problem.count_all_solutions();
+
+        let receiver = problem.solve_streaming();
+
+        assert_eq!(receiver.iter().filter(|solution| solution.is_ok()).count() as u64, num_solutions);
+    }
+
+    #[test]
+    fn test_solve_streaming_sends_search_error() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_memory_limit(100);
+
+        let messages: Vec<Result<ExactCoverSolution, SearchError>> = exact_cover_problem.solve_streaming().iter().collect();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].as_ref().err(), Some(&SearchError::MemoryLimitExceeded(100)));
+    }
+
+    #[test]
+    fn test_sample_solution_is_uniform() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+        let zdd = exact_cover_problem.build_zdd();
+
+        let mut frequencies: HashMap<Vec<String>, u32> = HashMap::new();
+        for seed in 0..7000 {
+            let mut selected_options = zdd.sample_solution(seed).unwrap().selected_options;
+            selected_options.sort();
+            *frequencies.entry(selected_options).or_default() += 1;
+        }
+
+        // Each of the 7 solutions is expected about 1000 times
+        assert_eq!(frequencies.len(), 7);
+        assert!(frequencies.values().all(|frequency| (850..1150).contains(frequency)), "{:?}", frequencies);
+    }
+
+    #[test]
+    fn test_sample_solution_without_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "BC", "AC"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        assert!(exact_cover_problem.sample_solution(0).is_none());
+    }
+
+    #[test]
+    fn test_solve_min_cost() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let option_costs = HashMap::from([
+            ("AB".to_string(), 5),
+            ("CD".to_string(), 5),
+            ("AC".to_string(), 3),
+            ("BD".to_string(), 4),
+            ("A".to_string(), 1),
+            ("B".to_string(), 2),
+            ("C".to_string(), 2),
+            ("DE".to_string(), 2),
+        ]);
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_option_costs(option_costs).unwrap();
+
+        let solution = exact_cover_problem.solve_min_cost().unwrap();
+
+        assert_eq!(solution.cost, 7);
+        assert_eq_ignore_order(&solution.selected_options, &["A".to_string(), "B".to_string(), "C".to_string(), "DE".to_string()]);
+    }
+
+    #[test]
+    fn test_solve_min_cost_fewest_options() {
+        // With every option costing 1, the cheapest solution is the one with the fewest options
+        for seed in 0..20 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let option_costs = (0..30).map(|option_idx| (format!("o{}", option_idx), 1)).collect();
+            let problem = problem.with_option_costs(option_costs).unwrap();
+
+            let mut fewest_options: Option<u64> = None;
+            let _: ControlFlow<()> = problem.for_each_solution(|selected_options| {
+                let num_options = selected_options.len() as u64;
+                fewest_options = Some(fewest_options.map_or(num_options, |fewest| fewest.min(num_options)));
+                ControlFlow::Continue(())
+            });
+
+            assert_eq!(problem.solve_min_cost().map(|solution| solution.cost), fewest_options, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_cost_of_unknown_option() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A"],
+            optional_items: vec![],
+            options: vec!["A"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let result = exact_cover_problem.with_option_costs(HashMap::from([("B".to_string(), 1)]));
+
+        assert_eq!(result.err(), Some(InvalidProblemError::UnknownCostOption("B".to_string())));
+    }
+
+    #[test]
+    fn test_back_to_back_calls_on_same_problem() {
+        // The search state lives outside the problem, so every call starts from the same clean problem definition
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let sorted = |selected_options: &[String]| {
+            let mut selected_options = selected_options.to_vec();
+            selected_options.sort();
+            selected_options
+        };
+
+        let first_solution = sorted(&exact_cover_problem.solve().unwrap().selected_options);
+        assert_eq!(exact_cover_problem.count_all_solutions(), 7);
+        assert_eq!(exact_cover_problem.count_solutions_up_to(3), 3);
+        let mut all_solutions: Vec<Vec<String>> = Vec::new();
+        let _: ControlFlow<()> = exact_cover_problem.for_each_solution(|selected_options| {
+            all_solutions.push(sorted(selected_options));
+            ControlFlow::Continue(())
+        });
+        assert_eq!(all_solutions.len(), 7);
+        assert_eq!(exact_cover_problem.solve_max_cover().uncovered_items.len(), 0);
+        assert_eq!(exact_cover_problem.explain_unsatisfiability(), None);
+        assert_eq!(exact_cover_problem.count_all_solutions(), 7);
+        // Options are tried in hash order, so a later call may find a different (but still valid) solution
+        let last_solution = sorted(&exact_cover_problem.solve().unwrap().selected_options);
+        assert!(all_solutions.contains(&first_solution));
+        assert!(all_solutions.contains(&last_solution));
+    }
+
+    #[test]
+    fn test_back_to_back_calls_on_problem_without_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["1", "2", "3", "4", "5", "6", "7"],
+            optional_items: vec![],
+            options: vec!["147", "14", "457", "356", "2367", "26"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        for _ in 0..3 {
+            assert!(exact_cover_problem.solve().is_none());
+            assert_eq!(exact_cover_problem.count_all_solutions(), 0);
+        }
+    }
+
+    #[test]
+    fn test_bitset_backend_agrees_with_general_engine() {
+        let all_solutions = |problem: &ExactCoverProblem| {
+            let mut all_solutions: Vec<Vec<String>> = Vec::new();
+            let _: ControlFlow<()> = problem.for_each_solution(|selected_options| {
+                let mut selected_options = selected_options.to_vec();
+                selected_options.sort();
+                all_solutions.push(selected_options);
+                ControlFlow::Continue(())
+            });
+            all_solutions.sort();
+            all_solutions
+        };
+
+        for seed in 0..50 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            assert!(problem.bitset.is_some());
+            let solutions = all_solutions(&problem);
+            let num_solutions = problem.count_all_solutions();
+            let problem = problem.with_bitset_backend(false);
+            assert_eq!(solutions, all_solutions(&problem), "seed {}", seed);
+            assert_eq!(num_solutions, problem.count_all_solutions(), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_bitset_backend_with_optional_items_and_required_options() {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string(), "AC".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+            ("C".to_string(), vec!["AC".to_string(), "C".to_string()]),
+            ("D".to_string(), vec!["DE".to_string()]),
+            ("E".to_string(), vec!["DE".to_string(), "E".to_string()]),
+        ]);
+        let required_items = vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()];
+        let exact_cover_problem =
+            ExactCoverProblem::new(required_items, vec!["E".to_string()], vec!["DE".to_string()], covered_by).unwrap();
+
+        // With DE selected, A, B and C remain: AB + C, AC + B or A + B + C
+        assert_eq!(exact_cover_problem.count_all_solutions(), 3);
+        assert_eq!(exact_cover_problem.count_solutions_up_to(2), 2);
+        let solution = exact_cover_problem.solve().unwrap();
+        assert!(solution.selected_options.contains(&"DE".to_string()));
+        assert!(!solution.selected_options.contains(&"E".to_string()));
+    }
+
+    #[test]
+    fn test_bitset_backend_not_used_for_large_problems() {
+        assert!(ExactCoverProblem::random(128, 10, 0.1, 0).bitset.is_some());
+        assert!(ExactCoverProblem::random(129, 10, 0.1, 0).bitset.is_none());
+    }
+
+    /**
+     * The domino tilings of a board with the given number of rows and columns, with the mirror symmetry which swaps
+     * the left and right side of the board.
+     */
+    fn get_domino_tilings_with_mirror(rows: usize, cols: usize) -> ExactCoverProblem {
+        let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+        let mut mirror: HashMap<String, String> = HashMap::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                covered_by.insert(format!("r{}c{}", row, col), Vec::new());
+            }
+        }
+        for row in 0..rows {
+            for col in 0..cols {
+                for (other_row, other_col) in [(row + 1, col), (row, col + 1)] {
+                    if other_row < rows && other_col < cols {
+                        let option_name = format!("r{}c{}-r{}c{}", row, col, other_row, other_col);
+                        covered_by.get_mut(&format!("r{}c{}", row, col)).unwrap().push(option_name.clone());
+                        covered_by.get_mut(&format!("r{}c{}", other_row, other_col)).unwrap().push(option_name.clone());
+                        // Mirroring swaps the cells of a horizontal domino, so its image starts at the mirrored right cell
+                        let mirrored_name = format!("r{}c{}-r{}c{}", row, cols - 1 - other_col, other_row, cols - 1 - col);
+                        mirror.insert(option_name, mirrored_name);
+                    }
+                }
+            }
+        }
+        let required_items: Vec<String> = covered_by.keys().cloned().collect();
+        return ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap()
+            .with_symmetries(vec![mirror]).unwrap();
+    }
+
+    #[test]
+    fn test_count_solutions_up_to_symmetry() {
+        // The 3 tilings of a 2x3 board are three vertical dominoes, which is its own mirror image, and a vertical domino
+        // on the left or on the right side, which are mirror images of each other
+        let counts = get_domino_tilings_with_mirror(2, 3).count_solutions_up_to_symmetry();
+        assert_eq!(counts, SymmetryCounts { num_classes: 2, num_solutions: 3 });
+
+        for (rows, cols) in [(3, 4), (4, 4), (4, 5)] {
+            let exact_cover_problem = get_domino_tilings_with_mirror(rows, cols);
+
+            let counts = exact_cover_problem.count_solutions_up_to_symmetry();
+
+            // Every class has one or two tilings
+            assert_eq!(counts.num_solutions, exact_cover_problem.count_all_solutions(), "{}x{}", rows, cols);
+            assert!(counts.num_classes < counts.num_solutions, "{}x{}", rows, cols);
+            assert!(2 * counts.num_classes >= counts.num_solutions, "{}x{}", rows, cols);
+        }
+    }
+
+    #[test]
+    fn test_for_each_solution_up_to_symmetry_stops_early() {
+        let exact_cover_problem = get_domino_tilings_with_mirror(4, 4);
+
+        let mut num_visited = 0;
+        let result = exact_cover_problem.for_each_solution_up_to_symmetry(|_, _| {
+            num_visited += 1;
+            if num_visited == 3 { ControlFlow::Break(num_visited) } else { ControlFlow::Continue(()) }
+        });
+
+        assert_eq!(result, ControlFlow::Break(3));
+    }
+
+    #[test]
+    fn test_invalid_symmetries() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec![],
+            options: vec!["AB", "CD", "A", "B"],
+        };
+
+        let unknown_option = HashMap::from([("AB".to_string(), "BA".to_string())]);
+        let result = convert_to_exact_cover_problem(&basic_example).with_symmetries(vec![unknown_option]);
+        assert_eq!(result.err(), Some(InvalidProblemError::UnknownSymmetryOption("BA".to_string())));
+
+        let not_bijective = HashMap::from([("A".to_string(), "B".to_string())]);
+        let result = convert_to_exact_cover_problem(&basic_example).with_symmetries(vec![not_bijective]);
+        assert_eq!(result.err(), Some(InvalidProblemError::NonBijectiveSymmetry("B".to_string())));
+    }
+
+    #[test]
+    fn test_solve_lexicographically() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_lexicographic_order(true);
+
+        let solution = exact_cover_problem.solve().unwrap();
+
+        assert_eq!(solution.selected_options, vec!["A", "B", "C", "DE"]);
+    }
+
+    #[test]
+    fn test_solve_lexicographically_finds_smallest_solution() {
+        for seed in 0..50 {
+            let exact_cover_problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let mut smallest_solution: Option<Vec<String>> = None;
+            let _: ControlFlow<()> = exact_cover_problem.for_each_solution(|selected_options| {
+                let mut selected_options = selected_options.to_vec();
+                selected_options.sort();
+                if smallest_solution.as_ref().is_none_or(|smallest_solution| selected_options < *smallest_solution) {
+                    smallest_solution = Some(selected_options);
+                }
+                ControlFlow::Continue(())
+            });
+            let exact_cover_problem = exact_cover_problem.with_lexicographic_order(true);
+
+            let solution = exact_cover_problem.solve().map(|solution| solution.selected_options);
+
+            assert_eq!(solution, smallest_solution, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_solve_lexicographically_skips_options_of_only_optional_items() {
+        // "A" sorts first but only covers the optional item, so the search never selects it
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["B", "C"],
+            optional_items: vec!["A"],
+            options: vec!["A", "BC", "B", "C"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+        let mut solutions: Vec<Vec<String>> = Vec::new();
+        let _: ControlFlow<()> = exact_cover_problem.for_each_solution(|selected_options| {
+            let mut selected_options = selected_options.to_vec();
+            selected_options.sort();
+            solutions.push(selected_options);
+            ControlFlow::Continue(())
+        });
+        solutions.sort();
+        let exact_cover_problem = exact_cover_problem.with_lexicographic_order(true);
+
+        let solution = exact_cover_problem.solve().unwrap().selected_options;
+
+        assert_eq!(solutions, vec![vec!["B", "C"], vec!["BC"]]);
+        assert_eq!(solution, solutions[0]);
+    }
+
+    #[test]
+    fn test_count_solutions_with_stats() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["1", "2", "3", "4", "5", "6", "7"],
+            optional_items: vec![],
+            options: vec!["147", "14", "457", "356", "2367", "26"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let (num_solutions, stats) = exact_cover_problem.count_solutions_with_stats(u64::MAX).unwrap();
+
+        assert_eq!(num_solutions, 0);
+        assert_eq!(stats.num_forced_options, 0);
+        assert!(stats.num_nodes > 0);
+        assert!(stats.num_backtracks > 0);
+    }
+
+    #[test]
+    fn test_count_solutions_with_stats_after_propagation() {
+        // Every item has a single option, so propagation solves the problem without branching
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec![],
+            options: vec!["AB", "C", "D"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_forced_option_propagation(true);
+
+        let (num_solutions, stats) = exact_cover_problem.count_solutions_with_stats(2).unwrap();
+
+        assert_eq!(num_solutions, 1);
+        assert_eq!(stats, SearchStats { num_forced_options: 3, num_nodes: 0, num_backtracks: 0, memory: None });
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        // The example from Knuth's Dancing Links paper, whose unique solution is rows 0, 3 and 4
+        let rows = vec![
+            vec![false, false, true, false, true, true, false],
+            vec![true, false, false, true, false, false, true],
+            vec![false, true, true, false, false, true, false],
+            vec![true, false, false, true, false, false, false],
+            vec![false, true, false, false, false, false, true],
+            vec![false, false, false, true, true, false, true],
+        ];
+
+        let exact_cover_problem = ExactCoverProblem::from_matrix(&rows).unwrap();
+
+        let mut selected_options = exact_cover_problem.solve().unwrap().selected_options;
+        selected_options.sort();
+        assert_eq!(selected_options, vec!["o0", "o3", "o4"]);
+        assert_eq!(exact_cover_problem.count_all_solutions(), 1);
+        assert_eq!(exact_cover_problem.to_matrix(), rows);
+    }
+
+    #[test]
+    fn test_matrix_roundtrip_keeps_order() {
+        // With more than 10 rows and columns the names must be padded to sort in matrix order
+        let rows: Vec<Vec<bool>> = (0..12)
+            .map(|option_idx| (0..11).map(|item_idx| (option_idx + item_idx) % 3 == 0).collect())
+            .collect();
+
+        let exact_cover_problem = ExactCoverProblem::from_matrix(&rows).unwrap();
+
+        assert_eq!(exact_cover_problem.to_matrix(), rows);
+    }
+
+    #[test]
+    fn test_from_matrix_inconsistent_row_length() {
+        let rows = vec![vec![true, false], vec![true], vec![false, true]];
+
+        let result = ExactCoverProblem::from_matrix(&rows);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::InconsistentRowLength(1)));
+    }
+
+    #[test]
+    fn test_diagnose_solution() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec!["E"],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "DE"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+        let diagnose = |selected_options: &[&str]| {
+            let selected_options: Vec<String> = selected_options.iter().map(|option_name| option_name.to_string()).collect();
+            exact_cover_problem.diagnose_solution(&selected_options)
+        };
+
+        assert_eq!(diagnose(&["AB", "CD"]), vec![]);
+        assert_eq!(diagnose(&["AB", "CE"]), vec![
+            EncodingIssue::UnknownOption("CE".to_string()),
+            EncodingIssue::UncoveredItem("C".to_string()),
+            EncodingIssue::UncoveredItem("D".to_string()),
+        ]);
+        assert_eq!(diagnose(&["AB", "AC", "DE"]), vec![
+            EncodingIssue::OvercoveredItem { item: "A".to_string(), options: vec!["AB".to_string(), "AC".to_string()] },
+        ]);
+        assert_eq!(diagnose(&["AB", "AC", "DE"])[0].to_string(), "item A is covered by more than one option: AB, AC");
+
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+        ]);
+        let required_items = vec!["A".to_string(), "B".to_string()];
+        let exact_cover_problem = ExactCoverProblem::new(required_items, vec![], vec!["AB".to_string()], covered_by).unwrap();
+        let issues = exact_cover_problem.diagnose_solution(&["A".to_string(), "B".to_string()]);
+        assert_eq!(issues, vec![EncodingIssue::MissingRequiredOption("AB".to_string())]);
+    }
+
+    #[test]
+    fn test_option_payloads() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B"],
+            optional_items: vec![],
+            options: vec!["AB", "A", "B"],
+        };
+        let payloads = HashMap::from([("AB".to_string(), 2usize), ("A".to_string(), 1), ("B".to_string(), 1)]);
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_option_payloads(payloads).unwrap();
+
+        let solution = exact_cover_problem.solve().unwrap();
+
+        let payloads: Vec<&usize> = exact_cover_problem.solution_payloads(&solution);
+        assert_eq!(payloads.len(), solution.selected_options.len());
+        assert_eq!(payloads.into_iter().sum::<usize>(), 2);
+        assert_eq!(exact_cover_problem.option_payload::<usize>("AB"), Some(&2));
+        assert_eq!(exact_cover_problem.option_payload::<String>("AB"), None);
+    }
+
+    #[test]
+    fn test_payload_of_unknown_option() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A"],
+            optional_items: vec![],
+            options: vec!["A"],
+        };
+        let payloads = HashMap::from([("B".to_string(), ())]);
+
+        let result = convert_to_exact_cover_problem(&basic_example).with_option_payloads(payloads);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::UnknownPayloadOption("B".to_string())));
+    }
+
+    /**
+     * Observer which records every step of the search as a line of text.
+     */
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Vec<String>,
+    }
+
+    impl Observer for RecordingObserver {
+        fn on_item_selected(&mut self, item_name: &str, options: &[String]) {
+            self.events.push(format!("item {} with {} options", item_name, options.len()));
+        }
+
+        fn on_option_tried(&mut self, item_name: &str, option_name: &str) {
+            self.events.push(format!("try {} for {}", option_name, item_name));
+        }
+
+        fn on_backtrack(&mut self, item_name: &str, option_name: &str) {
+            self.events.push(format!("backtrack {} for {}", option_name, item_name));
+        }
+
+        fn on_solution(&mut self, selected_options: &[String]) {
+            self.events.push(format!("solution {}", selected_options.len()));
+        }
+    }
+
+    /**
+     * Observer which only counts the solutions, relying on the default callbacks for the other steps.
+     */
+    #[derive(Default)]
+    struct SolutionCounter {
+        num_solutions: u64,
+    }
+
+    impl Observer for SolutionCounter {
+        fn on_solution(&mut self, _selected_options: &[String]) {
+            self.num_solutions += 1;
+        }
+    }
+
+    #[test]
+    fn test_for_each_solution_with_observers() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec![],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "D"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+        let mut recorder = RecordingObserver::default();
+        let mut counter = SolutionCounter::default();
+
+        let mut num_solutions = 0;
+        let result = exact_cover_problem.for_each_solution_with_observers(&mut [&mut recorder, &mut counter], |_| {
+            num_solutions += 1;
+            ControlFlow::<()>::Continue(())
+        });
+
+        assert_eq!(result, Ok(ControlFlow::Continue(())));
+
+        assert_eq!(num_solutions, exact_cover_problem.count_all_solutions());
+        assert_eq!(counter.num_solutions, num_solutions);
+        let count_events = |prefix: &str| recorder.events.iter().filter(|event| event.starts_with(prefix)).count();
+        assert_eq!(count_events("solution") as u64, num_solutions);
+        assert_eq!(count_events("try"), count_events("backtrack"));
+        let (_, stats) = exact_cover_problem.count_solutions_with_stats(u64::MAX).unwrap();
+        assert_eq!(count_events("try") as u64, stats.num_nodes);
+        assert!(recorder.events[0].starts_with("item"));
+    }
+
+    #[test]
+    fn test_for_each_solution_with_observers_exceeding_memory_limit() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D", "E", "F", "G"],
+            optional_items: vec![],
+            options: vec!["CEF", "ADG", "BCF", "AD", "BG", "DEG"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_memory_limit(100);
+        let mut counter = SolutionCounter::default();
+
+        let result = exact_cover_problem.for_each_solution_with_observers(&mut [&mut counter], |_| ControlFlow::<()>::Continue(()));
+
+        assert_eq!(result, Err(SearchError::MemoryLimitExceeded(100)));
+    }
+
+    #[test]
+    fn test_try_solve_with_observers() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C", "D"],
+            optional_items: vec![],
+            options: vec!["AB", "CD", "AC", "BD", "A", "B", "C", "D"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+        let mut first = RecordingObserver::default();
+        let mut second = RecordingObserver::default();
+
+        let solution = exact_cover_problem.try_solve_with_observers(&mut [&mut first, &mut second]).unwrap();
+
+        assert!(solution.is_some());
+        assert_eq!(first.events, second.events);
+        assert_eq!(first.events.iter().filter(|event| event.starts_with("solution")).count(), 1);
+        // The search stops after the first solution, but still unselects every option it tried
+        assert!(first.events.last().unwrap().starts_with("backtrack"));
+    }
+
+    #[test]
+    fn test_simplify_removes_dominated_option() {
+        // Both options covering A also cover B, so BC would leave no way to cover A
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "ABC", "BC", "C"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let (simplified, report) = exact_cover_problem.simplify();
+
+        assert_eq!(report, SimplificationReport {
+            dominated_options: vec!["BC".to_string()],
+            conflicting_options: vec![],
+            forced_items: vec![],
+        });
+        assert_eq!(simplified.stats().num_options, 3);
+        assert_eq!(simplified.count_all_solutions(), 2);
+    }
+
+    #[test]
+    fn test_simplify_selects_forced_options() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["A", "AB", "BC"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        let (simplified, report) = exact_cover_problem.simplify();
+
+        assert_eq!(report.forced_items, vec![("A".to_string(), "A".to_string()), ("C".to_string(), "BC".to_string())]);
+        assert_eq!(report.conflicting_options, vec!["AB".to_string()]);
+        assert_eq!(simplified.stats().num_required_options, 2);
+        assert_eq_ignore_order(&simplified.solve().unwrap().selected_options, &["A".to_string(), "BC".to_string()]);
+    }
+
+    #[test]
+    fn test_simplify_keeps_solutions() {
+        for seed in 0..50 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let mut solutions: Vec<Vec<String>> = Vec::new();
+            let _: ControlFlow<()> = problem.for_each_solution(|selected_options| {
+                let mut selected_options = selected_options.to_vec();
+                selected_options.sort();
+                solutions.push(selected_options);
+                ControlFlow::Continue(())
+            });
+            solutions.sort();
+
+            let (simplified, _) = problem.simplify();
+
+            let mut simplified_solutions: Vec<Vec<String>> = Vec::new();
+            let _: ControlFlow<()> = simplified.for_each_solution(|selected_options| {
+                let mut selected_options = selected_options.to_vec();
+                selected_options.sort();
+                simplified_solutions.push(selected_options);
+                ControlFlow::Continue(())
+            });
+            simplified_solutions.sort();
+            assert_eq!(simplified_solutions, solutions, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_solve_with_restarts() {
+        for seed in 0..50 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed).with_restarts(Some(1));
+            let num_solutions = problem.count_all_solutions();
+
+            let solution = problem.try_solve().unwrap();
+
+            assert_eq!(solution.is_some(), num_solutions > 0, "seed {}", seed);
+            if let Some(solution) = solution {
+                assert_eq!(problem.diagnose_solution(&solution.selected_options), vec![], "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_backends_find_the_same_solutions() {
+        let backends: [&dyn SolverBackend; 3] = [&GeneralBackend, &BitsetBackend, &DancingCellsBackend];
+        for seed in 0..50 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let mut solutions_per_backend: Vec<Vec<Vec<String>>> = Vec::new();
+            for backend in backends {
+                let mut solutions: Vec<Vec<String>> = Vec::new();
+                backend.visit_solutions(&problem, &mut |selected_options| {
+                    let mut selected_options = selected_options.to_vec();
+                    selected_options.sort();
+                    solutions.push(selected_options);
+                    ControlFlow::Continue(())
+                }).unwrap();
+                solutions.sort();
+                solutions_per_backend.push(solutions);
+            }
+
+            assert_eq!(solutions_per_backend[1], solutions_per_backend[0], "seed {}", seed);
+            assert_eq!(solutions_per_backend[2], solutions_per_backend[0], "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_backends_report_their_search_stats() {
+        let problem = ExactCoverProblem::random(12, 30, 0.2, 3);
+        let (num_solutions, general_stats) = problem.count_solutions_with_stats(u64::MAX).unwrap();
+        assert!(num_solutions > 0);
+
+        let backends: [&dyn SolverBackend; 3] = [&GeneralBackend, &BitsetBackend, &DancingCellsBackend];
+        for backend in backends {
+            let stats = backend.visit_solutions(&problem, &mut |_| ControlFlow::Continue(())).unwrap();
+            let (solution, solve_stats) = problem.try_solve_with_backend_and_stats(backend).unwrap();
+
+            // Every solution is a leaf of the search tree below at least one node
+            assert!(stats.num_nodes >= num_solutions, "{}", backend.name());
+            assert!(solve_stats.num_nodes > 0 && solve_stats.num_nodes <= stats.num_nodes, "{}", backend.name());
+            assert!(solution.is_some(), "{}", backend.name());
+        }
+        let general_stats_by_backend = GeneralBackend.visit_solutions(&problem, &mut |_| ControlFlow::Continue(())).unwrap();
+        assert_eq!(general_stats_by_backend, general_stats);
+    }
+
+    #[test]
+    fn test_dancing_cells_with_optional_items_and_required_options() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec!["D"],
+            options: vec!["AB", "ABD", "CD", "C", "A", "BD"],
+        };
+        let exact_cover_problem = ExactCoverProblem::new(
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            vec!["D".to_string()],
+            vec!["C".to_string()],
+            convert_to_exact_cover_problem(&basic_example).covered_by,
+        ).unwrap();
+
+        let num_solutions = exact_cover_problem.count_solutions_with_backend(&DancingCellsBackend, u64::MAX).unwrap();
+        let solution = exact_cover_problem.try_solve_with_backend(&DancingCellsBackend).unwrap().unwrap();
+
+        // With C required, the solutions are {C, AB}, {C, ABD} and {C, A, BD}
+        assert_eq!(num_solutions, 3);
+        assert_eq!(num_solutions, exact_cover_problem.count_all_solutions());
+        assert_eq!(exact_cover_problem.diagnose_solution(&solution.selected_options), vec![]);
+        assert_eq!(DancingCellsBackend.name(), "dancing cells");
+    }
+
+    #[test]
+    fn test_prerequisites() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B"],
+            optional_items: vec![],
+            options: vec!["AB", "A", "B"],
+        };
+        let prerequisites = vec![("A".to_string(), "AB".to_string())];
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_prerequisites(prerequisites).unwrap();
+
+        // A can't be selected together with AB, so requiring AB rules out the solution {A, B}
+        let solution = exact_cover_problem.solve().unwrap();
+
+        assert_eq!(exact_cover_problem.count_all_solutions(), 1);
+        assert_eq!(exact_cover_problem.without_auxiliary_options(&solution.selected_options), vec!["AB".to_string()]);
+        assert!(exact_cover_problem.is_auxiliary_option("A requires AB (without AB)"));
+    }
+
+    #[test]
+    fn test_prerequisites_keep_solutions_that_satisfy_them() {
+        for seed in 0..50 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let mut option_names: Vec<String> = problem.covers.keys().cloned().collect();
+            option_names.sort();
+            let prerequisites: Vec<(String, String)> = (0..4)
+                .map(|idx| (option_names[idx].clone(), option_names[idx + 4].clone()))
+                .collect();
+            let mut expected_solutions: Vec<Vec<String>> = Vec::new();
+            let _: ControlFlow<()> = problem.for_each_solution(|selected_options| {
+                let satisfied = prerequisites.iter().all(|(option_name, prerequisite_name)| {
+                    !selected_options.contains(option_name) || selected_options.contains(prerequisite_name)
+                });
+                if satisfied {
+                    let mut selected_options = selected_options.to_vec();
+                    selected_options.sort();
+                    expected_solutions.push(selected_options);
+                }
+                ControlFlow::Continue(())
+            });
+            expected_solutions.sort();
+
+            let problem = problem.with_prerequisites(prerequisites).unwrap();
+
+            let mut solutions: Vec<Vec<String>> = Vec::new();
+            let _: ControlFlow<()> = problem.for_each_solution(|selected_options| {
+                let mut selected_options = problem.without_auxiliary_options(selected_options);
+                selected_options.sort();
+                solutions.push(selected_options);
+                ControlFlow::Continue(())
+            });
+            solutions.sort();
+            assert_eq!(solutions, expected_solutions, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_prerequisite_of_unknown_option() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A"],
+            optional_items: vec![],
+            options: vec!["A"],
+        };
+
+        let result = convert_to_exact_cover_problem(&basic_example).with_prerequisites(vec![("A".to_string(), "B".to_string())]);
+
+        assert_eq!(result.err(), Some(InvalidProblemError::UnknownPrerequisiteOption("B".to_string())));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["B", "A"],
+            optional_items: vec!["C"],
+            options: vec!["AB", "BC", "A"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example);
+
+        assert_eq!(exact_cover_problem.items(), vec!["A", "B", "C"]);
+        assert_eq!(exact_cover_problem.options(), vec!["A", "AB", "BC"]);
+        assert_eq!(exact_cover_problem.options_covering("B"), Some(&["AB".to_string(), "BC".to_string()][..]));
+        assert_eq!(exact_cover_problem.items_covered_by("AB"), Some(&["B".to_string(), "A".to_string()][..]));
+        assert_eq!(exact_cover_problem.options_covering("D"), None);
+        assert_eq!(exact_cover_problem.items_covered_by("D"), None);
+    }
+
+    #[test]
+    fn test_solution_multiplicity() {
+        let unique = convert_to_exact_cover_problem(&BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "AC", "C"],
+        });
+        let multiple = convert_to_exact_cover_problem(&BasicExampleProblem {
+            required_items: vec!["A", "B"],
+            optional_items: vec![],
+            options: vec!["AB", "A", "B"],
+        });
+        let none = convert_to_exact_cover_problem(&BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "BC", "AC"],
+        });
+
+        match unique.solution_multiplicity(2) {
+            SolutionMultiplicity::Unique(solution) => assert_eq_ignore_order(&solution.selected_options, &["AB".to_string(), "C".to_string()]),
+            other => panic!("expected a unique solution, got {:?}", other),
+        }
+        assert!(matches!(multiple.solution_multiplicity(0), SolutionMultiplicity::Multiple(2)));
+        assert!(matches!(none.solution_multiplicity(2), SolutionMultiplicity::None));
+    }
+
+    #[test]
+    fn test_solution_multiplicity_agrees_with_count() {
+        for seed in 0..20 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+
+            let num_solutions = problem.count_solutions_up_to(5);
+
+            match problem.solution_multiplicity(5) {
+                SolutionMultiplicity::None => assert_eq!(num_solutions, 0, "seed {}", seed),
+                SolutionMultiplicity::Unique(_) => assert_eq!(num_solutions, 1, "seed {}", seed),
+                SolutionMultiplicity::Multiple(count) => assert_eq!(num_solutions, count, "seed {}", seed),
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_solutions_with_memory_tracking() {
+        let problem = ExactCoverProblem::random(12, 30, 0.2, 0).with_memory_tracking(true);
+
+        let (num_solutions, stats) = problem.count_solutions_with_stats(u64::MAX).unwrap();
+        let memory: MemoryStats = stats.memory.unwrap();
+
+        assert_eq!(num_solutions, problem.count_all_solutions());
+        assert!(memory.items_queue_bytes > 0);
+        assert!(memory.available_options_bytes > 0);
+        assert!(memory.peak_stack_bytes > 0);
+        assert_eq!(memory.peak_bytes, memory.items_queue_bytes + memory.available_options_bytes + memory.peak_stack_bytes);
+    }
+
+    #[test]
+    fn test_count_solutions_without_memory_tracking() {
+        let problem = ExactCoverProblem::random(12, 30, 0.2, 0);
+
+        let (_, stats) = problem.count_solutions_with_stats(u64::MAX).unwrap();
+
+        assert_eq!(stats.memory, None);
+    }
+
+    #[test]
+    fn test_chrome_trace() {
+        let basic_example = BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "A", "BC", "C"],
+        };
+        let exact_cover_problem = convert_to_exact_cover_problem(&basic_example).with_bitset_backend(false);
+        let mut trace = ChromeTrace::new();
+
+        let result = exact_cover_problem.for_each_solution_with_observers(&mut [&mut trace], |_| ControlFlow::<()>::Continue(()));
+        let mut json: Vec<u8> = Vec::new();
+        trace.write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        assert!(result.unwrap().is_continue());
+        assert!(trace.num_events() > 0);
+        assert_eq!(json.matches("\"ph\":\"B\"").count(), json.matches("\"ph\":\"E\"").count());
+        assert_eq!(json.matches("\"name\":\"solution\"").count(), 2);
+        assert!(json.ends_with("]}\n"));
+    }
+
+    #[test]
+    fn test_solve_with_solution_filter() {
+        let problem = convert_to_exact_cover_problem(&BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "A", "B", "C", "BC"],
+        }).with_solution_filter(|selected_options| !selected_options.contains(&"AB".to_string()));
+
+        let solution = problem.solve().unwrap();
+
+        assert!(problem.has_solution_filters());
+        assert!(!solution.selected_options.contains(&"AB".to_string()));
+        assert_eq!(problem.count_all_solutions(), 2);
+    }
+
+    #[test]
+    fn test_solution_filters_accumulate() {
+        let problem = convert_to_exact_cover_problem(&BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["AB", "A", "B", "C", "BC"],
+        })
+            .with_solution_filter(|selected_options| !selected_options.contains(&"AB".to_string()))
+            .with_solution_filter(|selected_options| !selected_options.contains(&"BC".to_string()))
+            .with_lexicographic_order(true);
+
+        let solution = problem.solve().unwrap();
+
+        assert_eq!(solution.selected_options, vec!["A".to_string(), "B".to_string(), "C".to_string()]);
+        assert_eq!(problem.count_all_solutions(), 1);
+    }
+
+    #[test]
+    fn test_for_each_solution_pruned() {
+        // Options o0 and o1 may not be selected together, which can be checked as soon as both are selected
+        let keep = |selected_options: &[String]| !(selected_options.contains(&"o0".to_string()) && selected_options.contains(&"o1".to_string()));
+        for seed in 0..20 {
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed);
+            let mut expected: Vec<Vec<String>> = Vec::new();
+            let _: ControlFlow<()> = problem.for_each_solution(|selected_options| {
+                if keep(selected_options) {
+                    expected.push(selected_options.to_vec());
+                }
+                ControlFlow::Continue(())
+            });
+
+            let mut solutions: Vec<Vec<String>> = Vec::new();
+            let result = problem.for_each_solution_pruned(&mut |selected_options| keep(selected_options), |selected_options| -> ControlFlow<()> {
+                solutions.push(selected_options.to_vec());
+                ControlFlow::Continue(())
+            });
+
+            assert_eq!(result, Ok(ControlFlow::Continue(())), "seed {}", seed);
+            for solution in expected.iter_mut().chain(solutions.iter_mut()) {
+                solution.sort();
+            }
+            expected.sort();
+            solutions.sort();
+            assert_eq!(solutions, expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_for_each_solution_pruned_skips_subtrees() {
+        let problem = convert_to_exact_cover_problem(&BasicExampleProblem {
+            required_items: vec!["A", "B", "C"],
+            optional_items: vec![],
+            options: vec!["A", "B", "C", "AB", "BC"],
+        });
+        let mut num_calls = 0;
+
+        let result = problem.for_each_solution_pruned(&mut |_| {
+            num_calls += 1;
+            false
+        }, |_| ControlFlow::Break(()));
+
+        // Every option of the first item is rejected right away, so the search never goes deeper
+        assert_eq!(result, Ok(ControlFlow::Continue(())));
+        assert_eq!(num_calls, 2);
+    }
+
+    #[test]
+    fn test_solution_filter_agrees_across_backends() {
+        let has_even_length = |selected_options: &[String]| selected_options.len() & 1 == 0;
+        for seed in 0..20 {
+            let mut expected: u64 = 0;
+            let _: ControlFlow<()> = ExactCoverProblem::random(12, 30, 0.2, seed).for_each_solution(|selected_options| {
+                if has_even_length(selected_options) {
+                    expected += 1;
+                }
+                ControlFlow::Continue(())
+            });
+            let problem = ExactCoverProblem::random(12, 30, 0.2, seed).with_solution_filter(has_even_length);
+
+            assert_eq!(problem.count_all_solutions(), expected, "seed {}", seed);
+            let backends: [&dyn SolverBackend; 3] = [&GeneralBackend, &BitsetBackend, &DancingCellsBackend];
+            for backend in backends {
+                assert_eq!(problem.count_solutions_with_backend(backend, u64::MAX).unwrap(), expected, "seed {} backend {}", seed, backend.name());
+            }
+            match problem.solve() {
+                Some(solution) => assert!(has_even_length(&solution.selected_options), "seed {}", seed),
+                None => assert_eq!(expected, 0, "seed {}", seed),
+            }
+        }
+    }
+}
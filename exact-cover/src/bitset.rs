@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use crate::SearchStats;
+
+/// The largest number of items (required and optional) a problem can have to be solved with the bitset backend
+pub(crate) const MAX_BITSET_ITEMS: usize = 128;
+
+/**
+ * An exact cover problem with at most 128 items, in which every option is a bitmask of the items it covers. Covering
+ * items and checking whether an option is still available are single bit operations, and the search doesn't allocate
+ * beyond its stack, which makes this much faster than the general engine for small problems.
+ */
+pub(crate) struct BitsetProblem {
+    /// The option names, indexed like option_masks
+    option_names: Vec<String>,
+    /// For each option, the bitmask of the items it covers
+    option_masks: Vec<u128>,
+    /// For each item, the indices of the options covering it
+    options_by_item: Vec<Vec<usize>>,
+    /// Bitmask of the required items
+    required_mask: u128,
+    /// The indices of the required options
+    required_options: Vec<usize>,
+}
+
+/**
+ * A level of the search: the item being covered and the next of its options to try.
+ */
+struct BitsetFrame {
+    /// Index of the item that is covered at this level
+    item_idx: usize,
+    /// Index into the options of the item of the next option to try
+    next_option_idx: usize,
+    /// The currently selected option
+    selected_option: Option<usize>,
+}
+
+impl BitsetProblem {
+    /**
+     * Create the bitset representation of a problem, or None if it has too many items.
+     */
+    pub(crate) fn new(
+        item_names: &[String],
+        required_items: &[String],
+        required_options: &[String],
+        covers: &HashMap<String, Vec<String>>) -> Option<BitsetProblem>
+    {
+        if item_names.len() > MAX_BITSET_ITEMS {
+            return None;
+        }
+        let item_indices: HashMap<&String, usize> = item_names.iter().enumerate()
+            .map(|(item_idx, item_name)| (item_name, item_idx))
+            .collect();
+
+        let mut option_names: Vec<String> = covers.keys().cloned().collect();
+        option_names.sort();
+        let mut option_masks: Vec<u128> = Vec::new();
+        let mut options_by_item: Vec<Vec<usize>> = vec![Vec::new(); item_names.len()];
+        for (option_idx, option_name) in option_names.iter().enumerate() {
+            let mut mask = 0u128;
+            for item_name in covers.get(option_name).unwrap() {
+                let item_idx = item_indices[item_name];
+                mask |= 1 << item_idx;
+                options_by_item[item_idx].push(option_idx);
+            }
+            option_masks.push(mask);
+        }
+
+        let required_mask = required_items.iter().fold(0u128, |mask, item_name| mask | (1 << item_indices[item_name]));
+        let required_options = required_options.iter()
+            .map(|option_name| option_names.binary_search(option_name).unwrap())
+            .collect();
+
+        return Some(BitsetProblem {
+            option_names,
+            option_masks,
+            options_by_item,
+            required_mask,
+            required_options,
+        });
+    }
+
+    /**
+     * Call visit with the selected options of every solution until it returns Break.
+     */
+    pub(crate) fn for_each_solution(&self, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> SearchStats {
+        let mut selected_option_names: Vec<String> = Vec::new();
+        return self.visit_solutions(&mut |selected_options| {
+            selected_option_names.clear();
+            selected_option_names.extend(selected_options.iter().map(|option_idx| self.option_names[*option_idx].clone()));
+            visit(&selected_option_names)
+        });
+    }
+
+    /**
+     * Count the solutions, stopping once the given limit is reached.
+     */
+    pub(crate) fn count_solutions_up_to(&self, limit: u64) -> u64 {
+        let mut num_solutions: u64 = 0;
+        if limit == 0 {
+            return num_solutions;
+        }
+        self.visit_solutions(&mut |_| {
+            num_solutions += 1;
+            if num_solutions < limit {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        return num_solutions;
+    }
+
+    /**
+     * Search for solutions and pass the indices of the selected options of each one to visit, until it returns Break.
+     * Like the general engine, this is an iterative depth-first search which covers the item with the fewest available
+     * options first. Returns the statistics of the search.
+     */
+    fn visit_solutions(&self, visit: &mut dyn FnMut(&[usize]) -> ControlFlow<()>) -> SearchStats {
+        let mut stats = SearchStats::default();
+        let mut covered = self.required_options.iter().fold(0u128, |mask, option_idx| mask | self.option_masks[*option_idx]);
+        let mut selected_options: Vec<usize> = self.required_options.clone();
+        let mut stack: Vec<BitsetFrame> = Vec::with_capacity(MAX_BITSET_ITEMS);
+        let mut stopped = false;
+        let mut descend = true;
+        loop {
+            if descend {
+                let uncovered = self.required_mask & !covered;
+                if uncovered == 0 {
+                    stopped = visit(&selected_options).is_break();
+                } else if let Some(item_idx) = self.select_item(uncovered, covered) {
+                    stack.push(BitsetFrame {
+                        item_idx,
+                        next_option_idx: 0,
+                        selected_option: None,
+                    });
+                } else {
+                    stats.num_backtracks += 1;
+                }
+            }
+
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => return stats,
+            };
+
+            if let Some(option_idx) = frame.selected_option.take() {
+                covered &= !self.option_masks[option_idx];
+                selected_options.pop();
+            }
+
+            descend = false;
+            let options = &self.options_by_item[frame.item_idx];
+            while !stopped && frame.next_option_idx < options.len() {
+                let option_idx = options[frame.next_option_idx];
+                frame.next_option_idx += 1;
+                if self.option_masks[option_idx] & covered == 0 {
+                    covered |= self.option_masks[option_idx];
+                    selected_options.push(option_idx);
+                    frame.selected_option = Some(option_idx);
+                    stats.num_nodes += 1;
+                    descend = true;
+                    break;
+                }
+            }
+            if !descend {
+                stack.pop();
+            }
+        }
+    }
+
+    /**
+     * Choose the uncovered required item with the fewest available options, or None if one of them has no options left.
+     */
+    fn select_item(&self, uncovered: u128, covered: u128) -> Option<usize> {
+        let mut best: Option<(usize, usize)> = None;
+        let mut remaining = uncovered;
+        while remaining != 0 {
+            let item_idx = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            let num_options = self.options_by_item[item_idx].iter()
+                .filter(|option_idx| self.option_masks[**option_idx] & covered == 0)
+                .count();
+            if num_options == 0 {
+                return None;
+            }
+            if best.is_none_or(|(_, best_num_options)| num_options < best_num_options) {
+                best = Some((item_idx, num_options));
+            }
+        }
+        return best.map(|(item_idx, _)| item_idx);
+    }
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use log::info;
+use thiserror::Error;
+
+use crate::{ExactCoverProblem, ExactCoverSolution, SearchError};
+
+/// Written to a solution file for problems without a solution
+const NO_SOLUTION: &str = "none";
+
+// CacheError is a custom error type for errors that occur when looking up or storing results in a solution cache.
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("could not access the cache directory")]
+    Io(#[from] io::Error),
+    #[error("the search for the result to cache was aborted")]
+    SearchAborted(#[from] SearchError),
+}
+
+/**
+ * How a solution cache counts the solutions of a problem whose count it hasn't stored yet. Every method counts all
+ * solutions, so a stored count is the same whichever method computed it.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CountMethod {
+    /// Visit every solution with the backtracking search
+    Search,
+    /// Count the solutions of the problem's zero-suppressed decision diagram
+    Zdd,
+}
+
+impl CountMethod {
+    fn count_solutions(&self, problem: &ExactCoverProblem) -> u128 {
+        return match self {
+            CountMethod::Search => problem.count_all_solutions() as u128,
+            CountMethod::Zdd => problem.build_zdd().count_solutions(),
+        };
+    }
+}
+
+/**
+ * A directory of solutions and solution counts of exact cover problems, keyed by the content hash of the problem, so
+ * that repeated runs over the same large problems don't have to search again. Every result is stored in a small text
+ * file named after the hash. Entries that can't be parsed are treated as missing and overwritten. Problems with
+ * solution filters are never cached, since the filters aren't part of the hash.
+ */
+pub struct SolutionCache {
+    dir: PathBuf,
+}
+
+impl ExactCoverProblem {
+    /**
+     * A hash of the problem definition: its items, options and required options. Problems with the same definition
+     * have the same hash regardless of the order in which they were declared, and the hash is stable across runs and
+     * versions, so it can be used as a key for storing results.
+     */
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        for names in [&self.required_items, &self.optional_items, &self.required_options] {
+            let mut names: Vec<&String> = names.iter().collect();
+            names.sort();
+            for name in names {
+                hasher.write_name(name);
+            }
+            hasher.write_separator();
+        }
+
+        let mut option_names: Vec<&String> = self.covers.keys().collect();
+        option_names.sort();
+        for option_name in option_names {
+            hasher.write_name(option_name);
+            let mut item_names: Vec<&String> = self.covers.get(option_name).unwrap().iter().collect();
+            item_names.sort();
+            for item_name in item_names {
+                hasher.write_name(item_name);
+            }
+            hasher.write_separator();
+        }
+        return hasher.0;
+    }
+}
+
+impl SolutionCache {
+    /**
+     * Create a cache which stores its entries in the given directory. The directory is created when the first entry is
+     * stored.
+     */
+    pub fn new(dir: impl Into<PathBuf>) -> SolutionCache {
+        return SolutionCache { dir: dir.into() };
+    }
+
+    /**
+     * Create a cache in the sudoku directory of the user's cache directory, i.e. $XDG_CACHE_HOME/sudoku or
+     * ~/.cache/sudoku. Returns None if neither XDG_CACHE_HOME nor HOME is set.
+     */
+    pub fn in_user_cache_dir() -> Option<SolutionCache> {
+        let cache_dir = match std::env::var_os("XDG_CACHE_HOME") {
+            Some(cache_dir) if !cache_dir.is_empty() => PathBuf::from(cache_dir),
+            _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+        };
+        return Some(SolutionCache::new(cache_dir.join("sudoku")));
+    }
+
+    /**
+     * The number of solutions of the problem, read from the cache or computed with the method and stored. The count
+     * is always the total number of solutions, so it doesn't matter which method stored it.
+     */
+    pub fn count_solutions(&self, problem: &ExactCoverProblem, method: CountMethod) -> Result<u128, CacheError> {
+        if problem.has_solution_filters() {
+            return Ok(method.count_solutions(problem));
+        }
+        let path = self.dir.join(format!("{:016x}.count", problem.content_hash()));
+        if let Some(num_solutions) = read_entry(&path)?.and_then(|contents| contents.trim().parse::<u128>().ok()) {
+            info!("Found solution count in {}", path.display());
+            return Ok(num_solutions);
+        }
+
+        let num_solutions = method.count_solutions(problem);
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, format!("{}\n", num_solutions))?;
+        return Ok(num_solutions);
+    }
+
+    /**
+     * A solution of the problem, read from the cache or found with try_solve and stored. Problems which are solved in
+     * lexicographic order are stored separately, because they can have a different solution.
+     */
+    pub fn solve(&self, problem: &ExactCoverProblem) -> Result<Option<ExactCoverSolution>, CacheError> {
+        if problem.has_solution_filters() {
+            return Ok(problem.try_solve()?);
+        }
+        let extension = if problem.lexicographic_order { "lexicographic.solution" } else { "solution" };
+        let path = self.dir.join(format!("{:016x}.{}", problem.content_hash(), extension));
+        if let Some(contents) = read_entry(&path)? {
+            info!("Found solution in {}", path.display());
+            if contents.trim() == NO_SOLUTION {
+                return Ok(None);
+            }
+            let selected_options: Vec<String> = contents.lines().map(String::from).collect();
+            if selected_options.iter().all(|option_name| problem.covers.contains_key(option_name)) {
+                return Ok(Some(ExactCoverSolution { selected_options }));
+            }
+        }
+
+        let solution = problem.try_solve()?;
+        let contents = match &solution {
+            Some(solution) => solution.selected_options.iter().map(|option_name| format!("{}\n", option_name)).collect(),
+            None => format!("{}\n", NO_SOLUTION),
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, contents)?;
+        return Ok(solution);
+    }
+}
+
+/**
+ * Read a cache entry, or None if it doesn't exist.
+ */
+fn read_entry(path: &PathBuf) -> io::Result<Option<String>> {
+    return match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    };
+}
+
+/**
+ * The 64-bit FNV-1a hash, which unlike the standard library's hasher is guaranteed to stay the same between versions.
+ */
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Fnv1a {
+        return Fnv1a(0xcbf2_9ce4_8422_2325);
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        self.0 ^= byte as u64;
+        self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    /**
+     * Hash a name followed by a zero byte, so that e.g. the names "ab", "c" and "a", "bc" hash differently.
+     */
+    fn write_name(&mut self, name: &str) {
+        name.bytes().for_each(|byte| self.write_byte(byte));
+        self.write_byte(0);
+    }
+
+    /**
+     * Hash the end of a list of names. UTF-8 strings never contain the byte 0xff, so it can't be confused with a name.
+     */
+    fn write_separator(&mut self) {
+        self.write_byte(0xff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn get_problem(required_items: &[&str], optional_items: &[&str]) -> ExactCoverProblem {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+            ("C".to_string(), vec!["C".to_string()]),
+        ]);
+        return ExactCoverProblem::new(
+            required_items.iter().map(|item_name| item_name.to_string()).collect(),
+            optional_items.iter().map(|item_name| item_name.to_string()).collect(),
+            vec![],
+            covered_by,
+        ).unwrap();
+    }
+
+    /**
+     * A cache in a new temporary directory which is specific to the test.
+     */
+    fn get_cache(test_name: &str) -> SolutionCache {
+        let dir = std::env::temp_dir().join(format!("exact-cover-cache-{}-{}", std::process::id(), test_name));
+        let _ = fs::remove_dir_all(&dir);
+        return SolutionCache::new(dir);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let problem = get_problem(&["A", "B", "C"], &[]);
+
+        assert_eq!(problem.content_hash(), get_problem(&["C", "B", "A"], &[]).content_hash());
+        assert_ne!(problem.content_hash(), get_problem(&["A", "B"], &["C"]).content_hash());
+    }
+
+    #[test]
+    fn test_count_solutions_is_cached() {
+        let cache = get_cache("count");
+        let problem = get_problem(&["A", "B", "C"], &[]);
+
+        let num_solutions = cache.count_solutions(&problem, CountMethod::Search).unwrap();
+        let path = cache.dir.join(format!("{:016x}.count", problem.content_hash()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2\n");
+        fs::write(&path, "7\n").unwrap();
+        let cached_num_solutions = cache.count_solutions(&problem, CountMethod::Zdd).unwrap();
+
+        assert_eq!(num_solutions, 2);
+        assert_eq!(cached_num_solutions, 7);
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_solve_is_cached() {
+        let cache = get_cache("solve");
+        let problem = get_problem(&["A", "B", "C"], &[]).with_lexicographic_order(true);
+
+        let solution = cache.solve(&problem).unwrap().unwrap();
+        let path = cache.dir.join(format!("{:016x}.lexicographic.solution", problem.content_hash()));
+        fs::write(&path, "A\nB\nC\n").unwrap();
+        let cached_solution = cache.solve(&problem).unwrap().unwrap();
+
+        assert_eq!(solution.selected_options, vec!["A", "B", "C"]);
+        assert_eq!(cached_solution.selected_options, vec!["A", "B", "C"]);
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_solve_without_solution_is_cached() {
+        let cache = get_cache("no-solution");
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([("A".to_string(), vec![])]);
+        let problem = ExactCoverProblem::new(vec!["A".to_string()], vec![], vec![], covered_by).unwrap();
+
+        assert!(cache.solve(&problem).unwrap().is_none());
+        let path = cache.dir.join(format!("{:016x}.solution", problem.content_hash()));
+        assert_eq!(fs::read_to_string(path).unwrap(), "none\n");
+        assert!(cache.solve(&problem).unwrap().is_none());
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_entry_is_recomputed() {
+        let cache = get_cache("invalid");
+        let problem = get_problem(&["A", "B", "C"], &[]);
+        fs::create_dir_all(&cache.dir).unwrap();
+        fs::write(cache.dir.join(format!("{:016x}.count", problem.content_hash())), "many\n").unwrap();
+        fs::write(cache.dir.join(format!("{:016x}.solution", problem.content_hash())), "XYZ\n").unwrap();
+
+        assert_eq!(cache.count_solutions(&problem, CountMethod::Zdd).unwrap(), 2);
+        assert_ne!(cache.solve(&problem).unwrap().unwrap().selected_options, vec!["XYZ"]);
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+}
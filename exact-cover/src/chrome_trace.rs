@@ -0,0 +1,161 @@
+use std::fmt::Write as _;
+use std::io;
+use std::io::Write;
+use std::time::Instant;
+
+use crate::Observer;
+
+/**
+ * An observer which records the steps of a search as events in the Chrome trace event format, so that the search can
+ * be viewed as a timeline in about://tracing or Perfetto. Every tried option becomes a span that lasts until it is
+ * unselected again, so deep searches show up as deep stacks of spans. Dead ends and solutions are instant events.
+ *
+ * All events are kept in memory until they are written, so only trace searches with a moderate number of nodes.
+ *
+ * ```
+ * use std::collections::HashMap;
+ * use exact_cover::{ChromeTrace, ExactCoverProblem};
+ *
+ * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+ * #     ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+ * #     ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+ * # ]);
+ * # let required_items = vec!["A".to_string(), "B".to_string()];
+ * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+ * let mut trace = ChromeTrace::new();
+ *
+ * problem.try_solve_with_observers(&mut [&mut trace]).unwrap();
+ *
+ * let mut json: Vec<u8> = Vec::new();
+ * trace.write_json(&mut json).unwrap();
+ * assert!(String::from_utf8(json).unwrap().starts_with("{\"traceEvents\":["));
+ * ```
+ */
+pub struct ChromeTrace {
+    /// The moment the trace was created, from which the timestamps of the events are measured
+    start: Instant,
+    /// The events in the order they happened
+    events: Vec<TraceEvent>,
+}
+
+/**
+ * An event of the trace, which is written as one JSON object.
+ */
+struct TraceEvent {
+    /// The option or what happened, e.g. "solution"
+    name: String,
+    /// The phase of the event: B begins a span, E ends it and i is an instant event
+    phase: char,
+    /// Microseconds since the start of the trace
+    timestamp: f64,
+    /// The item the search branches on, if any
+    item_name: Option<String>,
+}
+
+impl ChromeTrace {
+    /**
+     * Create a trace without events, whose timestamps start now.
+     */
+    pub fn new() -> ChromeTrace {
+        return ChromeTrace {
+            start: Instant::now(),
+            events: Vec::new(),
+        };
+    }
+
+    /**
+     * The number of recorded events.
+     */
+    pub fn num_events(&self) -> usize {
+        return self.events.len();
+    }
+
+    /**
+     * Write the trace as a JSON object with a traceEvents array, which is the format the trace viewers load.
+     */
+    pub fn write_json(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(b"{\"traceEvents\":[")?;
+        for (event_idx, event) in self.events.iter().enumerate() {
+            let mut json = String::new();
+            if event_idx > 0 {
+                json.push(',');
+            }
+            write!(json, "\n{{\"name\":\"{}\",\"ph\":\"{}\",\"ts\":{:.3},\"pid\":1,\"tid\":1",
+                   escape_json(&event.name), event.phase, event.timestamp).unwrap();
+            if event.phase == 'i' {
+                // Instant events only mark the thread instead of spanning the whole timeline
+                json.push_str(",\"s\":\"t\"");
+            }
+            if let Some(item_name) = &event.item_name {
+                write!(json, ",\"args\":{{\"item\":\"{}\"}}", escape_json(item_name)).unwrap();
+            }
+            json.push('}');
+            writer.write_all(json.as_bytes())?;
+        }
+        writer.write_all(b"\n]}\n")?;
+        return Ok(());
+    }
+
+    fn record(&mut self, name: &str, phase: char, item_name: Option<&str>) {
+        self.events.push(TraceEvent {
+            name: name.to_string(),
+            phase,
+            timestamp: self.start.elapsed().as_secs_f64() * 1e6,
+            item_name: item_name.map(str::to_string),
+        });
+    }
+}
+
+impl Default for ChromeTrace {
+    fn default() -> Self {
+        return ChromeTrace::new();
+    }
+}
+
+impl Observer for ChromeTrace {
+    fn on_item_selected(&mut self, item_name: &str, options: &[String]) {
+        if options.is_empty() {
+            self.record("dead end", 'i', Some(item_name));
+        }
+    }
+
+    fn on_option_tried(&mut self, item_name: &str, option_name: &str) {
+        self.record(option_name, 'B', Some(item_name));
+    }
+
+    fn on_backtrack(&mut self, item_name: &str, option_name: &str) {
+        self.record(option_name, 'E', Some(item_name));
+    }
+
+    fn on_solution(&mut self, _selected_options: &[String]) {
+        self.record("solution", 'i', None);
+    }
+}
+
+/**
+ * Escape a string for use inside a JSON string literal.
+ */
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for char in s.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            char if char.is_control() => write!(escaped, "\\u{:04x}", char as u32).unwrap(),
+            char => escaped.push(char),
+        }
+    }
+    return escaped;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(escape_json("r1c1 \"5\""), "r1c1 \\\"5\\\"");
+        assert_eq!(escape_json("a\\b\nc\u{1}"), "a\\\\b\\nc\\u0001");
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use crate::{ExactCoverProblem, SearchStats};
+
+/**
+ * An exact cover problem in the sparse-set representation of Knuth's dancing cells. Every incidence of an option and
+ * an item is a cell. The options of each item are stored as cells in an array whose first size entries are the active
+ * ones. Deactivating an option swaps its cell behind the active part, and reactivating it only grows the size again,
+ * as long as changes are undone in reverse order. The required items which still have to be covered form another
+ * sparse set.
+ */
+pub(crate) struct DancingCellsProblem {
+    /// The option names, indexed like option_cells
+    option_names: Vec<String>,
+    /// For each option, its cells
+    option_cells: Vec<Vec<usize>>,
+    /// For each cell, the option it belongs to
+    cell_option: Vec<usize>,
+    /// For each cell, the item it covers
+    cell_item: Vec<usize>,
+    /// For each item, the cells of the options covering it
+    item_cells: Vec<Vec<usize>>,
+    /// The indices of the required items
+    required_items: Vec<usize>,
+    /// The indices of the required options
+    required_options: Vec<usize>,
+}
+
+/**
+ * A change of the search state which can be undone.
+ */
+enum Change {
+    /// An option was selected
+    SelectedOption,
+    /// An option was deactivated for the item, i.e. the size of its set of active cells decreased by one
+    HiddenCell(usize),
+    /// A required item was covered, i.e. the number of uncovered items decreased by one
+    CoveredItem,
+}
+
+/**
+ * The mutable state of a dancing cells search.
+ */
+struct DancingCellsState {
+    /// For each item, the order of its cells; the first item_sizes[item] ones are active
+    item_cells: Vec<Vec<usize>>,
+    /// For each item, the number of active cells
+    item_sizes: Vec<usize>,
+    /// For each cell, its position in the cells of its item
+    cell_positions: Vec<usize>,
+    /// The required items; the first num_uncovered ones still have to be covered
+    items: Vec<usize>,
+    /// For each item, its position in items if it is required
+    item_positions: Vec<usize>,
+    /// The number of required items that still have to be covered
+    num_uncovered: usize,
+    /// The changes made so far, in order, so that they can be undone
+    trail: Vec<Change>,
+    /// The indices of the selected options
+    selected_options: Vec<usize>,
+}
+
+/**
+ * A level of the search: the options of the item being covered, the next one to try and the length of the trail
+ * before the current option was selected.
+ */
+struct DancingCellsFrame {
+    options: Vec<usize>,
+    next_option_idx: usize,
+    trail_len: Option<usize>,
+}
+
+impl DancingCellsProblem {
+    /**
+     * Create the dancing cells representation of a problem.
+     */
+    pub(crate) fn new(problem: &ExactCoverProblem) -> DancingCellsProblem {
+        let mut item_names: Vec<&String> = problem.covered_by.keys().collect();
+        item_names.sort();
+        let item_indices: HashMap<&String, usize> = item_names.iter().enumerate()
+            .map(|(item_idx, item_name)| (*item_name, item_idx))
+            .collect();
+        let mut option_names: Vec<String> = problem.covers.keys().cloned().collect();
+        option_names.sort();
+
+        let mut option_cells: Vec<Vec<usize>> = Vec::new();
+        let mut cell_option: Vec<usize> = Vec::new();
+        let mut cell_item: Vec<usize> = Vec::new();
+        let mut item_cells: Vec<Vec<usize>> = vec![Vec::new(); item_names.len()];
+        for (option_idx, option_name) in option_names.iter().enumerate() {
+            let mut cells: Vec<usize> = Vec::new();
+            for item_name in problem.covers[option_name].iter() {
+                let cell = cell_option.len();
+                cell_option.push(option_idx);
+                cell_item.push(item_indices[item_name]);
+                item_cells[item_indices[item_name]].push(cell);
+                cells.push(cell);
+            }
+            option_cells.push(cells);
+        }
+
+        let mut required_items: Vec<usize> = problem.required_items.iter().map(|item_name| item_indices[item_name]).collect();
+        required_items.sort();
+        let mut required_options: Vec<usize> = problem.required_options.iter()
+            .map(|option_name| option_names.binary_search(option_name).unwrap())
+            .collect();
+        required_options.sort();
+
+        return DancingCellsProblem {
+            option_names,
+            option_cells,
+            cell_option,
+            cell_item,
+            item_cells,
+            required_items,
+            required_options,
+        };
+    }
+
+    /**
+     * Call visit with the selected options of every solution until it returns Break. The search covers the uncovered
+     * item with the fewest active options first, like the other engines. Returns the statistics of the search.
+     */
+    pub(crate) fn for_each_solution(&self, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> SearchStats {
+        let mut stats = SearchStats::default();
+        let mut state = self.new_state();
+        for option_idx in self.required_options.iter() {
+            self.select_option(&mut state, *option_idx);
+        }
+
+        let mut selected_option_names: Vec<String> = Vec::new();
+        let mut stack: Vec<DancingCellsFrame> = Vec::new();
+        let mut stopped = false;
+        let mut descend = true;
+        loop {
+            if descend {
+                match self.choose_item(&state) {
+                    None => {
+                        selected_option_names.clear();
+                        selected_option_names.extend(state.selected_options.iter().map(|option_idx| self.option_names[*option_idx].clone()));
+                        stopped = visit(&selected_option_names).is_break();
+                    }
+                    Some(item) if state.item_sizes[item] > 0 => {
+                        let options = state.item_cells[item][..state.item_sizes[item]].iter()
+                            .map(|cell| self.cell_option[*cell])
+                            .collect();
+                        stack.push(DancingCellsFrame {
+                            options,
+                            next_option_idx: 0,
+                            trail_len: None,
+                        });
+                    }
+                    Some(_) => stats.num_backtracks += 1,
+                }
+            }
+
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => return stats,
+            };
+
+            if let Some(trail_len) = frame.trail_len.take() {
+                self.undo(&mut state, trail_len);
+            }
+
+            if frame.next_option_idx < frame.options.len() && !stopped {
+                let option_idx = frame.options[frame.next_option_idx];
+                frame.next_option_idx += 1;
+                frame.trail_len = Some(state.trail.len());
+                self.select_option(&mut state, option_idx);
+                stats.num_nodes += 1;
+                descend = true;
+            } else {
+                stack.pop();
+                descend = false;
+            }
+        }
+    }
+
+    fn new_state(&self) -> DancingCellsState {
+        let mut cell_positions: Vec<usize> = vec![0; self.cell_option.len()];
+        for cells in self.item_cells.iter() {
+            for (position, cell) in cells.iter().enumerate() {
+                cell_positions[*cell] = position;
+            }
+        }
+        let mut item_positions: Vec<usize> = vec![usize::MAX; self.item_cells.len()];
+        for (position, item) in self.required_items.iter().enumerate() {
+            item_positions[*item] = position;
+        }
+        return DancingCellsState {
+            item_cells: self.item_cells.clone(),
+            item_sizes: self.item_cells.iter().map(|cells| cells.len()).collect(),
+            cell_positions,
+            items: self.required_items.clone(),
+            item_positions,
+            num_uncovered: self.required_items.len(),
+            trail: Vec::new(),
+            selected_options: Vec::new(),
+        };
+    }
+
+    /**
+     * The uncovered item with the fewest active options, or None if all required items are covered.
+     */
+    fn choose_item(&self, state: &DancingCellsState) -> Option<usize> {
+        return state.items[..state.num_uncovered].iter()
+            .min_by_key(|item| (state.item_sizes[**item], **item))
+            .copied();
+    }
+
+    /**
+     * Select an option: cover its items and deactivate all other options covering them.
+     */
+    fn select_option(&self, state: &mut DancingCellsState, option_idx: usize) {
+        state.selected_options.push(option_idx);
+        state.trail.push(Change::SelectedOption);
+        for cell in self.option_cells[option_idx].iter() {
+            let item = self.cell_item[*cell];
+            if state.item_positions[item] != usize::MAX {
+                self.cover_item(state, item);
+            }
+            for position in 0..state.item_sizes[item] {
+                let other_option_idx = self.cell_option[state.item_cells[item][position]];
+                if other_option_idx != option_idx {
+                    self.hide_option(state, other_option_idx, item);
+                }
+            }
+        }
+    }
+
+    /**
+     * Remove a required item from the uncovered items by swapping it behind them.
+     */
+    fn cover_item(&self, state: &mut DancingCellsState, item: usize) {
+        let position = state.item_positions[item];
+        let last_position = state.num_uncovered - 1;
+        let last_item = state.items[last_position];
+        state.items.swap(position, last_position);
+        state.item_positions[last_item] = position;
+        state.item_positions[item] = last_position;
+        state.num_uncovered -= 1;
+        state.trail.push(Change::CoveredItem);
+    }
+
+    /**
+     * Deactivate an option for all its items except the given one, whose options are not looked at anymore.
+     */
+    fn hide_option(&self, state: &mut DancingCellsState, option_idx: usize, except_item: usize) {
+        for cell in self.option_cells[option_idx].iter() {
+            let item = self.cell_item[*cell];
+            if item == except_item {
+                continue;
+            }
+            let position = state.cell_positions[*cell];
+            let last_position = state.item_sizes[item] - 1;
+            let last_cell = state.item_cells[item][last_position];
+            state.item_cells[item].swap(position, last_position);
+            state.cell_positions[last_cell] = position;
+            state.cell_positions[*cell] = last_position;
+            state.item_sizes[item] -= 1;
+            state.trail.push(Change::HiddenCell(item));
+        }
+    }
+
+    /**
+     * Undo all changes until the trail has the given length. Since every change swapped an element right behind the
+     * active part of its set, growing the set again restores it.
+     */
+    fn undo(&self, state: &mut DancingCellsState, trail_len: usize) {
+        while state.trail.len() > trail_len {
+            match state.trail.pop().unwrap() {
+                Change::SelectedOption => {
+                    state.selected_options.pop();
+                }
+                Change::HiddenCell(item) => state.item_sizes[item] += 1,
+                Change::CoveredItem => state.num_uncovered += 1,
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::ExactCoverProblem;
+
+/**
+ * A reason why a solution that is known to be valid is not a solution of its exact cover encoding, which points at a
+ * bug in the encoder.
+ */
+#[derive(Debug, PartialEq)]
+pub enum EncodingIssue {
+    /// The option of the solution doesn't cover any item, e.g. because the encoder never added it to covered_by
+    UnknownOption(String),
+    /// The required option is not part of the solution, e.g. because the encoder made the wrong option required
+    MissingRequiredOption(String),
+    /// The required item is not covered by any option of the solution, e.g. because an option is missing from the
+    /// options covering the item
+    UncoveredItem(String),
+    /// The item is covered by more than one option of the solution, e.g. because an option covers the wrong item
+    OvercoveredItem {
+        item: String,
+        options: Vec<String>,
+    },
+}
+
+impl Display for EncodingIssue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            EncodingIssue::UnknownOption(option) => write!(f, "option {} doesn't cover any item", option),
+            EncodingIssue::MissingRequiredOption(option) => write!(f, "required option {} is not selected", option),
+            EncodingIssue::UncoveredItem(item) => write!(f, "required item {} is not covered by any option", item),
+            EncodingIssue::OvercoveredItem { item, options } => {
+                write!(f, "item {} is covered by more than one option: {}", item, options.join(", "))
+            }
+        }
+    }
+}
+
+impl ExactCoverProblem {
+    /**
+     * Check a solution that is known to be valid, e.g. a solved puzzle from a trusted source, against this problem and
+     * list every reason why it isn't a solution of the problem. An empty list means that the encoding accepts the
+     * solution. The issues are listed by kind, and sorted by name within each kind.
+     */
+    pub fn diagnose_solution(&self, selected_options: &[String]) -> Vec<EncodingIssue> {
+        let mut issues: Vec<EncodingIssue> = Vec::new();
+
+        let mut unknown_options: Vec<&String> = selected_options.iter()
+            .filter(|option_name| !self.covers.contains_key(*option_name))
+            .collect();
+        unknown_options.sort();
+        issues.extend(unknown_options.into_iter().map(|option_name| EncodingIssue::UnknownOption(option_name.clone())));
+
+        let selected: HashSet<&String> = selected_options.iter().collect();
+        let mut missing_options: Vec<&String> = self.required_options.iter()
+            .filter(|option_name| !selected.contains(option_name))
+            .collect();
+        missing_options.sort();
+        issues.extend(missing_options.into_iter().map(|option_name| EncodingIssue::MissingRequiredOption(option_name.clone())));
+
+        let mut covering_options: HashMap<&String, Vec<String>> = HashMap::new();
+        for option_name in selected_options.iter() {
+            for item_name in self.covers.get(option_name).into_iter().flatten() {
+                covering_options.entry(item_name).or_default().push(option_name.clone());
+            }
+        }
+
+        let mut uncovered_items: Vec<&String> = self.required_items.iter()
+            .filter(|item_name| !covering_options.contains_key(item_name))
+            .collect();
+        uncovered_items.sort();
+        issues.extend(uncovered_items.into_iter().map(|item_name| EncodingIssue::UncoveredItem(item_name.clone())));
+
+        let mut overcovered_items: Vec<(&String, Vec<String>)> = covering_options.into_iter()
+            .filter(|(_, option_names)| option_names.len() > 1)
+            .collect();
+        overcovered_items.sort();
+        issues.extend(overcovered_items.into_iter().map(|(item_name, options)| EncodingIssue::OvercoveredItem {
+            item: item_name.clone(),
+            options,
+        }));
+
+        return issues;
+    }
+}
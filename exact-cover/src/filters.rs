@@ -0,0 +1,129 @@
+use std::ops::ControlFlow;
+
+use crate::{ExactCoverProblem, SearchError};
+
+/// A predicate on the selected options of a solution, which can be shared between threads like the rest of the problem
+pub(crate) type SolutionFilter = Box<dyn Fn(&[String]) -> bool + Send + Sync>;
+
+/// A predicate on the options selected so far, which prunes the search below the nodes it rejects
+pub(crate) type NodeFilter<'a> = &'a mut dyn FnMut(&[String]) -> bool;
+
+impl ExactCoverProblem {
+    /**
+     * Only accept solutions whose selected options satisfy the predicate. The predicate is checked for every solution
+     * the search finds, before it is counted or returned, so it can express constraints that are awkward to encode as
+     * items, such as the sums of a Sudoku variant. Solutions rejected by a filter are skipped as if they were dead
+     * ends. Filters accumulate: a solution must satisfy every filter added.
+     *
+     * Filters apply to solving, counting and visiting solutions with any backend, but not to the searches with their
+     * own engine: the ZDD, min-cost and max-cover searches. Filters aren't serialized or hashed, so problems with
+     * filters bypass the solution cache.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use exact_cover::ExactCoverProblem;
+     *
+     * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     * #     ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+     * #     ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+     * # ]);
+     * # let required_items = vec!["A".to_string(), "B".to_string()];
+     * // Both {AB} and {A, B} cover A and B, but only solutions of two options are wanted
+     * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by)
+     *     .unwrap()
+     *     .with_solution_filter(|selected_options| selected_options.len() == 2);
+     *
+     * assert_eq!(problem.count_all_solutions(), 1);
+     * ```
+     */
+    pub fn with_solution_filter(mut self, filter: impl Fn(&[String]) -> bool + Send + Sync + 'static) -> ExactCoverProblem {
+        self.solution_filters.push(Box::new(filter));
+        return self;
+    }
+
+    /**
+     * Call visit with the selected options of every solution like for_each_solution, but prune the search below every
+     * node whose selected options don't satisfy keep. keep is called after every option the search selects while
+     * branching and for every solution, so it must accept every partial selection that may still be completed, e.g.
+     * one in which no two options break a rule of a Sudoku variant. Unlike a solution filter, which only rejects
+     * complete solutions, this skips whole subtrees of the search. Always uses the general engine, since the other
+     * backends don't search item by item, and fails if the search exceeds the memory limit.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use std::ops::ControlFlow;
+     * use exact_cover::ExactCoverProblem;
+     *
+     * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     * #     ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+     * #     ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+     * # ]);
+     * # let required_items = vec!["A".to_string(), "B".to_string()];
+     * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+     *
+     * // Never select AB, so that only {A, B} is left
+     * let mut num_solutions = 0;
+     * let result = problem.for_each_solution_pruned(
+     *     &mut |selected_options| !selected_options.contains(&"AB".to_string()),
+     *     |_| -> ControlFlow<()> {
+     *         num_solutions += 1;
+     *         ControlFlow::Continue(())
+     *     });
+     *
+     * assert_eq!(result, Ok(ControlFlow::Continue(())));
+     * assert_eq!(num_solutions, 1);
+     * ```
+     */
+    pub fn for_each_solution_pruned<B>(
+        &self,
+        keep: &mut dyn FnMut(&[String]) -> bool,
+        mut visit: impl FnMut(&[String]) -> ControlFlow<B>) -> Result<ControlFlow<B>, SearchError>
+    {
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+        state.node_filter = Some(keep);
+
+        let mut result = ControlFlow::Continue(());
+        self._visit_solutions(&mut state, &mut |selected_options| {
+            result = visit(selected_options);
+            match result {
+                ControlFlow::Continue(()) => ControlFlow::Continue(()),
+                ControlFlow::Break(_) => ControlFlow::Break(()),
+            }
+        })?;
+        return Ok(result);
+    }
+
+    /**
+     * Whether any solution filter has been added.
+     */
+    pub fn has_solution_filters(&self) -> bool {
+        return !self.solution_filters.is_empty();
+    }
+
+    /**
+     * Whether the selected options of a solution satisfy every solution filter.
+     */
+    pub(crate) fn accepts_solution(&self, selected_options: &[String]) -> bool {
+        return self.solution_filters.iter().all(|filter| filter(selected_options));
+    }
+
+    /**
+     * Wrap visit so that it is only called with solutions that satisfy every solution filter, for the engines that
+     * report solutions without going through the general search.
+     */
+    pub(crate) fn filter_visit<'a>(
+        &'a self,
+        visit: &'a mut dyn FnMut(&[String]) -> ControlFlow<()>) -> impl FnMut(&[String]) -> ControlFlow<()> + 'a
+    {
+        return move |selected_options| {
+            if !self.accepts_solution(selected_options) {
+                return ControlFlow::Continue(());
+            }
+            return visit(selected_options);
+        };
+    }
+}
@@ -0,0 +1,68 @@
+use log::info;
+
+use crate::{ExactCoverProblem, ExactCoverSolution, SearchError};
+
+impl ExactCoverProblem {
+    /**
+     * Make solve and try_solve return the solution whose sorted list of options is lexicographically smallest, instead
+     * of whichever solution the search happens to find first. This makes the result independent of the iteration
+     * order of the hash sets, which is useful for canonical solutions and stable test output, but it takes up to one
+     * search per option.
+     */
+    pub fn with_lexicographic_order(mut self, enabled: bool) -> ExactCoverProblem {
+        self.lexicographic_order = enabled;
+        return self;
+    }
+
+    /**
+     * Find the lexicographically smallest solution, with its options sorted.
+     *
+     * The smallest option that is part of any solution must be the first option of the smallest solution, the next
+     * smallest option that is part of a solution together with it must be the second, and so on. So the options are
+     * tried in sorted order, and each one is kept if there is still a solution with it, until the kept options cover
+     * all required items. Like the search, it never selects options that only cover optional items, so the solution
+     * is one that solve could also return.
+     */
+    pub(crate) fn solve_lexicographically(&self) -> Result<Option<ExactCoverSolution>, SearchError> {
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+
+        let mut option_names: Vec<&String> = self.covers.keys().collect();
+        option_names.sort();
+        for option_name in option_names {
+            if state.items_queue.is_empty() {
+                break;
+            }
+            let item_names = self.covers.get(option_name).unwrap();
+            if item_names.iter().all(|item_name| self.is_optional(item_name)) {
+                continue;
+            }
+            if !state.available_options.get(&item_names[0]).unwrap().contains(option_name) {
+                continue;
+            }
+
+            let removed_options = self.select_option(&mut state, option_name.clone());
+            let result = self._solve_until(&mut state, 1);
+            if let Some(error) = result.error {
+                return Err(error);
+            }
+            if result.num_solutions > 0 {
+                info!("Keeping option {}", option_name);
+            } else {
+                self.unselect_option(&mut state, option_name.clone(), removed_options);
+                // No solution contains the option together with the kept options, so it can't be selected anymore
+                self.remove_option(&mut state, option_name.clone());
+            }
+        }
+
+        if !state.items_queue.is_empty() {
+            return Ok(None);
+        }
+        let mut selected_options = state.selected_options;
+        selected_options.sort();
+        return Ok(Some(ExactCoverSolution { selected_options }));
+    }
+}
@@ -0,0 +1,1002 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+
+use log::info;
+use priority_queue::PriorityQueue;
+use thiserror::Error;
+
+pub use backend::{BitsetBackend, DancingCellsBackend, GeneralBackend, SolverBackend};
+pub use cache::{CacheError, CountMethod, SolutionCache};
+pub use chrome_trace::ChromeTrace;
+pub use diagnosis::EncodingIssue;
+pub use max_cover::MaxCoverSolution;
+pub use min_cost::MinCostSolution;
+pub use observer::Observer;
+pub use search_stats::{MemoryStats, SearchStats};
+pub use simplify::SimplificationReport;
+pub use stats::ProblemStats;
+pub use symmetry::SymmetryCounts;
+pub use unsatisfiable::{Contradiction, UnsatisfiableCore};
+pub use zdd::{Zdd, ZddSolutions};
+
+use bitset::BitsetProblem;
+use filters::{NodeFilter, SolutionFilter};
+use payloads::Payload;
+
+mod backend;
+mod bitset;
+mod cache;
+mod chrome_trace;
+mod dancing_cells;
+mod diagnosis;
+mod filters;
+mod lexicographic;
+mod matrix;
+mod max_cover;
+mod min_cost;
+mod observer;
+mod payloads;
+mod prerequisites;
+mod random;
+mod restarts;
+#[cfg(feature = "serde")]
+mod serialization;
+mod search_stats;
+mod simplify;
+mod stats;
+mod streaming;
+mod symmetry;
+mod unsatisfiable;
+mod zdd;
+#[cfg(test)]
+mod basic_example;
+#[cfg(test)]
+mod test_utils;
+
+/**
+ * An exact cover problem. See https://en.wikipedia.org/wiki/Exact_cover.
+ *
+ * The problem definition is immutable once constructed: all state that changes while searching lives in a separate
+ * SearchState, so a problem can be solved repeatedly and shared between threads.
+ *
+ * A problem is created with new and then configured with the with_ builder methods:
+ *
+ * ```
+ * use std::collections::HashMap;
+ * use exact_cover::ExactCoverProblem;
+ *
+ * // Items 1 to 4 must each be covered exactly once by the options 12, 34, 13 and 24
+ * let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+ *     ("1".to_string(), vec!["12".to_string(), "13".to_string()]),
+ *     ("2".to_string(), vec!["12".to_string(), "24".to_string()]),
+ *     ("3".to_string(), vec!["34".to_string(), "13".to_string()]),
+ *     ("4".to_string(), vec!["34".to_string(), "24".to_string()]),
+ * ]);
+ * let required_items = vec!["1".to_string(), "2".to_string(), "3".to_string(), "4".to_string()];
+ * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by)
+ *     .unwrap()
+ *     .with_forced_option_propagation(true)
+ *     .with_lexicographic_order(true);
+ *
+ * let solution = problem.solve().unwrap();
+ * assert_eq!(solution.selected_options, vec!["12".to_string(), "34".to_string()]);
+ * ```
+ */
+pub struct ExactCoverProblem {
+    /// Map from item name to option names
+    covered_by: HashMap<String, Vec<String>>,
+    /// Map from option name to item names
+    covers: HashMap<String, Vec<String>>,
+    /// The items that must be covered
+    required_items: HashSet<String>,
+    /// The items that may be covered at most once, but don't have to be covered
+    optional_items: HashSet<String>,
+    /// The options that must be selected as part of the solution
+    required_options: HashSet<String>,
+    /// Whether to select forced options (items with exactly one available option) before branching
+    propagate_forced_options: bool,
+    /// The approximate number of bytes the search may use before it is aborted, if limited
+    memory_limit: Option<usize>,
+    /// The cost of selecting each option, for options that have one
+    option_costs: HashMap<String, u64>,
+    /// The payload attached to each option, for options that have one
+    option_payloads: HashMap<String, Payload>,
+    /// Whether solve returns the lexicographically smallest solution
+    lexicographic_order: bool,
+    /// Maps from option name to option name which map solutions to solutions
+    symmetries: Vec<HashMap<String, String>>,
+    /// The problem as bitmasks, if it is small enough for the bitset backend
+    bitset: Option<BitsetProblem>,
+    /// Whether to solve with the bitset backend when the problem is small enough
+    use_bitset_backend: bool,
+    /// The number of nodes after which solve restarts the search for the first time, if restarts are enabled
+    restart_node_budget: Option<u64>,
+    /// The options that were added to encode prerequisites
+    auxiliary_options: HashSet<String>,
+    /// Whether the search estimates its memory use per data structure and reports it in its statistics
+    track_memory: bool,
+    /// The predicates that every solution must satisfy
+    solution_filters: Vec<SolutionFilter>,
+}
+
+/**
+ * The mutable state of a search for solutions of an exact cover problem.
+ */
+struct SearchState<'a> {
+    /// Map from item name to the available options (i.e. those that haven't been removed)
+    available_options: HashMap<String, HashSet<String>>,
+    /// Priority queue of items, ordered by the smallest number of available options
+    items_queue: PriorityQueue<String, i32>,
+    /// The selected options
+    selected_options: Vec<String>,
+    /// The work done by the search so far
+    stats: SearchStats,
+    /// The observers which are notified of the steps of the search
+    observers: Vec<&'a mut dyn Observer>,
+    /// For each item, the number of times the search ran into it without options left. Only used to order the items
+    /// when restarting, so it stays empty otherwise
+    item_failures: HashMap<String, u64>,
+    /// The number of nodes after which the search stops so that it can be restarted, if limited
+    node_limit: Option<u64>,
+    /// Whether the search stopped because it reached the node limit
+    node_limit_reached: bool,
+    /// The predicate which the selected options must satisfy after every option selected while branching, if the
+    /// search is pruned
+    node_filter: Option<NodeFilter<'a>>,
+}
+
+// InvalidProblemError is a custom error type for exact cover problems that are not well-defined.
+#[derive(Debug, PartialEq, Error)]
+pub enum InvalidProblemError {
+    /// The item is declared more than once, either as required or as optional item
+    #[error("item {0} is declared more than once")]
+    DuplicateItem(String),
+    /// The item has options covering it but is declared neither as required nor as optional item
+    #[error("item {0} is covered by options but not declared as required or optional item")]
+    UndeclaredItem(String),
+    /// The required option doesn't cover any item
+    #[error("required option {0} doesn't cover any item")]
+    UnknownRequiredOption(String),
+    /// Two required options cover the same item, so they can never both be part of a solution
+    #[error("required options {option_a} and {option_b} both cover item {item}")]
+    RequiredOptionConflict {
+        option_a: String,
+        option_b: String,
+        item: String,
+    },
+    /// The option was given a cost but doesn't cover any item
+    #[error("option {0} has a cost but doesn't cover any item")]
+    UnknownCostOption(String),
+    /// The option is mapped by a symmetry but doesn't cover any item
+    #[error("option {0} is mapped by a symmetry but doesn't cover any item")]
+    UnknownSymmetryOption(String),
+    /// A symmetry maps more than one option to the option, so it isn't a permutation of the options
+    #[error("a symmetry maps more than one option to option {0}")]
+    NonBijectiveSymmetry(String),
+    /// The row of the incidence matrix has a different length than the first row
+    #[error("row {0} of the matrix has a different length than the first row")]
+    InconsistentRowLength(usize),
+    /// The option was given a payload but doesn't cover any item
+    #[error("option {0} has a payload but doesn't cover any item")]
+    UnknownPayloadOption(String),
+    /// The option is part of a prerequisite but doesn't cover any item
+    #[error("option {0} is part of a prerequisite but doesn't cover any item")]
+    UnknownPrerequisiteOption(String),
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExactCoverSolution {
+    /// The selected options
+    pub selected_options: Vec<String>,
+}
+
+/**
+ * Whether a problem has no, exactly one or more than one solution.
+ */
+#[derive(Debug)]
+pub enum SolutionMultiplicity {
+    /// The problem has no solution
+    None,
+    /// The problem has exactly this solution
+    Unique(ExactCoverSolution),
+    /// The problem has at least this many solutions, which is at least 2
+    Multiple(u64),
+}
+
+// SearchError is a custom error type for searches that are aborted before they are finished.
+#[derive(Debug, PartialEq, Error)]
+pub enum SearchError {
+    /// The search would use more than the given number of bytes
+    #[error("the search exceeded the memory limit of {0} bytes")]
+    MemoryLimitExceeded(usize),
+}
+
+struct ExactCoverResult {
+    last_solution: Option<ExactCoverSolution>,
+    num_solutions: u64,
+    /// Why the search was aborted, if it didn't run to completion
+    error: Option<SearchError>,
+}
+
+/**
+ * A level of the depth-first search: the item being covered and the options still to try for it.
+ */
+struct SearchFrame {
+    /// The item that is covered at this level
+    item_name: String,
+    /// The options that were available for the item when it was selected
+    options: Vec<String>,
+    /// Index of the next option to try
+    next_option_idx: usize,
+    /// The currently selected option together with the options its selection removed
+    selected_option: Option<(String, Vec<String>)>,
+    /// The number of solutions found before this level was entered
+    num_solutions_before: u64,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Create a new exact cover problem.
+     *
+     * Every item must be declared exactly once, either as a required item (which must be covered exactly once) or as
+     * an optional item (which must be covered at most once). Items without an entry in covered_by have no options.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use exact_cover::ExactCoverProblem;
+     *
+     * // A and B must be covered, while the optional item X may be left uncovered
+     * let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     *     ("A".to_string(), vec!["AX".to_string(), "AB".to_string()]),
+     *     ("B".to_string(), vec!["BX".to_string(), "AB".to_string()]),
+     *     ("X".to_string(), vec!["AX".to_string(), "BX".to_string()]),
+     * ]);
+     * let problem = ExactCoverProblem::new(vec!["A".to_string(), "B".to_string()], vec!["X".to_string()], vec![], covered_by)
+     *     .unwrap();
+     *
+     * // AX and BX would cover X twice, so AB is the only solution
+     * assert!(problem.is_optional("X"));
+     * assert_eq!(problem.count_all_solutions(), 1);
+     * assert_eq!(problem.solve().unwrap().selected_options, vec!["AB".to_string()]);
+     * ```
+     */
+    pub fn new(
+        required_items: Vec<String>,
+        optional_items: Vec<String>,
+        required_options: Vec<String>,
+        mut covered_by: HashMap<String, Vec<String>>) -> Result<ExactCoverProblem, InvalidProblemError>
+    {
+        info!("Covered by: {:?}", covered_by);
+        let mut declared_items: HashSet<String> = HashSet::new();
+        for item_name in required_items.iter().chain(optional_items.iter()) {
+            if !declared_items.insert(item_name.clone()) {
+                return Err(InvalidProblemError::DuplicateItem(item_name.clone()));
+            }
+            covered_by.entry(item_name.clone()).or_default();
+        }
+        if let Some(item_name) = covered_by.keys().find(|item_name| !declared_items.contains(*item_name)) {
+            return Err(InvalidProblemError::UndeclaredItem(item_name.clone()));
+        }
+
+        // Items are visited in declaration order so that the items of each option are listed deterministically
+        let mut covers: HashMap<String, Vec<String>> = HashMap::new();
+        for item_name in required_items.iter().chain(optional_items.iter()) {
+            for option_name in covered_by.get(item_name).unwrap().iter() {
+                if !covers.contains_key(option_name) {
+                    covers.insert(option_name.clone(), Vec::new());
+                }
+                covers.get_mut(option_name).unwrap().push(item_name.clone());
+            }
+        }
+
+        if let Some(option_name) = required_options.iter().find(|option_name| !covers.contains_key(*option_name)) {
+            return Err(InvalidProblemError::UnknownRequiredOption(option_name.clone()));
+        }
+        let mut covering_option: HashMap<&String, &String> = HashMap::new();
+        for option_name in required_options.iter() {
+            for item_name in covers.get(option_name).unwrap().iter() {
+                if let Some(other_option_name) = covering_option.insert(item_name, option_name) {
+                    return Err(InvalidProblemError::RequiredOptionConflict {
+                        option_a: other_option_name.clone(),
+                        option_b: option_name.clone(),
+                        item: item_name.clone(),
+                    });
+                }
+            }
+        }
+
+        let item_names: Vec<String> = required_items.iter().chain(optional_items.iter()).cloned().collect();
+        let bitset = BitsetProblem::new(&item_names, &required_items, &required_options, &covers);
+
+        let required_items = HashSet::from_iter(required_items.iter().cloned());
+        let optional_items = HashSet::from_iter(optional_items.iter().cloned());
+        let required_options = HashSet::from_iter(required_options.iter().cloned());
+
+        Ok(ExactCoverProblem {
+            covered_by,
+            covers,
+            required_items,
+            optional_items,
+            required_options,
+            propagate_forced_options: false,
+            memory_limit: None,
+            option_costs: HashMap::new(),
+            option_payloads: HashMap::new(),
+            lexicographic_order: false,
+            symmetries: Vec::new(),
+            bitset,
+            use_bitset_backend: true,
+            restart_node_budget: None,
+            track_memory: false,
+            auxiliary_options: HashSet::new(),
+            solution_filters: Vec::new(),
+        })
+    }
+
+    /**
+     * Whether the item is an optional item, i.e. an item which may be covered at most once but doesn't have to be.
+     */
+    pub fn is_optional(&self, item_name: &str) -> bool {
+        return self.optional_items.contains(item_name);
+    }
+
+    /**
+     * The names of all items, both required and optional, sorted by name.
+     */
+    pub fn items(&self) -> Vec<&str> {
+        let mut item_names: Vec<&str> = self.covered_by.keys().map(String::as_str).collect();
+        item_names.sort();
+        return item_names;
+    }
+
+    /**
+     * The names of all options, sorted by name.
+     */
+    pub fn options(&self) -> Vec<&str> {
+        let mut option_names: Vec<&str> = self.covers.keys().map(String::as_str).collect();
+        option_names.sort();
+        return option_names;
+    }
+
+    /**
+     * The options covering the item, or None if there is no such item.
+     */
+    pub fn options_covering(&self, item_name: &str) -> Option<&[String]> {
+        return self.covered_by.get(item_name).map(Vec::as_slice);
+    }
+
+    /**
+     * The items covered by the option, in the order in which the items were declared, or None if there is no such
+     * option.
+     */
+    pub fn items_covered_by(&self, option_name: &str) -> Option<&[String]> {
+        return self.covers.get(option_name).map(Vec::as_slice);
+    }
+
+    /**
+     * Create the initial search state, in which all options are available and no option is selected.
+     */
+    fn new_search_state<'a>(&self) -> SearchState<'a> {
+        let mut available_options: HashMap<String, HashSet<String>> = HashMap::new();
+        for (item_name, option_names) in self.covered_by.iter() {
+            available_options.insert(item_name.clone(), HashSet::from_iter(option_names.iter().cloned()));
+        }
+
+        // The items are pushed in a fixed order, so that items with equally many options are selected in the same
+        // order every time the problem is searched
+        let mut required_items: Vec<&String> = self.required_items.iter().collect();
+        required_items.sort();
+        let mut items_queue = PriorityQueue::new();
+        for item_name in required_items {
+            let option_names = self.covered_by.get(item_name).unwrap();
+            items_queue.push(item_name.clone(), -(option_names.len() as i32));
+        }
+
+        SearchState {
+            available_options,
+            items_queue,
+            selected_options: Vec::new(),
+            stats: SearchStats::default(),
+            observers: Vec::new(),
+            item_failures: HashMap::new(),
+            node_limit: None,
+            node_limit_reached: false,
+            node_filter: None,
+        }
+    }
+
+    /**
+     * Enable or disable the preprocessing pass which selects forced options before branching.
+     */
+    pub fn with_forced_option_propagation(mut self, enabled: bool) -> ExactCoverProblem {
+        self.propagate_forced_options = enabled;
+        return self;
+    }
+
+    /**
+     * Enable or disable the bitset backend. Problems with at most 128 items are solved with it by default, because
+     * representing options as bitmasks is much faster than the general engine; disabling it forces the general engine.
+     */
+    pub fn with_bitset_backend(mut self, enabled: bool) -> ExactCoverProblem {
+        self.use_bitset_backend = enabled;
+        return self;
+    }
+
+    /**
+     * The bitset backend, if it should be used to solve this problem. The general engine is used instead when a memory
+     * limit is set, since only its memory use is estimated.
+     */
+    fn bitset_backend(&self) -> Option<&BitsetProblem> {
+        if !self.use_bitset_backend || self.memory_limit.is_some() {
+            return None;
+        }
+        return self.bitset.as_ref();
+    }
+
+    /**
+     * Limit the approximate memory used by the search to the given number of bytes. The estimate covers the option
+     * names held by the search state and stack, which is what grows with the depth of the search.
+     */
+    pub fn with_memory_limit(mut self, max_bytes: usize) -> ExactCoverProblem {
+        self.memory_limit = Some(max_bytes);
+        return self;
+    }
+
+    /**
+     * Enable or disable estimating the memory use of the search per data structure, which is reported in the
+     * statistics returned by count_solutions_with_stats. Useful to find out why a large problem runs out of memory.
+     */
+    pub fn with_memory_tracking(mut self, enabled: bool) -> ExactCoverProblem {
+        self.track_memory = enabled;
+        return self;
+    }
+
+    /**
+     * Solve the exact cover problem. Returns None if there is no solution or the memory limit is exceeded; use
+     * try_solve to tell these apart.
+     */
+    pub fn solve(&self) -> Option<ExactCoverSolution> {
+        return self.try_solve().unwrap_or(None);
+    }
+
+    /**
+     * Solve the exact cover problem, failing if the search is aborted because it exceeds the memory limit.
+     */
+    pub fn try_solve(&self) -> Result<Option<ExactCoverSolution>, SearchError> {
+        if self.lexicographic_order {
+            return self.solve_lexicographically();
+        }
+        if let Some(node_budget) = self.restart_node_budget {
+            return self.solve_with_restarts(node_budget);
+        }
+        if let Some(bitset) = self.bitset_backend() {
+            let mut solution: Option<ExactCoverSolution> = None;
+            bitset.for_each_solution(&mut self.filter_visit(&mut |selected_options| {
+                solution = Some(ExactCoverSolution { selected_options: selected_options.to_vec() });
+                ControlFlow::Break(())
+            }));
+            return Ok(solution);
+        }
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+        let result = self._solve_until(&mut state, 1);
+        return match result.error {
+            Some(error) => Err(error),
+            None => Ok(result.last_solution),
+        };
+    }
+
+    fn select_required_options(&self, state: &mut SearchState) {
+        let mut required_options: Vec<&String> = self.required_options.iter().collect();
+        required_options.sort();
+        for option_name in required_options {
+            self.select_option(state, option_name.clone());
+        }
+    }
+
+    /**
+     * Repeatedly select the only available option of any required item that has exactly one option left.
+     * Selecting an option can force other items in turn, so this continues until no item is forced anymore.
+     */
+    fn select_forced_options(&self, state: &mut SearchState) {
+        loop {
+            // The items queue is ordered by the smallest number of available options, so a forced item is at the top
+            let forced_item_name = match state.items_queue.peek() {
+                Some((item_name, priority)) if *priority == -1 => item_name.clone(),
+                _ => return,
+            };
+            let option_name = state.available_options.get(&forced_item_name).unwrap().iter().next().unwrap().clone();
+            info!("Selecting forced option {} for item {}", option_name, forced_item_name);
+            self.select_option(state, option_name);
+            state.stats.num_forced_options += 1;
+        }
+    }
+
+    /**
+     * Solve the exact cover problem until the given number of solutions are found.
+     */
+    fn _solve_until(&self, state: &mut SearchState, remaining_solutions: u64) -> ExactCoverResult {
+        let result = ExactCoverResult {
+            last_solution: None,
+            num_solutions: 0,
+            error: None,
+        };
+        return self._solve_into(state, result, remaining_solutions);
+    }
+
+    /**
+     * Add the solutions found by the search to the result, until it holds the given number of solutions. The count
+     * is a u64 throughout, so it can go beyond i32::MAX like the counts of large problems do.
+     */
+    fn _solve_into(&self, state: &mut SearchState, mut result: ExactCoverResult, limit: u64) -> ExactCoverResult {
+        if result.num_solutions >= limit {
+            return result;
+        }
+
+        result.error = self._visit_solutions(state, &mut |selected_options| {
+            if result.last_solution.is_none() {
+                result.last_solution = Some(ExactCoverSolution {
+                    selected_options: selected_options.to_vec(),
+                });
+            }
+            result.num_solutions += 1;
+            if result.num_solutions < limit {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        }).err();
+        return result;
+    }
+
+    /**
+     * Search for solutions and pass the selected options of each one to visit, until it returns Break.
+     *
+     * The search is a depth-first search which keeps its own stack of frames instead of recursing, so the depth of
+     * the search is only bounded by the available heap memory, or by the memory limit if one is set.
+     */
+    fn _visit_solutions(&self, state: &mut SearchState, visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> Result<(), SearchError> {
+        let mut num_solutions: u64 = 0;
+        let mut stopped = false;
+        let mut error: Option<SearchError> = None;
+        let mut used_bytes: usize = state.available_options.values()
+            .map(|option_names| estimate_bytes(option_names.iter()))
+            .sum();
+        let initial_used_bytes = used_bytes;
+        let mut peak_used_bytes = used_bytes;
+        let mut stack: Vec<SearchFrame> = Vec::new();
+        let mut descend = true;
+        loop {
+            if descend {
+                info!("Items queue: {:?}", state.get_items_queue());
+                info!("Available options: {:?}", state.available_options);
+                match state.select_new_item() {
+                    Some(item_name) => {
+                        info!("Selecting item {}", item_name);
+
+                        // The options are tried in the order in which they cover the item rather than the
+                        // iteration order of the hash set, so that every search of the problem takes the same path
+                        let available_options = state.available_options.get(&item_name).unwrap();
+                        let options: Vec<String> = self.covered_by.get(&item_name).unwrap().iter()
+                            .filter(|option_name| available_options.contains(*option_name))
+                            .cloned().collect();
+                        for observer in state.observers.iter_mut() {
+                            observer.on_item_selected(&item_name, &options);
+                        }
+                        if options.is_empty() {
+                            info!("Contradiction: item {} has no options left", item_name);
+                            // Contradiction => backtrack to the previous frame
+                            if state.node_limit.is_some() {
+                                *state.item_failures.entry(item_name.clone()).or_default() += 1;
+                            }
+                            state.return_item(item_name);
+                            state.stats.num_backtracks += 1;
+                        } else {
+                            used_bytes += estimate_bytes(options.iter());
+                            stack.push(SearchFrame {
+                                item_name,
+                                options,
+                                next_option_idx: 0,
+                                selected_option: None,
+                                num_solutions_before: num_solutions,
+                            });
+                        }
+                    }
+                    None if !self.accepts_solution(&state.selected_options) || !state.keeps_selection() => {
+                        // No more item left, but the solution is rejected by a filter => backtrack like a dead end
+                        info!("No more items left. Solution rejected by a filter: {:?}", state.selected_options);
+                        state.stats.num_backtracks += 1;
+                    }
+                    None => {
+                        // No more item left => solution found
+                        info!("No more items left. Solution found: {:?}", state.selected_options);
+                        num_solutions += 1;
+                        for observer in state.observers.iter_mut() {
+                            observer.on_solution(&state.selected_options);
+                        }
+                        stopped = visit(&state.selected_options).is_break();
+                    }
+                }
+            }
+            // The stack only grows by selecting an option and descending, so the peak is always reached here
+            peak_used_bytes = peak_used_bytes.max(used_bytes);
+
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => break,
+            };
+
+            if let Some((option_name, removed_options)) = frame.selected_option.take() {
+                info!("Unselecting option {}", option_name);
+                for observer in state.observers.iter_mut() {
+                    observer.on_backtrack(&frame.item_name, &option_name);
+                }
+                used_bytes -= estimate_bytes(removed_options.iter());
+                self.unselect_option(state, option_name, removed_options); // backtrack
+            }
+
+            if let Some(memory_limit) = self.memory_limit {
+                if used_bytes > memory_limit && error.is_none() {
+                    info!("Aborting search which uses about {} bytes", used_bytes);
+                    error = Some(SearchError::MemoryLimitExceeded(memory_limit));
+                    stopped = true;
+                }
+            }
+
+            if state.node_limit.is_some_and(|node_limit| state.stats.num_nodes >= node_limit) && !stopped {
+                info!("Stopping search after {} nodes to restart it", state.stats.num_nodes);
+                state.node_limit_reached = true;
+                stopped = true;
+            }
+
+            if frame.next_option_idx < frame.options.len() && !stopped {
+                let option_name = frame.options[frame.next_option_idx].clone();
+                frame.next_option_idx += 1;
+                info!("Selecting option {}", option_name);
+                for observer in state.observers.iter_mut() {
+                    observer.on_option_tried(&frame.item_name, &option_name);
+                }
+                let removed_options = self.select_option(state, option_name.clone());
+                state.stats.num_nodes += 1;
+                used_bytes += estimate_bytes(removed_options.iter());
+                frame.selected_option = Some((option_name, removed_options));
+                descend = state.keeps_selection();
+                if !descend {
+                    // The selected options can't be completed to a solution => backtrack like a dead end
+                    info!("Pruning the search below {:?}", state.selected_options);
+                    state.stats.num_backtracks += 1;
+                }
+            } else {
+                if num_solutions == frame.num_solutions_before {
+                    info!("No solution found for item {}", frame.item_name);
+                }
+                used_bytes -= estimate_bytes(frame.options.iter());
+                stack.pop();
+                descend = false;
+            }
+        }
+
+        if self.track_memory {
+            let items_queue_bytes = state.items_queue.capacity() * (size_of::<String>() + size_of::<i32>() + 2 * size_of::<usize>())
+                + state.items_queue.iter().map(|(item_name, _)| item_name.len()).sum::<usize>();
+            let available_options_bytes = state.available_options.capacity() * (size_of::<String>() + size_of::<HashSet<String>>())
+                + state.available_options.iter()
+                    .map(|(item_name, option_names)| item_name.len() + option_names.capacity() * size_of::<String>()
+                        + option_names.iter().map(String::len).sum::<usize>())
+                    .sum::<usize>();
+            let peak_stack_bytes = peak_used_bytes - initial_used_bytes;
+            state.stats.memory = Some(MemoryStats {
+                peak_bytes: items_queue_bytes + available_options_bytes + peak_stack_bytes,
+                items_queue_bytes,
+                available_options_bytes,
+                peak_stack_bytes,
+            });
+        }
+
+        return match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        };
+    }
+
+    /**
+     * Call visit with the selected options of every solution, without collecting the solutions. The search stops early
+     * when visit returns Break, in which case its value is returned, or when the memory limit is exceeded; use
+     * try_for_each_solution to tell the latter apart.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use std::ops::ControlFlow;
+     * use exact_cover::ExactCoverProblem;
+     *
+     * let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     *     ("1".to_string(), vec!["12".to_string(), "13".to_string(), "1".to_string()]),
+     *     ("2".to_string(), vec!["12".to_string(), "2".to_string()]),
+     *     ("3".to_string(), vec!["13".to_string(), "3".to_string()]),
+     * ]);
+     * let required_items = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+     * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+     *
+     * // Find the solution with the fewest options, or stop as soon as one with two options is found
+     * let mut fewest_options = usize::MAX;
+     * let result = problem.for_each_solution(|selected_options| {
+     *     fewest_options = fewest_options.min(selected_options.len());
+     *     if selected_options.len() == 2 {
+     *         return ControlFlow::Break(selected_options.to_vec());
+     *     }
+     *     ControlFlow::Continue(())
+     * });
+     *
+     * assert!(result.is_break());
+     * assert_eq!(fewest_options, 2);
+     * ```
+     */
+    pub fn for_each_solution<B>(&self, visit: impl FnMut(&[String]) -> ControlFlow<B>) -> ControlFlow<B> {
+        // An aborted search simply stops visiting solutions
+        return self.try_for_each_solution(visit).unwrap_or(ControlFlow::Continue(()));
+    }
+
+    /**
+     * Call visit with the selected options of every solution like for_each_solution, failing if the search is aborted
+     * because it exceeds the memory limit.
+     */
+    pub fn try_for_each_solution<B>(&self, mut visit: impl FnMut(&[String]) -> ControlFlow<B>) -> Result<ControlFlow<B>, SearchError> {
+        let mut result = ControlFlow::Continue(());
+        if let Some(bitset) = self.bitset_backend() {
+            bitset.for_each_solution(&mut self.filter_visit(&mut |selected_options| {
+                result = visit(selected_options);
+                match result {
+                    ControlFlow::Continue(()) => ControlFlow::Continue(()),
+                    ControlFlow::Break(_) => ControlFlow::Break(()),
+                }
+            }));
+            return Ok(result);
+        }
+
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+
+        self._visit_solutions(&mut state, &mut |selected_options| {
+            result = visit(selected_options);
+            match result {
+                ControlFlow::Continue(()) => ControlFlow::Continue(()),
+                ControlFlow::Break(_) => ControlFlow::Break(()),
+            }
+        })?;
+        return Ok(result);
+    }
+
+    /**
+     * Count all solutions to the exact cover problem.
+     */
+    pub fn count_all_solutions(&self) -> u64 {
+        return self.count_solutions_up_to(u64::MAX);
+    }
+
+    /**
+     * Count the solutions to the exact cover problem, stopping once the given limit is reached. If the memory limit is
+     * exceeded, the number of solutions found until then is returned.
+     */
+    pub fn count_solutions_up_to(&self, limit: u64) -> u64 {
+        // The bitset backend counts without naming the options of each solution, which filters need
+        if let Some(bitset) = self.bitset_backend().filter(|_| !self.has_solution_filters()) {
+            return bitset.count_solutions_up_to(limit);
+        }
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+        let result = self._solve_until(&mut state, limit);
+        return result.num_solutions;
+    }
+
+    /**
+     * Find out whether the problem has no, a unique or multiple solutions, counting the solutions up to the given limit.
+     * A limit of 2 or less stops the search as soon as a second solution is found, which is all that is needed to check
+     * uniqueness and much cheaper than counting all solutions.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use exact_cover::{ExactCoverProblem, SolutionMultiplicity};
+     *
+     * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     * #     ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+     * #     ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+     * # ]);
+     * # let required_items = vec!["A".to_string(), "B".to_string()];
+     * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+     *
+     * assert!(matches!(problem.solution_multiplicity(2), SolutionMultiplicity::Multiple(2)));
+     * ```
+     */
+    pub fn solution_multiplicity(&self, limit: u64) -> SolutionMultiplicity {
+        let limit = limit.max(2);
+        let mut num_solutions: u64 = 0;
+        let mut first_solution: Option<ExactCoverSolution> = None;
+        let _ = self.for_each_solution(|selected_options| {
+            num_solutions += 1;
+            if num_solutions == 1 {
+                first_solution = Some(ExactCoverSolution { selected_options: selected_options.to_vec() });
+            }
+            if num_solutions < limit {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        return match (num_solutions, first_solution) {
+            (0, _) => SolutionMultiplicity::None,
+            (1, Some(solution)) => SolutionMultiplicity::Unique(solution),
+            _ => SolutionMultiplicity::Multiple(num_solutions),
+        };
+    }
+
+    /**
+     * Select an option.
+     */
+    fn select_option(&self, state: &mut SearchState, option_name: String) -> Vec<String> {
+        state.selected_options.push(option_name.clone());
+
+        let mut removed_options: Vec<String> = Vec::new();
+        // For each item that this option covers ...
+        self.covers.get(&option_name).unwrap().iter()
+            .for_each(|item_name| {
+                // ... remove it from the items queue ...
+                info!("Removing item {}", item_name);
+                state.remove_item(item_name);
+
+                // ... and make all its options unavailable because only one option can be selected per item, in a
+                // fixed order so that the priorities of the items are updated the same way in every search
+                let available_options: Vec<String> = self.covered_by.get(item_name).unwrap().iter()
+                    .filter(|other_option_name| state.available_options.get(item_name).unwrap().contains(*other_option_name))
+                    .cloned().collect();
+                available_options.iter()
+                    .for_each(|other_option_name| {
+                        info!("Removing option {}", other_option_name);
+                        self.remove_option(state, other_option_name.clone());
+                        removed_options.push(other_option_name.clone());
+                    });
+            });
+        return removed_options;
+    }
+
+    /**
+     * Unselect an option (essentially perform the inverse of select_option).
+     */
+    fn unselect_option(&self, state: &mut SearchState, option_name: String, removed_options: Vec<String>) {
+        let removed_options_set = removed_options.iter().collect::<HashSet<_>>();
+
+        // For each item that this option covers ...
+        self.covers.get(&option_name).unwrap().iter()
+            .for_each(|item_name| {
+                // ... make all its options available again which were removed...
+                self.covered_by.get(item_name).unwrap().iter()
+                    .for_each(|other_option_name| {
+                        if removed_options_set.contains(other_option_name) {
+                            info!("Returning option {}", other_option_name);
+                            self.return_option(state, other_option_name.clone());
+                        }
+                    });
+
+                if self.required_items.contains(item_name) {
+                    // ... and return it to the items queue if it's required ...
+                    info!("Returning item {}", item_name);
+                    state.return_item(item_name.clone());
+                }
+            });
+
+        state.selected_options.pop();
+    }
+
+    /**
+     * Remove an option from the available options of all items that it covers.
+     */
+    fn remove_option(&self, state: &mut SearchState, option_name: String) {
+        // For each item that this option covers ...
+        self.covers.get(&option_name).unwrap().iter()
+            .for_each(|item_name| {
+                // ... remove the option from its available options ...
+                state.available_options.get_mut(item_name).unwrap().remove(&option_name);
+
+                // ... and update priority of the item because it has one fewer option
+                if self.required_items.contains(item_name) {
+                    state.update_priority(item_name)
+                }
+            });
+    }
+
+    /**
+     * Add an option to the available options of all items that it covers.
+     */
+    fn return_option(&self, state: &mut SearchState, option_name: String) {
+        // For each item that this option covers ...
+        self.covers.get(&option_name).unwrap().iter()
+            .for_each(|item_name| {
+                // ... add the option to its available options ...
+                state.available_options.get_mut(item_name).unwrap().insert(option_name.clone());
+
+                // ... and update priority of the item because it has one more option
+                if self.required_items.contains(item_name) {
+                    state.update_priority(item_name)
+                }
+            });
+    }
+}
+
+/**
+ * Estimate the number of bytes used by a collection of names.
+ */
+fn estimate_bytes<'a>(names: impl Iterator<Item=&'a String>) -> usize {
+    return names.map(|name| size_of::<String>() + name.len()).sum();
+}
+
+impl SearchState<'_> {
+    /**
+     * Select a new item from the items queue. Once items have failed, the item with the fewest available options per
+     * failure is selected instead, unless an item has at most one option left.
+     */
+    fn select_new_item(&mut self) -> Option<String> {
+        let top_priority = *self.items_queue.peek()?.1;
+        if self.item_failures.is_empty() || top_priority >= -1 {
+            return self.items_queue.pop().map(|(item_name, _)| item_name);
+        }
+        let item_name = self.items_queue.iter()
+            .map(|(item_name, priority)| {
+                let num_failures = self.item_failures.get(item_name).copied().unwrap_or_default();
+                (-priority as f64 / (1 + num_failures) as f64, item_name)
+            })
+            .min_by(|(weight_a, item_a), (weight_b, item_b)| weight_a.total_cmp(weight_b).then(item_a.cmp(item_b)))
+            .map(|(_, item_name)| item_name.clone())
+            .unwrap();
+        self.items_queue.remove(&item_name);
+        return Some(item_name);
+    }
+
+    /**
+     * Whether the selected options satisfy the node filter, if the search is pruned.
+     */
+    fn keeps_selection(&mut self) -> bool {
+        return match self.node_filter.as_mut() {
+            Some(keep) => keep(&self.selected_options),
+            None => true,
+        };
+    }
+
+    /**
+     * Remove an item from the items queue.
+     */
+    fn remove_item(&mut self, item_name: &String) {
+        self.items_queue.remove(item_name);
+    }
+
+    /**
+     * Add an item to the items queue.
+     */
+    fn return_item(&mut self, item_name: String) {
+        let priority = -(self.available_options.get(&item_name).unwrap().len() as i32);
+        self.items_queue.push(item_name, priority);
+    }
+
+    /**
+     * Update the priority of an item in the items queue.
+     */
+    fn update_priority(&mut self, item_name: &String) {
+        let priority = -(self.available_options.get(item_name).unwrap().len() as i32);
+        self.items_queue.change_priority(item_name, priority);
+    }
+
+    /**
+     * Get the items queue.
+     */
+    fn get_items_queue(&self) -> Vec<String> {
+        return self.items_queue.clone().into_sorted_vec();
+    }
+}
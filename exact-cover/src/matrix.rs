@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use crate::{ExactCoverProblem, InvalidProblemError};
+
+impl ExactCoverProblem {
+    /**
+     * Create an exact cover problem from its incidence matrix, in which every row is an option and every column a
+     * required item, and rows[i][j] tells whether option i covers item j. Items are named i0, i1, ... and options o0,
+     * o1, ..., padded with zeros to the same length (e.g. o08, o09, o10) so that sorting the names keeps the order of
+     * the matrix. Rows without any true entry can never be selected and are left out.
+     */
+    pub fn from_matrix(rows: &[Vec<bool>]) -> Result<ExactCoverProblem, InvalidProblemError> {
+        let num_items = rows.first().map_or(0, |row| row.len());
+        if let Some(row_idx) = rows.iter().position(|row| row.len() != num_items) {
+            return Err(InvalidProblemError::InconsistentRowLength(row_idx));
+        }
+
+        let item_names: Vec<String> = (0..num_items).map(|item_idx| padded_name('i', item_idx, num_items)).collect();
+        let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+        for (option_idx, row) in rows.iter().enumerate() {
+            let option_name = padded_name('o', option_idx, rows.len());
+            for (item_idx, covers_item) in row.iter().enumerate() {
+                if *covers_item {
+                    covered_by.entry(item_names[item_idx].clone()).or_default().push(option_name.clone());
+                }
+            }
+        }
+
+        return ExactCoverProblem::new(item_names, vec![], vec![], covered_by);
+    }
+
+    /**
+     * The incidence matrix of the problem: a row for every option and a column for every required or optional item,
+     * both sorted by name, in which an entry is true if the option covers the item. This is the inverse of from_matrix
+     * for problems created with it.
+     */
+    pub fn to_matrix(&self) -> Vec<Vec<bool>> {
+        let mut item_names: Vec<&String> = self.covered_by.keys().collect();
+        item_names.sort();
+        let mut option_names: Vec<&String> = self.covers.keys().collect();
+        option_names.sort();
+
+        return option_names.iter()
+            .map(|option_name| {
+                let covered_items = self.covers.get(*option_name).unwrap();
+                item_names.iter().map(|item_name| covered_items.contains(item_name)).collect()
+            })
+            .collect();
+    }
+}
+
+/**
+ * The name of the element with the given index out of count, with the index padded with zeros to the length of the
+ * largest index.
+ */
+fn padded_name(prefix: char, idx: usize, count: usize) -> String {
+    let width = count.saturating_sub(1).to_string().len();
+    return format!("{}{:0width$}", prefix, idx, width = width);
+}
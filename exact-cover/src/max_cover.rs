@@ -0,0 +1,153 @@
+use log::info;
+
+use crate::{ExactCoverProblem, SearchState};
+
+/**
+ * The best partial solution of an exact cover problem: a selection of options covering as many required items as
+ * possible, without covering any item more than once.
+ */
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaxCoverSolution {
+    /// The selected options
+    pub selected_options: Vec<String>,
+    /// The required items which are not covered by the selected options
+    pub uncovered_items: Vec<String>,
+}
+
+/**
+ * A level of the branch and bound search: the item being decided and the options still to try for it. After all
+ * options have been tried, the item is left uncovered.
+ */
+struct MaxCoverFrame {
+    /// The item that is decided at this level
+    item_name: String,
+    /// The options that were available for the item when it was selected
+    options: Vec<String>,
+    /// Index of the next option to try, or options.len() if the item should be left uncovered next
+    next_option_idx: usize,
+    /// The currently chosen option (None if the item is left uncovered) together with the options it removed
+    choice: Option<(Option<String>, Vec<String>)>,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Find a selection of options which covers the maximum number of required items.
+     *
+     * If the problem has an exact cover, the result is an exact cover without uncovered items. Otherwise it is the best
+     * partial assignment, which is useful to diagnose why a problem has no solution. Note that this explores a much
+     * larger search tree than solve(), so it is only practical for problems that are close to being solvable.
+     */
+    pub fn solve_max_cover(&self) -> MaxCoverSolution {
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+
+        let num_required_items = state.items_queue.len();
+        let mut uncovered_items: Vec<String> = Vec::new();
+        let mut best = MaxCoverSolution {
+            selected_options: state.selected_options.clone(),
+            uncovered_items: state.items_queue.iter().map(|(item_name, _)| item_name.clone()).collect(),
+        };
+
+        let mut stack: Vec<MaxCoverFrame> = Vec::new();
+        let mut descend = true;
+        loop {
+            if descend {
+                // Items without options left can never be covered anymore, which bounds what this branch can achieve
+                let num_uncoverable_items = state.items_queue.iter().filter(|(_, priority)| **priority == 0).count();
+                let upper_bound = num_required_items - uncovered_items.len() - num_uncoverable_items;
+
+                if upper_bound <= num_required_items - best.uncovered_items.len() {
+                    info!("Pruning branch which can cover at most {} items", upper_bound);
+                } else {
+                    match state.select_new_item() {
+                        Some(item_name) => {
+                            info!("Selecting item {}", item_name);
+                            let options: Vec<String> = state.available_options.get(&item_name).unwrap()
+                                .iter().cloned().collect();
+                            stack.push(MaxCoverFrame {
+                                item_name,
+                                options,
+                                next_option_idx: 0,
+                                choice: None,
+                            });
+                        }
+                        None => {
+                            info!("New best cover found leaving {} items uncovered", uncovered_items.len());
+                            best = MaxCoverSolution {
+                                selected_options: state.selected_options.clone(),
+                                uncovered_items: uncovered_items.clone(),
+                            };
+                            if best.uncovered_items.is_empty() {
+                                // An exact cover can't be improved upon
+                                stack.clear();
+                            }
+                        }
+                    }
+                }
+            }
+
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => break,
+            };
+
+            match frame.choice.take() {
+                Some((Some(option_name), removed_options)) => {
+                    self.unselect_option(&mut state, option_name, removed_options);
+                }
+                Some((None, removed_options)) => {
+                    uncovered_items.pop();
+                    self.return_skipped_item(&mut state, removed_options);
+                }
+                None => {}
+            }
+
+            if frame.next_option_idx < frame.options.len() {
+                let option_name = frame.options[frame.next_option_idx].clone();
+                frame.next_option_idx += 1;
+                info!("Selecting option {}", option_name);
+                let removed_options = self.select_option(&mut state, option_name.clone());
+                frame.choice = Some((Some(option_name), removed_options));
+                descend = true;
+            } else if frame.next_option_idx == frame.options.len() {
+                frame.next_option_idx += 1;
+                info!("Leaving item {} uncovered", frame.item_name);
+                let removed_options = self.skip_item(&mut state, &frame.item_name);
+                uncovered_items.push(frame.item_name.clone());
+                frame.choice = Some((None, removed_options));
+                descend = true;
+            } else {
+                let frame = stack.pop().unwrap();
+                state.return_item(frame.item_name);
+                descend = false;
+            }
+        }
+
+        return best;
+    }
+
+    /**
+     * Leave an item uncovered by taking it out of the items queue and making all of its options unavailable.
+     */
+    fn skip_item(&self, state: &mut SearchState, item_name: &String) -> Vec<String> {
+        state.remove_item(item_name);
+        let removed_options: Vec<String> = state.available_options.get(item_name).unwrap().iter().cloned().collect();
+        for option_name in removed_options.iter() {
+            self.remove_option(state, option_name.clone());
+        }
+        return removed_options;
+    }
+
+    /**
+     * Undo skip_item, making the removed options available again.
+     */
+    fn return_skipped_item(&self, state: &mut SearchState, removed_options: Vec<String>) {
+        for option_name in removed_options {
+            self.return_option(state, option_name);
+        }
+    }
+}
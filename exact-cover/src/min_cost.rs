@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use log::info;
+
+use crate::{ExactCoverProblem, InvalidProblemError, SearchFrame, SearchState};
+
+/**
+ * The cheapest solution of an exact cover problem with option costs.
+ */
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinCostSolution {
+    /// The selected options
+    pub selected_options: Vec<String>,
+    /// The sum of the costs of the selected options
+    pub cost: u64,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Set the cost of selecting each option, used by solve_min_cost. Options without a cost cost nothing.
+     */
+    pub fn with_option_costs(mut self, option_costs: HashMap<String, u64>) -> Result<ExactCoverProblem, InvalidProblemError> {
+        if let Some(option_name) = option_costs.keys().find(|option_name| !self.covers.contains_key(*option_name)) {
+            return Err(InvalidProblemError::UnknownCostOption(option_name.clone()));
+        }
+        self.option_costs = option_costs;
+        return Ok(self);
+    }
+
+    /**
+     * The cost of selecting the option.
+     */
+    fn option_cost(&self, option_name: &String) -> u64 {
+        return self.option_costs.get(option_name).copied().unwrap_or(0);
+    }
+
+    /**
+     * Find the solution with the lowest total option cost, or None if the problem has no solution.
+     *
+     * This is a branch and bound search: the options of each item are tried from cheap to expensive, and a branch is
+     * pruned as soon as it can't beat the best solution found so far. Every uncovered item still needs one of its
+     * available options, so the cost of a branch is at least its current cost plus the cheapest option of the most
+     * expensive uncovered item.
+     */
+    pub fn solve_min_cost(&self) -> Option<MinCostSolution> {
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+
+        let mut cost: u64 = state.selected_options.iter().map(|option_name| self.option_cost(option_name)).sum();
+        let mut best: Option<MinCostSolution> = None;
+        let mut stack: Vec<SearchFrame> = Vec::new();
+        let mut descend = true;
+        loop {
+            if descend {
+                let lower_bound = cost + self.min_remaining_cost(&state);
+                if best.as_ref().is_some_and(|best| lower_bound >= best.cost) {
+                    info!("Pruning branch which costs at least {}", lower_bound);
+                } else {
+                    match state.select_new_item() {
+                        Some(item_name) => {
+                            info!("Selecting item {}", item_name);
+                            let mut options: Vec<String> = state.available_options.get(&item_name).unwrap()
+                                .iter().cloned().collect();
+                            if options.is_empty() {
+                                state.return_item(item_name);
+                            } else {
+                                options.sort_by_key(|option_name| (self.option_cost(option_name), option_name.clone()));
+                                stack.push(SearchFrame {
+                                    item_name,
+                                    options,
+                                    next_option_idx: 0,
+                                    selected_option: None,
+                                    num_solutions_before: 0,
+                                });
+                            }
+                        }
+                        None => {
+                            info!("New cheapest solution found with cost {}", cost);
+                            best = Some(MinCostSolution {
+                                selected_options: state.selected_options.clone(),
+                                cost,
+                            });
+                        }
+                    }
+                }
+            }
+
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => break,
+            };
+
+            if let Some((option_name, removed_options)) = frame.selected_option.take() {
+                cost -= self.option_cost(&option_name);
+                self.unselect_option(&mut state, option_name, removed_options);
+            }
+
+            if frame.next_option_idx < frame.options.len() {
+                let option_name = frame.options[frame.next_option_idx].clone();
+                frame.next_option_idx += 1;
+                info!("Selecting option {}", option_name);
+                cost += self.option_cost(&option_name);
+                let removed_options = self.select_option(&mut state, option_name.clone());
+                frame.selected_option = Some((option_name, removed_options));
+                descend = true;
+            } else {
+                stack.pop();
+                descend = false;
+            }
+        }
+
+        return best;
+    }
+
+    /**
+     * A lower bound on the cost of covering the remaining required items: the largest cost of the cheapest available
+     * option of any of them.
+     */
+    fn min_remaining_cost(&self, state: &SearchState) -> u64 {
+        return state.items_queue.iter()
+            .map(|(item_name, _)| {
+                state.available_options.get(item_name).unwrap().iter()
+                    .map(|option_name| self.option_cost(option_name))
+                    .min()
+                    .unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0);
+    }
+}
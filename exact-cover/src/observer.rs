@@ -0,0 +1,110 @@
+use std::ops::ControlFlow;
+
+use crate::{ExactCoverProblem, ExactCoverSolution, SearchError};
+
+/**
+ * Receives the steps of a search as they happen, e.g. to visualize Algorithm X. All callbacks do nothing by default,
+ * so an observer only implements the ones it needs.
+ *
+ * Only the branching part of the search is reported: the required options and the options selected by forced option
+ * propagation are already selected when the first item is selected.
+ *
+ * ```
+ * use std::collections::HashMap;
+ * use exact_cover::{ExactCoverProblem, Observer};
+ *
+ * struct DeadEndCounter(u64);
+ *
+ * impl Observer for DeadEndCounter {
+ *     fn on_item_selected(&mut self, _item_name: &str, options: &[String]) {
+ *         if options.is_empty() {
+ *             self.0 += 1;
+ *         }
+ *     }
+ * }
+ *
+ * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+ * #     ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+ * #     ("B".to_string(), vec!["AB".to_string(), "BC".to_string()]),
+ * #     ("C".to_string(), vec!["BC".to_string()]),
+ * # ]);
+ * # let required_items = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+ * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+ * let mut dead_ends = DeadEndCounter(0);
+ *
+ * let solution = problem.try_solve_with_observers(&mut [&mut dead_ends]).unwrap();
+ * assert!(solution.is_some());
+ * ```
+ */
+pub trait Observer {
+    /**
+     * The search branches on the item, trying the given options one by one. If there are no options left, the search
+     * backtracks right away.
+     */
+    fn on_item_selected(&mut self, _item_name: &str, _options: &[String]) {}
+
+    /**
+     * The option is selected to cover the item the search branches on.
+     */
+    fn on_option_tried(&mut self, _item_name: &str, _option_name: &str) {}
+
+    /**
+     * The option is unselected again, after all solutions containing it have been found or the search was stopped.
+     */
+    fn on_backtrack(&mut self, _item_name: &str, _option_name: &str) {}
+
+    /**
+     * A solution is found, consisting of the given selected options.
+     */
+    fn on_solution(&mut self, _selected_options: &[String]) {}
+}
+
+impl ExactCoverProblem {
+    /**
+     * Solve the exact cover problem like try_solve, notifying the observers of every step of the search. Observers
+     * are notified in the order they are given. Always uses the general engine, since the other backends don't search
+     * item by item.
+     */
+    pub fn try_solve_with_observers(&self, observers: &mut [&mut dyn Observer]) -> Result<Option<ExactCoverSolution>, SearchError> {
+        let mut solution: Option<ExactCoverSolution> = None;
+        self.visit_solutions_with_observers(observers, &mut |selected_options| {
+            solution = Some(ExactCoverSolution { selected_options: selected_options.to_vec() });
+            ControlFlow::Break(())
+        })?;
+        return Ok(solution);
+    }
+
+    /**
+     * Call visit with the selected options of every solution like for_each_solution, notifying the observers of every
+     * step of the search, but fail if the search is aborted because it exceeds the memory limit.
+     */
+    pub fn for_each_solution_with_observers<B>(
+        &self,
+        observers: &mut [&mut dyn Observer],
+        mut visit: impl FnMut(&[String]) -> ControlFlow<B>) -> Result<ControlFlow<B>, SearchError>
+    {
+        let mut result = ControlFlow::Continue(());
+        self.visit_solutions_with_observers(observers, &mut |selected_options| {
+            result = visit(selected_options);
+            match result {
+                ControlFlow::Continue(()) => ControlFlow::Continue(()),
+                ControlFlow::Break(_) => ControlFlow::Break(()),
+            }
+        })?;
+        return Ok(result);
+    }
+
+    fn visit_solutions_with_observers(
+        &self,
+        observers: &mut [&mut dyn Observer],
+        visit: &mut dyn FnMut(&[String]) -> ControlFlow<()>) -> Result<(), SearchError>
+    {
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+        state.observers = observers.iter_mut().map(|observer| &mut **observer as &mut dyn Observer).collect();
+        return self._visit_solutions(&mut state, visit);
+    }
+}
@@ -0,0 +1,66 @@
+use std::any::Any;
+use std::collections::HashMap;
+
+use crate::{ExactCoverProblem, ExactCoverSolution, InvalidProblemError};
+
+/// A payload of any type, which can be shared between threads like the rest of the problem
+pub(crate) type Payload = Box<dyn Any + Send + Sync>;
+
+impl ExactCoverProblem {
+    /**
+     * Attach a payload to options, e.g. the cell and digit that an option of a Sudoku stands for, so that a solution
+     * can be converted back without parsing option names. Payloads replace any payloads attached before, and aren't
+     * serialized.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use exact_cover::ExactCoverProblem;
+     *
+     * # let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     * #     ("A".to_string(), vec!["AB".to_string()]),
+     * #     ("B".to_string(), vec!["AB".to_string()]),
+     * # ]);
+     * # let required_items = vec!["A".to_string(), "B".to_string()];
+     * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by)
+     *     .unwrap()
+     *     .with_option_payloads(HashMap::from([("AB".to_string(), (0u8, 1u8))]))
+     *     .unwrap();
+     *
+     * let solution = problem.solve().unwrap();
+     * assert_eq!(problem.solution_payloads::<(u8, u8)>(&solution), vec![&(0, 1)]);
+     * ```
+     */
+    pub fn with_option_payloads<T: Any + Send + Sync>(mut self, payloads: HashMap<String, T>) -> Result<ExactCoverProblem, InvalidProblemError> {
+        if let Some(option_name) = payloads.keys().find(|option_name| !self.covers.contains_key(*option_name)) {
+            return Err(InvalidProblemError::UnknownPayloadOption(option_name.clone()));
+        }
+        self.option_payloads = payloads.into_iter()
+            .map(|(option_name, payload)| (option_name, Box::new(payload) as Payload))
+            .collect();
+        return Ok(self);
+    }
+
+    /**
+     * The payload attached to the option, or None if it has no payload of type T.
+     */
+    pub fn option_payload<T: Any>(&self, option_name: &str) -> Option<&T> {
+        return self.option_payloads.get(option_name).and_then(|payload| payload.downcast_ref::<T>());
+    }
+
+    /**
+     * The payloads of the selected options of a solution, in the order of the options. Options without a payload of
+     * type T are skipped.
+     */
+    pub fn solution_payloads<T: Any>(&self, solution: &ExactCoverSolution) -> Vec<&T> {
+        return self.selected_payloads(&solution.selected_options);
+    }
+
+    /**
+     * The payloads of the given options, like solution_payloads, e.g. for the options passed to for_each_solution.
+     */
+    pub fn selected_payloads<T: Any>(&self, selected_options: &[String]) -> Vec<&T> {
+        return selected_options.iter()
+            .filter_map(|option_name| self.option_payload::<T>(option_name))
+            .collect();
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{ExactCoverProblem, InvalidProblemError};
+
+impl ExactCoverProblem {
+    /**
+     * Declare that an option may only be selected if another option is selected too, as (option, prerequisite) pairs,
+     * e.g. that a piece in some orientation requires its anchor.
+     *
+     * Every pair is encoded with two auxiliary items and an auxiliary option. The option covers the required item
+     * "A requires B" when B isn't selected, and the optional item "A requires B (guard)" which A covers as well. So if
+     * A is selected, the auxiliary option can't be, and only B can cover "A requires B". The auxiliary options are
+     * part of the solutions; use is_auxiliary_option or without_auxiliary_options to leave them out.
+     *
+     * The settings of the problem are kept, except for the symmetries, which don't map the auxiliary options.
+     */
+    pub fn with_prerequisites(self, prerequisites: Vec<(String, String)>) -> Result<ExactCoverProblem, InvalidProblemError> {
+        if let Some(option_name) = prerequisites.iter()
+            .flat_map(|(option_name, prerequisite_name)| [option_name, prerequisite_name])
+            .find(|option_name| !self.covers.contains_key(*option_name))
+        {
+            return Err(InvalidProblemError::UnknownPrerequisiteOption(option_name.clone()));
+        }
+
+        let mut covered_by: HashMap<String, Vec<String>> = self.covered_by.clone();
+        let mut required_items: Vec<String> = self.required_items.iter().cloned().collect();
+        required_items.sort();
+        let mut optional_items: Vec<String> = self.optional_items.iter().cloned().collect();
+        optional_items.sort();
+        let mut auxiliary_options = self.auxiliary_options.clone();
+        // Duplicate pairs are encoded once, and an option trivially is its own prerequisite
+        let prerequisites: BTreeSet<&(String, String)> = prerequisites.iter()
+            .filter(|(option_name, prerequisite_name)| option_name != prerequisite_name)
+            .collect();
+        for (option_name, prerequisite_name) in prerequisites {
+            let required_item_name = format!("{} requires {}", option_name, prerequisite_name);
+            let guard_item_name = format!("{} (guard)", required_item_name);
+            let auxiliary_option_name = format!("{} (without {})", required_item_name, prerequisite_name);
+
+            covered_by.insert(required_item_name.clone(), vec![prerequisite_name.clone(), auxiliary_option_name.clone()]);
+            covered_by.insert(guard_item_name.clone(), vec![option_name.clone(), auxiliary_option_name.clone()]);
+            required_items.push(required_item_name);
+            optional_items.push(guard_item_name);
+            auxiliary_options.insert(auxiliary_option_name);
+        }
+
+        let required_options: Vec<String> = self.required_options.iter().cloned().collect();
+        let mut problem = ExactCoverProblem::new(required_items, optional_items, required_options, covered_by)?;
+        problem.propagate_forced_options = self.propagate_forced_options;
+        problem.memory_limit = self.memory_limit;
+        problem.lexicographic_order = self.lexicographic_order;
+        problem.use_bitset_backend = self.use_bitset_backend;
+        problem.restart_node_budget = self.restart_node_budget;
+        problem.track_memory = self.track_memory;
+        problem.solution_filters = self.solution_filters;
+        problem.option_costs = self.option_costs;
+        problem.option_payloads = self.option_payloads;
+        problem.auxiliary_options = auxiliary_options;
+        return Ok(problem);
+    }
+
+    /**
+     * Whether the option was added to encode a prerequisite, rather than being an option of the original problem.
+     */
+    pub fn is_auxiliary_option(&self, option_name: &str) -> bool {
+        return self.auxiliary_options.contains(option_name);
+    }
+
+    /**
+     * The selected options of a solution without the auxiliary options added to encode prerequisites.
+     */
+    pub fn without_auxiliary_options(&self, selected_options: &[String]) -> Vec<String> {
+        return selected_options.iter()
+            .filter(|option_name| !self.is_auxiliary_option(option_name))
+            .cloned()
+            .collect();
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use crate::ExactCoverProblem;
+
+/**
+ * A small pseudo-random number generator (SplitMix64), so that random problems are reproducible from their seed
+ * without depending on the output of an external crate staying the same across versions.
+ */
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    /**
+     * A number in [0, 1).
+     */
+    fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    }
+}
+
+impl ExactCoverProblem {
+    /**
+     * Generate a random exact cover problem with the given number of required items and options, in which every option
+     * covers every item with probability density. Options that would cover no item cover one random item instead.
+     *
+     * The same arguments always give the same problem, which makes these instances suitable for benchmarks and stress
+     * tests that shouldn't depend on Sudoku-shaped problems only. Items are named i0, i1, ... and options o0, o1, ...
+     */
+    pub fn random(num_items: usize, num_options: usize, density: f64, seed: u64) -> ExactCoverProblem {
+        let mut rng = SplitMix64(seed);
+        let item_names: Vec<String> = (0..num_items).map(|item_idx| format!("i{}", item_idx)).collect();
+        let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+
+        if num_items > 0 {
+            for option_idx in 0..num_options {
+                let option_name = format!("o{}", option_idx);
+                let mut item_idxs: Vec<usize> = (0..num_items).filter(|_| rng.next_f64() < density).collect();
+                if item_idxs.is_empty() {
+                    item_idxs.push((rng.next_u64() % num_items as u64) as usize);
+                }
+                for item_idx in item_idxs {
+                    covered_by.entry(item_names[item_idx].clone()).or_default().push(option_name.clone());
+                }
+            }
+        }
+
+        return ExactCoverProblem::new(item_names, vec![], vec![], covered_by)
+            .expect("a random problem declares every item exactly once");
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use log::info;
+
+use crate::{ExactCoverProblem, ExactCoverSolution, SearchError};
+
+impl ExactCoverProblem {
+    /**
+     * Let solve restart the search when it hasn't found a solution after the given number of nodes, or disable
+     * restarts with None. The budget doubles after every restart, so the search still finishes.
+     *
+     * The search counts how often it runs into each item without options left. After a restart, it prefers the items
+     * that failed most often, which helps on problems where the initial order of the items is unlucky. Restarts only
+     * apply to solve and try_solve, since the other searches visit every solution anyway.
+     */
+    pub fn with_restarts(mut self, node_budget: Option<u64>) -> ExactCoverProblem {
+        self.restart_node_budget = node_budget;
+        return self;
+    }
+
+    /**
+     * Search for a solution, restarting with a doubled node budget and the failure counts gathered so far whenever
+     * the budget is exhausted.
+     */
+    pub(crate) fn solve_with_restarts(&self, node_budget: u64) -> Result<Option<ExactCoverSolution>, SearchError> {
+        let mut node_budget = node_budget.max(1);
+        let mut item_failures: HashMap<String, u64> = HashMap::new();
+        loop {
+            let mut state = self.new_search_state();
+            self.select_required_options(&mut state);
+            if self.propagate_forced_options {
+                self.select_forced_options(&mut state);
+            }
+            state.item_failures = item_failures;
+            state.node_limit = Some(node_budget);
+
+            let mut solution: Option<ExactCoverSolution> = None;
+            self._visit_solutions(&mut state, &mut |selected_options| {
+                solution = Some(ExactCoverSolution { selected_options: selected_options.to_vec() });
+                ControlFlow::Break(())
+            })?;
+            if solution.is_some() || !state.node_limit_reached {
+                return Ok(solution);
+            }
+
+            info!("Restarting search with a budget of {} nodes", node_budget * 2);
+            item_failures = state.item_failures;
+            node_budget = node_budget.saturating_mul(2);
+        }
+    }
+}
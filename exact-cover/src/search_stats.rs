@@ -0,0 +1,53 @@
+use crate::{ExactCoverProblem, SearchError};
+
+/**
+ * Counters of the work done by a search, which measure how hard a problem is for the solver.
+ */
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct SearchStats {
+    /// The number of options selected by the forced option propagation before the search started
+    pub num_forced_options: u64,
+    /// The number of options selected while branching, i.e. the number of nodes of the search tree below the root
+    pub num_nodes: u64,
+    /// The number of times the search ran into an item without options left and had to backtrack
+    pub num_backtracks: u64,
+    /// The estimated memory use of the search, if memory tracking is enabled
+    pub memory: Option<MemoryStats>,
+}
+
+/**
+ * The estimated memory use of a search in bytes, per data structure. The estimates count the option and item names
+ * and the allocated capacity of the collections holding them, but not the allocator's own overhead.
+ */
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct MemoryStats {
+    /// The highest memory use of all structures together
+    pub peak_bytes: usize,
+    /// The memory used by the priority queue of items. It is allocated up front and doesn't grow during the search
+    pub items_queue_bytes: usize,
+    /// The memory used by the sets of available options of each item. They are allocated up front and don't grow
+    /// during the search
+    pub available_options_bytes: usize,
+    /// The highest memory use of the search stack, i.e. the options still to try and the options removed at each level
+    pub peak_stack_bytes: usize,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Count the solutions up to the given limit like count_solutions_up_to, and also return the statistics of the
+     * search. Always uses the general engine so that the statistics are comparable between problems. The memory use
+     * is only included if it is enabled with with_memory_tracking.
+     */
+    pub fn count_solutions_with_stats(&self, limit: u64) -> Result<(u64, SearchStats), SearchError> {
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+        let result = self._solve_until(&mut state, limit);
+        return match result.error {
+            Some(error) => Err(error),
+            None => Ok((result.num_solutions, state.stats)),
+        };
+    }
+}
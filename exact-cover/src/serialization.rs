@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ExactCoverProblem;
+
+/**
+ * The serialized form of an exact cover problem: the arguments of ExactCoverProblem::new, sorted so that the same
+ * problem always serializes to the same output and can be diffed between runs.
+ */
+#[derive(Serialize, Deserialize)]
+struct ProblemDefinition {
+    required_items: Vec<String>,
+    #[serde(default)]
+    optional_items: Vec<String>,
+    #[serde(default)]
+    required_options: Vec<String>,
+    covered_by: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    propagate_forced_options: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    option_costs: BTreeMap<String, u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    symmetries: Vec<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    auxiliary_options: Vec<String>,
+}
+
+impl Serialize for ExactCoverProblem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let definition = ProblemDefinition {
+            required_items: sorted_names(&self.required_items),
+            optional_items: sorted_names(&self.optional_items),
+            required_options: sorted_names(&self.required_options),
+            covered_by: self.covered_by.iter()
+                .map(|(item_name, option_names)| (item_name.clone(), sorted_names(option_names)))
+                .collect(),
+            propagate_forced_options: self.propagate_forced_options,
+            option_costs: self.option_costs.iter()
+                .map(|(option_name, cost)| (option_name.clone(), *cost))
+                .collect(),
+            symmetries: self.symmetries.iter()
+                .map(|symmetry| symmetry.iter().map(|(option_name, image_name)| (option_name.clone(), image_name.clone())).collect())
+                .collect(),
+            auxiliary_options: sorted_names(&self.auxiliary_options),
+        };
+        return definition.serialize(serializer);
+    }
+}
+
+fn sorted_names<'a>(names: impl IntoIterator<Item=&'a String>) -> Vec<String> {
+    let mut names: Vec<String> = names.into_iter().cloned().collect();
+    names.sort();
+    return names;
+}
+
+impl<'de> Deserialize<'de> for ExactCoverProblem {
+    /**
+     * Deserialize a problem definition, validating it like ExactCoverProblem::new does.
+     */
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let definition = ProblemDefinition::deserialize(deserializer)?;
+        let covered_by: HashMap<String, Vec<String>> = definition.covered_by.into_iter().collect();
+        let mut problem = ExactCoverProblem::new(
+            definition.required_items,
+            definition.optional_items,
+            definition.required_options,
+            covered_by,
+        ).map_err(serde::de::Error::custom)?
+            .with_option_costs(definition.option_costs.into_iter().collect())
+            .map_err(serde::de::Error::custom)?
+            .with_symmetries(definition.symmetries.into_iter().map(|symmetry| symmetry.into_iter().collect()).collect())
+            .map_err(serde::de::Error::custom)?;
+        problem.auxiliary_options = definition.auxiliary_options.into_iter().collect();
+        return Ok(problem.with_forced_option_propagation(definition.propagate_forced_options));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExactCoverSolution, MaxCoverSolution};
+
+    use super::*;
+
+    fn get_problem() -> ExactCoverProblem {
+        let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+            ("A".to_string(), vec!["AB".to_string(), "A".to_string()]),
+            ("B".to_string(), vec!["AB".to_string(), "B".to_string()]),
+            ("C".to_string(), vec!["C".to_string()]),
+        ]);
+        let required_items = vec!["A".to_string(), "B".to_string()];
+        let optional_items = vec!["C".to_string()];
+        return ExactCoverProblem::new(required_items, optional_items, vec!["C".to_string()], covered_by).unwrap();
+    }
+
+    #[test]
+    fn test_serialize_problem() {
+        let problem = get_problem();
+
+        let json = serde_json::to_string(&problem).unwrap();
+
+        assert_eq!(json, r#"{"required_items":["A","B"],"optional_items":["C"],"required_options":["C"],"covered_by":{"A":["A","AB"],"B":["AB","B"],"C":["C"]},"propagate_forced_options":false}"#);
+    }
+
+    #[test]
+    fn test_problem_roundtrip() {
+        let problem = get_problem().with_forced_option_propagation(true);
+
+        let json = serde_json::to_string(&problem).unwrap();
+        let deserialized: ExactCoverProblem = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+        assert_eq!(deserialized.count_all_solutions(), problem.count_all_solutions());
+    }
+
+    #[test]
+    fn test_problem_with_costs_roundtrip() {
+        let option_costs = HashMap::from([("AB".to_string(), 3), ("A".to_string(), 1)]);
+        let problem = get_problem().with_option_costs(option_costs).unwrap();
+
+        let json = serde_json::to_string(&problem).unwrap();
+        let deserialized: ExactCoverProblem = serde_json::from_str(&json).unwrap();
+
+        assert!(json.ends_with(r#""option_costs":{"A":1,"AB":3}}"#));
+        assert_eq!(deserialized.solve_min_cost().unwrap().cost, 1);
+    }
+
+    #[test]
+    fn test_problem_with_symmetries_roundtrip() {
+        let symmetries = vec![HashMap::from([("A".to_string(), "B".to_string()), ("B".to_string(), "A".to_string())])];
+        let problem = get_problem().with_symmetries(symmetries).unwrap();
+
+        let json = serde_json::to_string(&problem).unwrap();
+        let deserialized: ExactCoverProblem = serde_json::from_str(&json).unwrap();
+
+        assert!(json.ends_with(r#""symmetries":[{"A":"B","B":"A"}]}"#));
+        assert_eq!(deserialized.count_solutions_up_to_symmetry(), problem.count_solutions_up_to_symmetry());
+    }
+
+    #[test]
+    fn test_problem_with_prerequisites_roundtrip() {
+        let problem = get_problem().with_prerequisites(vec![("A".to_string(), "B".to_string())]).unwrap();
+
+        let json = serde_json::to_string(&problem).unwrap();
+        let deserialized: ExactCoverProblem = serde_json::from_str(&json).unwrap();
+
+        assert!(json.ends_with(r#""auxiliary_options":["A requires B (without B)"]}"#));
+        assert!(deserialized.is_auxiliary_option("A requires B (without B)"));
+        assert_eq!(deserialized.count_all_solutions(), problem.count_all_solutions());
+    }
+
+    #[test]
+    fn test_deserialize_invalid_problem() {
+        let json = r#"{"required_items":["A"],"covered_by":{"A":["A"],"B":["B"]}}"#;
+
+        let result = serde_json::from_str::<ExactCoverProblem>(json);
+
+        assert_eq!(result.err().unwrap().to_string(), "item B is covered by options but not declared as required or optional item");
+    }
+
+    #[test]
+    fn test_solution_roundtrip() {
+        let solution = ExactCoverSolution { selected_options: vec!["AB".to_string(), "C".to_string()] };
+        let max_cover_solution = MaxCoverSolution {
+            selected_options: vec!["AB".to_string()],
+            uncovered_items: vec!["C".to_string()],
+        };
+
+        let json = serde_json::to_string(&solution).unwrap();
+        let max_cover_json = serde_json::to_string(&max_cover_solution).unwrap();
+
+        assert_eq!(json, r#"{"selected_options":["AB","C"]}"#);
+        let deserialized: ExactCoverSolution = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.selected_options, solution.selected_options);
+        assert_eq!(max_cover_json, r#"{"selected_options":["AB"],"uncovered_items":["C"]}"#);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+
+use log::info;
+
+use crate::ExactCoverProblem;
+
+/**
+ * What simplify removed from an exact cover problem. All lists are sorted by name.
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct SimplificationReport {
+    /// Options that can't be part of any solution because they cover an item j but not an item i, while every option
+    /// covering i also covers j
+    pub dominated_options: Vec<String>,
+    /// Options that overlap a required or forced option
+    pub conflicting_options: Vec<String>,
+    /// Required items that can be covered in only one way, together with the option covering them, which is now a
+    /// required option
+    pub forced_items: Vec<(String, String)>,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Shrink the problem before searching by repeatedly applying two reductions until neither applies anymore:
+     *
+     * - A required item with only one option left forces that option, which becomes a required option. All options
+     *   overlapping it are removed.
+     * - If every option covering a required item i also covers an item j, any option covering j but not i is removed:
+     *   selecting it would leave no way to cover i without covering j twice.
+     *
+     * The simplified problem has exactly the same solutions. The settings of the problem are kept, except for the
+     * symmetries, which may map removed options. If the problem has no solution, simplifying can leave a required item
+     * without options.
+     *
+     * ```
+     * use std::collections::HashMap;
+     * use exact_cover::ExactCoverProblem;
+     *
+     * // C can only be covered by BC, which rules out AB
+     * let covered_by: HashMap<String, Vec<String>> = HashMap::from([
+     *     ("A".to_string(), vec!["A".to_string(), "AB".to_string()]),
+     *     ("B".to_string(), vec!["AB".to_string(), "BC".to_string()]),
+     *     ("C".to_string(), vec!["BC".to_string()]),
+     * ]);
+     * let required_items = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+     * let problem = ExactCoverProblem::new(required_items, vec![], vec![], covered_by).unwrap();
+     *
+     * let (simplified, report) = problem.simplify();
+     *
+     * assert_eq!(report.conflicting_options, vec!["AB".to_string()]);
+     * assert_eq!(simplified.count_all_solutions(), 1);
+     * ```
+     */
+    pub fn simplify(self) -> (ExactCoverProblem, SimplificationReport) {
+        let mut report = SimplificationReport::default();
+        let mut available_options: HashMap<&String, HashSet<&String>> = self.covered_by.iter()
+            .map(|(item_name, option_names)| (item_name, option_names.iter().collect()))
+            .collect();
+        let mut settled_items: HashSet<&String> = HashSet::new();
+        let mut removed_options: HashSet<&String> = HashSet::new();
+
+        let mut required_options: Vec<&String> = self.required_options.iter().collect();
+        required_options.sort();
+        for option_name in required_options.iter() {
+            self.settle_option(option_name, &mut available_options, &mut settled_items, &mut removed_options, &mut report.conflicting_options);
+        }
+
+        let mut required_items: Vec<&String> = self.required_items.iter().collect();
+        required_items.sort();
+        loop {
+            let mut changed = false;
+            for item_name in required_items.iter() {
+                if settled_items.contains(item_name) || available_options[item_name].len() != 1 {
+                    continue;
+                }
+                let option_name = *available_options[item_name].iter().next().unwrap();
+                info!("Item {} is forced to be covered by option {}", item_name, option_name);
+                report.forced_items.push(((*item_name).clone(), option_name.clone()));
+                required_options.push(option_name);
+                self.settle_option(option_name, &mut available_options, &mut settled_items, &mut removed_options, &mut report.conflicting_options);
+                changed = true;
+            }
+
+            for item_name in required_items.iter() {
+                let first_option_name = match available_options[item_name].iter().min() {
+                    Some(option_name) if !settled_items.contains(item_name) => *option_name,
+                    _ => continue,
+                };
+                for other_item_name in self.covers[first_option_name].iter() {
+                    if other_item_name == *item_name || settled_items.contains(other_item_name) {
+                        continue;
+                    }
+                    let options = &available_options[item_name];
+                    let other_options = &available_options[other_item_name];
+                    if !options.is_subset(other_options) {
+                        continue;
+                    }
+                    let mut dominated_options: Vec<&String> = other_options.difference(options).cloned().collect();
+                    dominated_options.sort();
+                    for option_name in dominated_options {
+                        info!("Option {} is dominated, since every option for item {} covers item {}", option_name, item_name, other_item_name);
+                        self.remove_available_option(option_name, &mut available_options, &mut removed_options);
+                        report.dominated_options.push(option_name.clone());
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+        report.dominated_options.sort();
+        report.conflicting_options.sort();
+        report.forced_items.sort();
+
+        let covered_by: HashMap<String, Vec<String>> = self.covered_by.iter()
+            .map(|(item_name, option_names)| {
+                let option_names = option_names.iter().filter(|option_name| !removed_options.contains(option_name)).cloned().collect();
+                (item_name.clone(), option_names)
+            })
+            .collect();
+        let mut optional_items: Vec<String> = self.optional_items.iter().cloned().collect();
+        optional_items.sort();
+        let required_items: Vec<String> = required_items.into_iter().cloned().collect();
+        let required_options: Vec<String> = required_options.into_iter().cloned().collect();
+        let removed_options: HashSet<String> = removed_options.into_iter().cloned().collect();
+
+        // The forced options don't overlap any other required option, since overlapping options are removed as soon as
+        // an option is required
+        let mut simplified = ExactCoverProblem::new(required_items, optional_items, required_options, covered_by).unwrap();
+        simplified.propagate_forced_options = self.propagate_forced_options;
+        simplified.memory_limit = self.memory_limit;
+        simplified.lexicographic_order = self.lexicographic_order;
+        simplified.use_bitset_backend = self.use_bitset_backend;
+        simplified.restart_node_budget = self.restart_node_budget;
+        simplified.track_memory = self.track_memory;
+        simplified.solution_filters = self.solution_filters;
+        simplified.option_costs = self.option_costs.into_iter()
+            .filter(|(option_name, _)| !removed_options.contains(option_name))
+            .collect();
+        simplified.option_payloads = self.option_payloads.into_iter()
+            .filter(|(option_name, _)| !removed_options.contains(option_name))
+            .collect();
+        simplified.auxiliary_options = self.auxiliary_options.into_iter()
+            .filter(|option_name| !removed_options.contains(option_name))
+            .collect();
+        return (simplified, report);
+    }
+
+    /**
+     * Mark the items of a required option as settled and remove all other options covering them.
+     */
+    fn settle_option<'a>(
+        &'a self,
+        option_name: &'a String,
+        available_options: &mut HashMap<&'a String, HashSet<&'a String>>,
+        settled_items: &mut HashSet<&'a String>,
+        removed_options: &mut HashSet<&'a String>,
+        conflicting_options: &mut Vec<String>)
+    {
+        for item_name in self.covers[option_name].iter() {
+            settled_items.insert(item_name);
+            let other_option_names: Vec<&String> = available_options[item_name].iter()
+                .filter(|other_option_name| **other_option_name != option_name)
+                .cloned()
+                .collect();
+            for other_option_name in other_option_names {
+                self.remove_available_option(other_option_name, available_options, removed_options);
+                conflicting_options.push(other_option_name.clone());
+            }
+        }
+    }
+
+    fn remove_available_option<'a>(
+        &'a self,
+        option_name: &'a String,
+        available_options: &mut HashMap<&'a String, HashSet<&'a String>>,
+        removed_options: &mut HashSet<&'a String>)
+    {
+        for item_name in self.covers[option_name].iter() {
+            available_options.get_mut(item_name).unwrap().remove(option_name);
+        }
+        removed_options.insert(option_name);
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use crate::ExactCoverProblem;
+
+/**
+ * Statistics about the size and shape of an exact cover problem, useful to sanity-check an encoding before solving it.
+ */
+#[derive(Debug, PartialEq)]
+pub struct ProblemStats {
+    /// The number of items, both required and optional
+    pub num_items: usize,
+    /// The number of optional items
+    pub num_optional_items: usize,
+    /// The number of options
+    pub num_options: usize,
+    /// The number of required options
+    pub num_required_options: usize,
+    /// The smallest number of options covering a single item
+    pub min_options_per_item: usize,
+    /// The largest number of options covering a single item
+    pub max_options_per_item: usize,
+    /// The average number of options covering an item
+    pub avg_options_per_item: f64,
+    /// The fraction of (item, option) pairs in which the option covers the item
+    pub density: f64,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Compute statistics about the items and options of the exact cover problem.
+     */
+    pub fn stats(&self) -> ProblemStats {
+        let num_items = self.covered_by.len();
+        let num_options = self.covers.len();
+        let options_per_item: Vec<usize> = self.covered_by.values().map(|option_names| option_names.len()).collect();
+        let num_incidences: usize = options_per_item.iter().sum();
+
+        return ProblemStats {
+            num_items,
+            num_optional_items: self.optional_items.len(),
+            num_options,
+            num_required_options: self.required_options.len(),
+            min_options_per_item: options_per_item.iter().copied().min().unwrap_or(0),
+            max_options_per_item: options_per_item.iter().copied().max().unwrap_or(0),
+            avg_options_per_item: if num_items == 0 { 0.0 } else { num_incidences as f64 / num_items as f64 },
+            density: if num_items == 0 || num_options == 0 {
+                0.0
+            } else {
+                num_incidences as f64 / (num_items as f64 * num_options as f64)
+            },
+        };
+    }
+}
+
+impl fmt::Display for ProblemStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Items: {} ({} optional)", self.num_items, self.num_optional_items)?;
+        writeln!(f, "Options: {} ({} required)", self.num_options, self.num_required_options)?;
+        writeln!(f, "Options per item: min {}, max {}, avg {:.2}",
+                 self.min_options_per_item, self.max_options_per_item, self.avg_options_per_item)?;
+        write!(f, "Density: {:.4}", self.density)
+    }
+}
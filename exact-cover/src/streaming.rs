@@ -0,0 +1,41 @@
+use std::ops::ControlFlow;
+use std::sync::mpsc::{Receiver, sync_channel};
+use std::thread;
+
+use log::info;
+
+use crate::{ExactCoverProblem, ExactCoverSolution, SearchError};
+
+/// The number of solutions the search may run ahead of the receiver before it waits
+const STREAMING_BUFFER_SIZE: usize = 64;
+
+impl ExactCoverProblem {
+    /**
+     * Search for all solutions on a worker thread and send each one over the returned channel as soon as it is found.
+     *
+     * The channel is bounded, so the search pauses when the receiver falls behind, and it stops as soon as the receiver
+     * is dropped. The channel is closed once all solutions have been sent. If the search is aborted because it exceeds
+     * the memory limit, the error is sent as the last message, so that the receiver doesn't mistake the solutions found
+     * so far for all of them.
+     */
+    pub fn solve_streaming(self) -> Receiver<Result<ExactCoverSolution, SearchError>> {
+        let (sender, receiver) = sync_channel(STREAMING_BUFFER_SIZE);
+        thread::spawn(move || {
+            let result: Result<ControlFlow<()>, SearchError> = self.try_for_each_solution(|selected_options| {
+                let solution = ExactCoverSolution { selected_options: selected_options.to_vec() };
+                match sender.send(Ok(solution)) {
+                    Ok(()) => ControlFlow::Continue(()),
+                    Err(_) => {
+                        info!("Receiver dropped, stopping the search");
+                        ControlFlow::Break(())
+                    }
+                }
+            });
+            if let Err(error) = result {
+                // The receiver may be gone already, in which case nobody is waiting for the error
+                let _ = sender.send(Err(error));
+            }
+        });
+        return receiver;
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+
+use log::info;
+
+use crate::{ExactCoverProblem, InvalidProblemError};
+
+/**
+ * The number of solutions of an exact cover problem with symmetries, counted once per class of symmetric solutions
+ * and in total.
+ */
+#[derive(Debug, PartialEq)]
+pub struct SymmetryCounts {
+    /// The number of classes of solutions that are symmetric images of each other
+    pub num_classes: u64,
+    /// The total number of solutions, i.e. the sum of the sizes of all classes
+    pub num_solutions: u64,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Declare symmetries of the problem. Each symmetry maps options to options; options it doesn't mention are mapped
+     * to themselves. The symmetries generate a group, e.g. a 90 degree rotation and a mirror generate all 8 symmetries
+     * of a square board.
+     *
+     * Every symmetry must map every solution to a solution, which is not checked. Only the options are checked: they
+     * must exist and no two options may be mapped to the same option.
+     */
+    pub fn with_symmetries(mut self, symmetries: Vec<HashMap<String, String>>) -> Result<ExactCoverProblem, InvalidProblemError> {
+        for symmetry in symmetries.iter() {
+            if let Some(option_name) = symmetry.iter()
+                .flat_map(|(option_name, image_name)| [option_name, image_name])
+                .find(|option_name| !self.covers.contains_key(*option_name))
+            {
+                return Err(InvalidProblemError::UnknownSymmetryOption(option_name.clone()));
+            }
+
+            let mut images: HashSet<&String> = HashSet::new();
+            for option_name in self.covers.keys() {
+                let image_name = symmetry.get(option_name).unwrap_or(option_name);
+                if !images.insert(image_name) {
+                    return Err(InvalidProblemError::NonBijectiveSymmetry(image_name.clone()));
+                }
+            }
+        }
+        self.symmetries = symmetries;
+        return Ok(self);
+    }
+
+    /**
+     * Call visit with one solution of every class of symmetric solutions, together with the size of its class.
+     *
+     * Branches are pruned at the first item the search branches on: once all solutions with one of its options have
+     * been visited, the images of that option under the symmetries are not selected anymore, because any solution
+     * containing them is the image of a solution that was already visited. The remaining duplicates are filtered out
+     * by comparing the canonical form of each solution, i.e. its smallest image.
+     */
+    pub fn for_each_solution_up_to_symmetry<B>(&self, mut visit: impl FnMut(&[String], u64) -> ControlFlow<B>) -> ControlFlow<B> {
+        let mut option_names: Vec<&String> = self.covers.keys().collect();
+        option_names.sort();
+        let option_indices: HashMap<&String, usize> = option_names.iter().enumerate()
+            .map(|(option_idx, option_name)| (*option_name, option_idx))
+            .collect();
+        let group = self.symmetry_group(&option_names, &option_indices);
+
+        let mut canonical_solutions: HashSet<Vec<usize>> = HashSet::new();
+        let mut result = ControlFlow::Continue(());
+        let mut visit_if_canonical = |selected_options: &[String], result: &mut ControlFlow<B>| {
+            let (canonical_solution, class_size) = canonical_form(selected_options, &option_indices, &group);
+            if !canonical_solutions.insert(canonical_solution) {
+                return ControlFlow::Continue(());
+            }
+            *result = visit(selected_options, class_size);
+            match result {
+                ControlFlow::Continue(()) => ControlFlow::Continue(()),
+                ControlFlow::Break(_) => ControlFlow::Break(()),
+            }
+        };
+
+        let mut state = self.new_search_state();
+        self.select_required_options(&mut state);
+        if self.propagate_forced_options {
+            self.select_forced_options(&mut state);
+        }
+
+        let root_item_name = match state.select_new_item() {
+            Some(item_name) => item_name,
+            None => {
+                let _ = visit_if_canonical(&state.selected_options, &mut result);
+                return result;
+            }
+        };
+        let mut root_options: Vec<String> = state.available_options.get(&root_item_name).unwrap().iter().cloned().collect();
+        root_options.sort();
+        state.return_item(root_item_name);
+
+        let mut pruned_options: HashSet<usize> = HashSet::new();
+        for option_name in root_options {
+            let option_idx = option_indices[&option_name];
+            if !pruned_options.contains(&option_idx) {
+                // Options that are symmetric images of earlier root options can't be part of new solutions
+                let mut removed_options: Vec<String> = Vec::new();
+                for pruned_option_idx in pruned_options.iter() {
+                    let pruned_option_name = option_names[*pruned_option_idx];
+                    let item_name = &self.covers.get(pruned_option_name).unwrap()[0];
+                    if state.available_options.get(item_name).unwrap().contains(pruned_option_name) {
+                        self.remove_option(&mut state, pruned_option_name.clone());
+                        removed_options.push(pruned_option_name.clone());
+                    }
+                }
+
+                info!("Selecting root option {}", option_name);
+                let removed_by_selection = self.select_option(&mut state, option_name.clone());
+                // An aborted search simply stops visiting solutions
+                let _ = self._visit_solutions(&mut state, &mut |selected_options| visit_if_canonical(selected_options, &mut result));
+                self.unselect_option(&mut state, option_name, removed_by_selection);
+                for removed_option_name in removed_options {
+                    self.return_option(&mut state, removed_option_name);
+                }
+                if result.is_break() {
+                    return result;
+                }
+            } else {
+                info!("Pruning root option {} which is symmetric to an earlier one", option_name);
+            }
+            pruned_options.extend(group.iter().map(|permutation| permutation[option_idx]));
+        }
+        return result;
+    }
+
+    /**
+     * Count the classes of symmetric solutions and the total number of solutions, which is found by adding up the
+     * sizes of the classes instead of visiting every solution.
+     */
+    pub fn count_solutions_up_to_symmetry(&self) -> SymmetryCounts {
+        let mut counts = SymmetryCounts {
+            num_classes: 0,
+            num_solutions: 0,
+        };
+        let _: ControlFlow<()> = self.for_each_solution_up_to_symmetry(|_, class_size| {
+            counts.num_classes += 1;
+            counts.num_solutions += class_size;
+            ControlFlow::Continue(())
+        });
+        return counts;
+    }
+
+    /**
+     * All permutations of the options generated by the symmetries, including the identity, as maps from option index
+     * to the index of its image.
+     */
+    fn symmetry_group(&self, option_names: &[&String], option_indices: &HashMap<&String, usize>) -> Vec<Vec<usize>> {
+        let generators: Vec<Vec<usize>> = self.symmetries.iter()
+            .map(|symmetry| {
+                option_names.iter()
+                    .map(|option_name| option_indices[symmetry.get(*option_name).unwrap_or(option_name)])
+                    .collect()
+            })
+            .collect();
+
+        let identity: Vec<usize> = (0..option_names.len()).collect();
+        let mut group: Vec<Vec<usize>> = vec![identity.clone()];
+        let mut seen: HashSet<Vec<usize>> = HashSet::from([identity]);
+        let mut next_idx = 0;
+        while next_idx < group.len() {
+            for generator in generators.iter() {
+                let composed: Vec<usize> = group[next_idx].iter().map(|option_idx| generator[*option_idx]).collect();
+                if seen.insert(composed.clone()) {
+                    group.push(composed);
+                }
+            }
+            next_idx += 1;
+        }
+        return group;
+    }
+}
+
+/**
+ * The canonical form of a solution, i.e. the smallest of its images as sorted option indices, together with the
+ * number of different images, which is the size of its class.
+ */
+fn canonical_form(selected_options: &[String], option_indices: &HashMap<&String, usize>, group: &[Vec<usize>]) -> (Vec<usize>, u64) {
+    let mut images: Vec<Vec<usize>> = group.iter()
+        .map(|permutation| {
+            let mut image: Vec<usize> = selected_options.iter()
+                .map(|option_name| permutation[option_indices[option_name]])
+                .collect();
+            image.sort();
+            image
+        })
+        .collect();
+    images.sort();
+    images.dedup();
+    let class_size = images.len() as u64;
+    return (images.swap_remove(0), class_size);
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use log::info;
+
+use crate::{ExactCoverProblem, SearchState};
+
+/**
+ * The reason why a selection of required options can't be extended to a solution.
+ */
+#[derive(Debug, PartialEq)]
+pub enum Contradiction {
+    /// The item is covered by more than one of the selected options
+    CoveredTwice(String),
+    /// The required item has no available options left, so it can't be covered anymore
+    Uncoverable(String),
+}
+
+/**
+ * A small set of required options which together already make the exact cover problem unsatisfiable.
+ */
+#[derive(Debug, PartialEq)]
+pub struct UnsatisfiableCore {
+    /// A minimal set of required options without solution: removing any one of them makes the problem satisfiable
+    pub required_options: Vec<String>,
+    /// The contradiction those options lead to, if it shows up without searching (i.e. after propagation)
+    pub contradiction: Option<Contradiction>,
+}
+
+impl ExactCoverProblem {
+    /**
+     * Explain why the exact cover problem has no solution. Returns None if the problem does have a solution.
+     *
+     * The explanation is found by removing required options one at a time and keeping them out whenever the problem
+     * stays unsatisfiable without them, which needs one search per required option. Required options covering the same
+     * item are already rejected by ExactCoverProblem::new, so they don't need to be considered here.
+     */
+    pub fn explain_unsatisfiability(&self) -> Option<UnsatisfiableCore> {
+        let mut core: Vec<String> = self.required_options.iter().cloned().collect();
+        core.sort();
+        if self.is_satisfiable_with(&core) {
+            return None;
+        }
+
+        let mut idx = 0;
+        while idx < core.len() {
+            let mut smaller_core = core.clone();
+            let option_name = smaller_core.remove(idx);
+            if self.is_satisfiable_with(&smaller_core) {
+                info!("Required option {} is part of the unsatisfiable core", option_name);
+                idx += 1;
+            } else {
+                core = smaller_core;
+            }
+        }
+
+        let mut state = self.new_search_state();
+        let contradiction = self.select_and_propagate(&mut state, &core);
+        return Some(UnsatisfiableCore {
+            required_options: core,
+            contradiction,
+        });
+    }
+
+    /**
+     * Whether the problem has a solution when only the given options are required.
+     */
+    fn is_satisfiable_with(&self, required_options: &[String]) -> bool {
+        let mut state = self.new_search_state();
+        if self.select_and_propagate(&mut state, required_options).is_some() {
+            return false;
+        }
+        return self._solve_until(&mut state, 1).num_solutions > 0;
+    }
+
+    /**
+     * Select the given options followed by all forced options, and report the first contradiction found.
+     */
+    fn select_and_propagate(&self, state: &mut SearchState, option_names: &[String]) -> Option<Contradiction> {
+        for option_name in option_names {
+            let first_item_name = &self.covers.get(option_name).unwrap()[0];
+            if !state.available_options.get(first_item_name).unwrap().contains(option_name) {
+                // The option was removed because an already selected option covers one of its items
+                let covered_items: HashSet<&String> = state.selected_options.iter()
+                    .flat_map(|selected_option_name| self.covers.get(selected_option_name).unwrap())
+                    .collect();
+                let item_name = self.covers.get(option_name).unwrap().iter()
+                    .find(|item_name| covered_items.contains(item_name))
+                    .unwrap();
+                return Some(Contradiction::CoveredTwice(item_name.clone()));
+            }
+            self.select_option(state, option_name.clone());
+        }
+
+        self.select_forced_options(state);
+        return match state.items_queue.peek() {
+            Some((item_name, priority)) if *priority == 0 => Some(Contradiction::Uncoverable(item_name.clone())),
+            _ => None,
+        };
+    }
+}
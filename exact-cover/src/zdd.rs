@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use log::info;
+
+use crate::{ExactCoverProblem, ExactCoverSolution};
+use crate::random::SplitMix64;
+
+/// Index of the terminal node which represents the empty family (no solutions)
+const BOTTOM: usize = 0;
+/// Index of the terminal node which represents the family containing only the empty set (one solution)
+const TOP: usize = 1;
+
+/**
+ * A zero-suppressed decision diagram (ZDD) of all solutions of an exact cover problem, as built by Knuth's DLX-to-ZDD
+ * approach. See https://en.wikipedia.org/wiki/Zero-suppressed_decision_diagram.
+ *
+ * Every non-terminal node stands for "select this option and continue with the HI node, or don't and continue with the
+ * LO node". Every path from the root to the TOP terminal that takes a HI edge for each selected option is one solution,
+ * so subproblems that are reached in several ways are stored only once. This makes it possible to count and enumerate
+ * astronomically many solutions without visiting them one by one.
+ */
+#[derive(Debug)]
+pub struct Zdd {
+    /// The option names, indexed by the option index stored in the nodes
+    option_names: Vec<String>,
+    /// The nodes of the diagram, of which the first two are the BOTTOM and TOP terminals
+    nodes: Vec<ZddNode>,
+    /// The number of solutions represented by each node
+    counts: Vec<u128>,
+    /// The root node of the diagram
+    root: usize,
+    /// The required options, which are part of every solution but not stored in the nodes
+    required_options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ZddNode {
+    /// The index of the option this node decides on
+    option: usize,
+    /// The node to continue with if the option is not selected
+    lo: usize,
+    /// The node to continue with if the option is selected
+    hi: usize,
+}
+
+/**
+ * A subproblem of which the ZDD is being built: the options of the chosen item, of which the ones that lead to a
+ * solution are chained together into a single node.
+ */
+struct ZddFrame {
+    /// The covered items that define the subproblem
+    covered: Vec<u64>,
+    /// The options available to cover the chosen item
+    options: Vec<usize>,
+    /// Index of the next option to build the subproblem of
+    next_option_idx: usize,
+    /// The node chaining the options processed so far
+    node: usize,
+}
+
+/**
+ * Builds a ZDD by a depth-first search over subproblems, which are identified by the set of covered items.
+ */
+struct ZddBuilder<'a> {
+    /// The required items, as indices into items
+    required_items: Vec<usize>,
+    /// The options covering each item
+    covered_by: Vec<Vec<usize>>,
+    /// The items each option covers
+    covers: Vec<Vec<usize>>,
+    /// The nodes built so far
+    nodes: Vec<ZddNode>,
+    /// Map from node to its index, so that equal nodes are only stored once
+    unique_nodes: HashMap<ZddNode, usize>,
+    /// Map from subproblem (covered items) to the node representing its solutions
+    memo: HashMap<Vec<u64>, usize>,
+    /// The option names, indexed like covers
+    option_names: &'a [String],
+}
+
+impl ExactCoverProblem {
+    /**
+     * Build a zero-suppressed decision diagram of all solutions to the exact cover problem.
+     */
+    pub fn build_zdd(&self) -> Zdd {
+        let mut item_names: Vec<&String> = self.covered_by.keys().collect();
+        item_names.sort();
+        let item_indices: HashMap<&String, usize> = item_names.iter().enumerate()
+            .map(|(item_idx, item_name)| (*item_name, item_idx))
+            .collect();
+        let mut option_names: Vec<String> = self.covers.keys().cloned().collect();
+        option_names.sort();
+        let option_indices: HashMap<&String, usize> = option_names.iter().enumerate()
+            .map(|(option_idx, option_name)| (option_name, option_idx))
+            .collect();
+
+        let mut required_items: Vec<usize> = self.required_items.iter().map(|item_name| item_indices[item_name]).collect();
+        required_items.sort();
+        let covered_by: Vec<Vec<usize>> = item_names.iter()
+            .map(|item_name| self.covered_by[*item_name].iter().map(|option_name| option_indices[option_name]).collect())
+            .collect();
+        let covers: Vec<Vec<usize>> = option_names.iter()
+            .map(|option_name| self.covers[option_name].iter().map(|item_name| item_indices[item_name]).collect())
+            .collect();
+
+        let mut required_options: Vec<String> = self.required_options.iter().cloned().collect();
+        required_options.sort();
+
+        let mut builder = ZddBuilder {
+            required_items,
+            covered_by,
+            covers,
+            nodes: vec![
+                ZddNode { option: usize::MAX, lo: BOTTOM, hi: BOTTOM },
+                ZddNode { option: usize::MAX, lo: TOP, hi: TOP },
+            ],
+            unique_nodes: HashMap::new(),
+            memo: HashMap::new(),
+            option_names: &option_names,
+        };
+
+        // The required options are selected up front, so the search starts with their items covered
+        let mut covered = vec![0u64; item_names.len().div_ceil(64)];
+        for option_name in required_options.iter() {
+            for &item_idx in builder.covers[option_indices[option_name]].iter() {
+                covered[item_idx / 64] |= 1 << (item_idx % 64);
+            }
+        }
+        let root = builder.build(covered);
+        info!("Built ZDD with {} nodes for {} subproblems", builder.nodes.len(), builder.memo.len());
+
+        // Nodes are only ever added after their LO and HI nodes, so the counts can be computed in index order
+        let mut counts: Vec<u128> = vec![0; builder.nodes.len()];
+        counts[TOP] = 1;
+        for node_idx in (TOP + 1)..builder.nodes.len() {
+            let node = &builder.nodes[node_idx];
+            counts[node_idx] = counts[node.lo] + counts[node.hi];
+        }
+
+        Zdd {
+            nodes: builder.nodes,
+            counts,
+            root,
+            required_options,
+            option_names,
+        }
+    }
+
+    /**
+     * Pick a solution uniformly at random, or None if there are no solutions. This builds the ZDD of all solutions, so
+     * use build_zdd and Zdd::sample_solution instead to draw many samples.
+     */
+    pub fn sample_solution(&self, seed: u64) -> Option<ExactCoverSolution> {
+        return self.build_zdd().sample_solution(seed);
+    }
+}
+
+impl ZddBuilder<'_> {
+    /**
+     * Build the node representing all solutions of the subproblem in which the given items are already covered.
+     */
+    fn build(&mut self, covered: Vec<u64>) -> usize {
+        let mut stack: Vec<ZddFrame> = Vec::new();
+        let mut child_node = match self.open(covered) {
+            Ok(node) => return node,
+            Err(frame) => {
+                stack.push(frame);
+                None
+            }
+        };
+
+        loop {
+            let frame = stack.last_mut().unwrap();
+            if let Some(child_node) = child_node.take() {
+                // The last option tried leads to solutions, so chain it in front of the previously processed options
+                if child_node != BOTTOM {
+                    let option = frame.options[frame.next_option_idx - 1];
+                    frame.node = Self::add_node(&mut self.nodes, &mut self.unique_nodes, option, frame.node, child_node);
+                }
+            }
+
+            if frame.next_option_idx < frame.options.len() {
+                let option = frame.options[frame.next_option_idx];
+                frame.next_option_idx += 1;
+                let mut child_covered = frame.covered.clone();
+                for &item_idx in self.covers[option].iter() {
+                    child_covered[item_idx / 64] |= 1 << (item_idx % 64);
+                }
+                match self.open(child_covered) {
+                    Ok(node) => child_node = Some(node),
+                    Err(child_frame) => stack.push(child_frame),
+                }
+            } else {
+                let frame = stack.pop().unwrap();
+                self.memo.insert(frame.covered, frame.node);
+                if stack.is_empty() {
+                    return frame.node;
+                }
+                child_node = Some(frame.node);
+            }
+        }
+    }
+
+    /**
+     * Start on a subproblem: return its node if it's already known or trivial, or a frame to build it otherwise.
+     */
+    fn open(&mut self, covered: Vec<u64>) -> Result<usize, ZddFrame> {
+        if let Some(node) = self.memo.get(&covered) {
+            return Ok(*node);
+        }
+
+        // Choose the uncovered required item with the fewest available options
+        let mut best_options: Option<Vec<usize>> = None;
+        for &item_idx in self.required_items.iter() {
+            if is_covered(&covered, item_idx) {
+                continue;
+            }
+            let options: Vec<usize> = self.covered_by[item_idx].iter().copied()
+                .filter(|option| self.covers[*option].iter().all(|other_item_idx| !is_covered(&covered, *other_item_idx)))
+                .collect();
+            if best_options.as_ref().is_none_or(|best_options| options.len() < best_options.len()) {
+                let is_empty = options.is_empty();
+                best_options = Some(options);
+                if is_empty {
+                    break;
+                }
+            }
+        }
+
+        return match best_options {
+            None => Ok(TOP),
+            Some(options) if options.is_empty() => {
+                self.memo.insert(covered, BOTTOM);
+                Ok(BOTTOM)
+            }
+            Some(mut options) => {
+                // Options are chained from last to first, so that the first option ends up at the top of the chain
+                options.sort_by(|a, b| self.option_names[*b].cmp(&self.option_names[*a]));
+                Err(ZddFrame {
+                    covered,
+                    options,
+                    next_option_idx: 0,
+                    node: BOTTOM,
+                })
+            }
+        };
+    }
+
+    /**
+     * Add a node to the diagram, reusing an equal node if it exists already.
+     */
+    fn add_node(nodes: &mut Vec<ZddNode>, unique_nodes: &mut HashMap<ZddNode, usize>, option: usize, lo: usize, hi: usize) -> usize {
+        let node = ZddNode { option, lo, hi };
+        return *unique_nodes.entry(node).or_insert_with(|| {
+            nodes.push(node);
+            nodes.len() - 1
+        });
+    }
+}
+
+fn is_covered(covered: &[u64], item_idx: usize) -> bool {
+    return covered[item_idx / 64] & (1 << (item_idx % 64)) != 0;
+}
+
+impl Zdd {
+    /**
+     * The number of nodes in the diagram, including the two terminal nodes.
+     */
+    pub fn num_nodes(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    /**
+     * The number of solutions represented by the diagram. The counts are computed while building the diagram.
+     */
+    pub fn count_solutions(&self) -> u128 {
+        return self.counts[self.root];
+    }
+
+    /**
+     * Pick a solution uniformly at random, or None if there are no solutions. The same seed gives the same solution.
+     *
+     * At every node the HI edge is taken with probability proportional to the number of solutions below it, so every
+     * path to the TOP terminal, and therefore every solution, is equally likely.
+     */
+    pub fn sample_solution(&self, seed: u64) -> Option<ExactCoverSolution> {
+        if self.root == BOTTOM {
+            return None;
+        }
+
+        let mut rng = SplitMix64(seed);
+        let mut selected_options = self.required_options.clone();
+        let mut node_idx = self.root;
+        while node_idx != TOP {
+            let node = self.nodes[node_idx];
+            // 128 random bits make the bias of taking the remainder negligible for any realistic number of solutions
+            let random = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+            if random % self.counts[node_idx] < self.counts[node.hi] {
+                selected_options.push(self.option_names[node.option].clone());
+                node_idx = node.hi;
+            } else {
+                node_idx = node.lo;
+            }
+        }
+        return Some(ExactCoverSolution { selected_options });
+    }
+
+    /**
+     * Iterate over all solutions represented by the diagram.
+     */
+    pub fn solutions(&self) -> ZddSolutions<'_> {
+        let stack = if self.root == BOTTOM { vec![] } else { vec![(self.root, 0)] };
+        ZddSolutions {
+            zdd: self,
+            stack,
+            selected_options: Vec::new(),
+        }
+    }
+}
+
+/**
+ * Iterator over the solutions of a ZDD, which walks all paths to the TOP terminal depth-first.
+ */
+pub struct ZddSolutions<'a> {
+    zdd: &'a Zdd,
+    /// The nodes on the current path, with the number of edges of each that have been followed
+    stack: Vec<(usize, u8)>,
+    /// The options selected on the current path
+    selected_options: Vec<usize>,
+}
+
+impl Iterator for ZddSolutions<'_> {
+    type Item = ExactCoverSolution;
+
+    fn next(&mut self) -> Option<ExactCoverSolution> {
+        while let Some((node_idx, followed_edges)) = self.stack.pop() {
+            if node_idx == TOP {
+                let mut selected_options = self.zdd.required_options.clone();
+                selected_options.extend(self.selected_options.iter().map(|option| self.zdd.option_names[*option].clone()));
+                return Some(ExactCoverSolution { selected_options });
+            }
+
+            let node = self.zdd.nodes[node_idx];
+            match followed_edges {
+                0 => {
+                    // Follow the HI edge first, selecting the option
+                    self.stack.push((node_idx, 1));
+                    self.selected_options.push(node.option);
+                    self.stack.push((node.hi, 0));
+                }
+                1 => {
+                    // Then follow the LO edge, unselecting the option again
+                    self.selected_options.pop();
+                    self.stack.push((node_idx, 2));
+                    if node.lo != BOTTOM {
+                        self.stack.push((node.lo, 0));
+                    }
+                }
+                _ => {}
+            }
+        }
+        return None;
+    }
+}
@@ -0,0 +1,32 @@
+//! Place n queens on an n by n chess board so that no two queens attack each other.
+//!
+//! Usage: cargo run --example nqueens -- <n> [--count]
+//!
+//! With --count, the number of solutions is printed instead of the first one found, both in total and up to rotating
+//! and mirroring the board.
+
+use rust_sudoku::nqueens::{count_nqueens_solutions_up_to_symmetry, solve_nqueens_problem_with_exact_cover, NQueensProblem};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let count = args.iter().any(|arg| arg == "--count");
+    let n: u16 = match args.iter().find(|arg| *arg != "--count").and_then(|arg| arg.parse::<u16>().ok()) {
+        Some(n) if n > 0 => n,
+        _ => {
+            eprintln!("Usage: nqueens <n> [--count]");
+            std::process::exit(1);
+        }
+    };
+
+    let problem = NQueensProblem::new(n);
+    if count {
+        let counts = count_nqueens_solutions_up_to_symmetry(&problem);
+        println!("Solutions for {} queens: {} ({} up to symmetry)", n, counts.num_solutions, counts.num_classes);
+        return;
+    }
+
+    match solve_nqueens_problem_with_exact_cover(&problem) {
+        Some(solution) => print!("{}", solution.board()),
+        None => println!("There is no solution for {} queens", n),
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+/// Mask of the 81 bits that stand for the cells of a board
+const ALL_CELLS: u128 = (1 << 81) - 1;
+
+/**
+ * A set of cells of a board, e.g. a row, a cage or the cells that are still empty. Cell (row, col) is bit
+ * row * 9 + col of the mask, so set operations are single bit operations and iteration is in reading order.
+ */
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct CellSet(u128);
+
+impl CellSet {
+    /**
+     * The set without cells.
+     */
+    pub fn empty() -> CellSet {
+        return CellSet(0);
+    }
+
+    /**
+     * The set of all 81 cells.
+     */
+    pub fn full() -> CellSet {
+        return CellSet(ALL_CELLS);
+    }
+
+    /**
+     * The cells of row i (0-8).
+     */
+    pub fn row(i: usize) -> CellSet {
+        return CellSet(0x1FF << (i * 9));
+    }
+
+    /**
+     * The cells of column j (0-8).
+     */
+    pub fn column(j: usize) -> CellSet {
+        return (0..9).map(|i| (i, j)).collect();
+    }
+
+    /**
+     * The cells of block b (0-8), numbered from left to right and top to bottom.
+     */
+    pub fn block(b: usize) -> CellSet {
+        return (0..9).map(|k| (b / 3 * 3 + k / 3, b % 3 * 3 + k % 3)).collect();
+    }
+
+    /**
+     * Whether the set contains the cell at (row, col).
+     */
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        return self.0 & bit(row, col) != 0;
+    }
+
+    /**
+     * Add the cell at (row, col) to the set.
+     */
+    pub fn insert(&mut self, row: usize, col: usize) {
+        self.0 |= bit(row, col);
+    }
+
+    /**
+     * Remove the cell at (row, col) from the set.
+     */
+    pub fn remove(&mut self, row: usize, col: usize) {
+        self.0 &= !bit(row, col);
+    }
+
+    /**
+     * The number of cells in the set.
+     */
+    pub fn len(&self) -> usize {
+        return self.0.count_ones() as usize;
+    }
+
+    /**
+     * Whether the set has no cells.
+     */
+    pub fn is_empty(&self) -> bool {
+        return self.0 == 0;
+    }
+
+    /**
+     * Whether every cell of the set is also in the other set.
+     */
+    pub fn is_subset(&self, other: &CellSet) -> bool {
+        return self.0 & !other.0 == 0;
+    }
+
+    /**
+     * The cells of the set as (row, col), in reading order.
+     */
+    pub fn iter(&self) -> CellSetIter {
+        return CellSetIter(self.0);
+    }
+}
+
+/**
+ * The bit of the cell at (row, col).
+ */
+fn bit(row: usize, col: usize) -> u128 {
+    assert!(row < 9 && col < 9, "cell ({}, {}) is outside the board", row, col);
+    return 1 << (row * 9 + col);
+}
+
+/**
+ * Iterator over the cells of a CellSet, which removes the lowest bit in every step.
+ */
+pub struct CellSetIter(u128);
+
+impl Iterator for CellSetIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.0 == 0 {
+            return None;
+        }
+        let cell_idx = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        return Some((cell_idx / 9, cell_idx % 9));
+    }
+}
+
+impl IntoIterator for CellSet {
+    type Item = (usize, usize);
+    type IntoIter = CellSetIter;
+
+    fn into_iter(self) -> CellSetIter {
+        return self.iter();
+    }
+}
+
+impl FromIterator<(usize, usize)> for CellSet {
+    fn from_iter<I: IntoIterator<Item=(usize, usize)>>(cells: I) -> CellSet {
+        let mut cell_set = CellSet::empty();
+        for (row, col) in cells {
+            cell_set.insert(row, col);
+        }
+        return cell_set;
+    }
+}
+
+impl BitOr for CellSet {
+    type Output = CellSet;
+
+    /**
+     * The union of the sets.
+     */
+    fn bitor(self, other: CellSet) -> CellSet {
+        return CellSet(self.0 | other.0);
+    }
+}
+
+impl BitAnd for CellSet {
+    type Output = CellSet;
+
+    /**
+     * The intersection of the sets.
+     */
+    fn bitand(self, other: CellSet) -> CellSet {
+        return CellSet(self.0 & other.0);
+    }
+}
+
+impl Sub for CellSet {
+    type Output = CellSet;
+
+    /**
+     * The cells of the first set that are not in the second one.
+     */
+    fn sub(self, other: CellSet) -> CellSet {
+        return CellSet(self.0 & !other.0);
+    }
+}
+
+impl Not for CellSet {
+    type Output = CellSet;
+
+    /**
+     * The cells of the board that are not in the set.
+     */
+    fn not(self) -> CellSet {
+        return CellSet(!self.0 & ALL_CELLS);
+    }
+}
+
+impl Debug for CellSet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        return f.debug_set().entries(self.iter()).finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(CellSet::row(4), (4, 0), (5, 0))]
+    #[case(CellSet::column(4), (0, 4), (0, 5))]
+    #[case(CellSet::block(4), (3, 3), (2, 3))]
+    fn test_units(#[case] unit: CellSet, #[case] inside: (usize, usize), #[case] outside: (usize, usize)) {
+        assert_eq!(unit.len(), 9);
+        assert!(unit.contains(inside.0, inside.1));
+        assert!(!unit.contains(outside.0, outside.1));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let row = CellSet::row(0);
+        let block = CellSet::block(0);
+
+        assert_eq!((row & block).len(), 3);
+        assert_eq!((row | block).len(), 15);
+        assert_eq!((row - block).iter().collect::<Vec<_>>(), (3..9).map(|col| (0, col)).collect::<Vec<_>>());
+        assert_eq!((!row).len(), 72);
+        assert!((row & block).is_subset(&row));
+        assert!(!row.is_subset(&block));
+        assert_eq!(!CellSet::full(), CellSet::empty());
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut cell_set = CellSet::empty();
+
+        cell_set.insert(8, 8);
+        cell_set.insert(0, 1);
+        cell_set.insert(8, 8);
+        cell_set.remove(0, 1);
+        cell_set.insert(3, 2);
+
+        assert_eq!(cell_set.iter().collect::<Vec<_>>(), vec![(3, 2), (8, 8)]);
+        assert_eq!(format!("{:?}", cell_set), "{(3, 2), (8, 8)}");
+    }
+}
@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+
+use rust_sudoku::messages::{Locale, Localize};
+use rust_sudoku::sudoku::{CountEngine, DigitSymbols};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The config file which is loaded from the working directory if no other one is given with --config
+pub const DEFAULT_CONFIG_FILE: &str = "sudoku.toml";
+
+/**
+ * Defaults for the options of the command line interface, loaded from a TOML config file at startup. Options given on
+ * the command line take precedence over the config file, which takes precedence over the built-in defaults.
+ */
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    /// The language of the output, if the config file chooses one
+    pub locale: Option<Locale>,
+    /// The board file to read when none is given
+    pub board: String,
+    /// The symbols of the digits 1 to 9 for the solve and export commands
+    pub symbols: DigitSymbols,
+    /// The memory limit of the solve command in bytes, if any
+    pub max_memory: Option<usize>,
+    /// The engine of the count command
+    pub engine: CountEngine,
+    /// The directory of the solution cache used by count --cache, instead of the user's cache directory
+    pub cache_dir: Option<PathBuf>,
+}
+
+/**
+ * The config file as written, before its values are checked.
+ */
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct ConfigFile {
+    lang: Option<String>,
+    board: Option<String>,
+    symbols: Option<String>,
+    max_memory: Option<usize>,
+    engine: Option<String>,
+    cache_dir: Option<PathBuf>,
+}
+
+// ConfigError is a custom error type for config files that can't be loaded.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("could not read the config file {0}")]
+    Read(String, #[source] std::io::Error),
+    #[error("the config file {0} is not valid TOML")]
+    Parse(String, #[source] toml::de::Error),
+    #[error("invalid {key} in the config file: {value}")]
+    InvalidValue { key: &'static str, value: String },
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Config {
+            locale: None,
+            board: "data/sudoku.txt".to_string(),
+            symbols: DigitSymbols::DIGITS,
+            max_memory: None,
+            engine: CountEngine::Backtracking,
+            cache_dir: None,
+        };
+    }
+}
+
+impl Config {
+    /**
+     * Load the given config file, or sudoku.toml in the working directory if none is given. Without a config file,
+     * the built-in defaults are used.
+     */
+    pub fn load(filepath: Option<&str>) -> Result<Config, ConfigError> {
+        let filepath = match filepath {
+            Some(filepath) => filepath,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => DEFAULT_CONFIG_FILE,
+            None => return Ok(Config::default()),
+        };
+        let contents = std::fs::read_to_string(filepath).map_err(|e| ConfigError::Read(filepath.to_string(), e))?;
+        return Config::from_toml(&contents, filepath);
+    }
+
+    /**
+     * Parse the contents of a config file, using the path only in error messages.
+     */
+    fn from_toml(contents: &str, filepath: &str) -> Result<Config, ConfigError> {
+        let file: ConfigFile = toml::from_str(contents).map_err(|e| ConfigError::Parse(filepath.to_string(), e))?;
+        let defaults = Config::default();
+
+        let invalid = |key: &'static str, value: &str| ConfigError::InvalidValue { key, value: value.to_string() };
+        let locale = match file.lang {
+            Some(tag) => Some(Locale::from_tag(&tag).ok_or_else(|| invalid("lang", &tag))?),
+            None => None,
+        };
+        let symbols = match file.symbols {
+            Some(symbols) => DigitSymbols::new(&symbols).map_err(|_| invalid("symbols", &symbols))?,
+            None => defaults.symbols,
+        };
+        let engine = match file.engine.as_deref() {
+            Some("backtracking") => CountEngine::Backtracking,
+            Some("zdd") => CountEngine::Zdd,
+            Some(engine) => return Err(invalid("engine", engine)),
+            None => defaults.engine,
+        };
+        let max_memory = match file.max_memory {
+            Some(megabytes) => Some(megabytes.checked_mul(1024 * 1024).ok_or_else(|| invalid("max-memory", &megabytes.to_string()))?),
+            None => None,
+        };
+        return Ok(Config {
+            locale,
+            board: file.board.unwrap_or(defaults.board),
+            symbols,
+            max_memory,
+            engine,
+            cache_dir: file.cache_dir,
+        });
+    }
+}
+
+impl Localize for ConfigError {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, ConfigError::Read(filepath, _)) =>
+                format!("het configuratiebestand {} kon niet gelezen worden", filepath),
+            (Locale::Dutch, ConfigError::Parse(filepath, _)) =>
+                format!("het configuratiebestand {} is geen geldige TOML", filepath),
+            (Locale::Dutch, ConfigError::InvalidValue { key, value }) =>
+                format!("ongeldige {} in het configuratiebestand: {}", key, value),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let contents = "lang = \"nl\"\nboard = \"data/sudoku_hard.txt\"\nsymbols = \"ABCDEFGHI\"\nmax-memory = 2\n\
+                        engine = \"zdd\"\ncache-dir = \"/tmp/sudoku\"\n";
+
+        let config = Config::from_toml(contents, "sudoku.toml").unwrap();
+
+        assert_eq!(config, Config {
+            locale: Some(Locale::Dutch),
+            board: "data/sudoku_hard.txt".to_string(),
+            symbols: DigitSymbols::LETTERS,
+            max_memory: Some(2 * 1024 * 1024),
+            engine: CountEngine::Zdd,
+            cache_dir: Some(PathBuf::from("/tmp/sudoku")),
+        });
+    }
+
+    #[test]
+    fn test_from_empty_toml() {
+        let config = Config::from_toml("", "sudoku.toml").unwrap();
+
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_from_toml_with_unknown_key() {
+        let result = Config::from_toml("colors = true\n", "sudoku.toml");
+
+        assert!(matches!(result, Err(ConfigError::Parse(..))));
+    }
+
+    #[test]
+    fn test_from_toml_with_invalid_engine() {
+        let result = Config::from_toml("engine = \"dlx\"\n", "sudoku.toml");
+
+        assert!(matches!(result, Err(ConfigError::InvalidValue { key: "engine", .. })));
+    }
+
+    #[test]
+    fn test_from_toml_with_too_much_memory() {
+        let result = Config::from_toml("max-memory = 20000000000000\n", "sudoku.toml");
+
+        assert!(matches!(result, Err(ConfigError::InvalidValue { key: "max-memory", .. })));
+    }
+}
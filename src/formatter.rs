@@ -0,0 +1,157 @@
+use crate::sudoku::{Board, DigitSymbols};
+
+/**
+ * Formats boards as text with configurable conventions, for applications whose output must look a certain way. The
+ * default formatter writes boards like Display: 9 lines of 9 cells with '.' for empty cells, a space between the blocks
+ * of a row and an empty line between the bands of blocks. Options are set with the with_ methods, e.g.
+ * BoardFormatter::new().with_blank('0').with_separators(false).
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoardFormatter {
+    /// The character for empty cells
+    blank: char,
+    /// Whether blocks are separated by spaces and bands by empty lines, or rows by spaces on a single line
+    separators: bool,
+    /// Whether all 81 cells are written on a single line instead of a line per row
+    single_line: bool,
+    /// Whether rows and columns are labelled with their numbers, counting from 1
+    labels: bool,
+    /// The symbols of the digits
+    symbols: DigitSymbols,
+}
+
+impl BoardFormatter {
+    /**
+     * Create a formatter that writes boards like Display.
+     */
+    pub fn new() -> BoardFormatter {
+        return BoardFormatter { blank: '.', separators: true, single_line: false, labels: false, symbols: DigitSymbols::DIGITS };
+    }
+
+    /**
+     * Write empty cells as the given character instead of '.', e.g. '0' or ' '.
+     */
+    pub fn with_blank(mut self, blank: char) -> BoardFormatter {
+        self.blank = blank;
+        return self;
+    }
+
+    /**
+     * Whether to separate the blocks of a row by spaces and the bands of blocks by empty lines. On a single line, the
+     * rows are separated by spaces instead.
+     */
+    pub fn with_separators(mut self, separators: bool) -> BoardFormatter {
+        self.separators = separators;
+        return self;
+    }
+
+    /**
+     * Whether to write all 81 cells in reading order on a single line, as collections of puzzles do.
+     */
+    pub fn with_single_line(mut self, single_line: bool) -> BoardFormatter {
+        self.single_line = single_line;
+        return self;
+    }
+
+    /**
+     * Whether to start every row with its number and put a line with the numbers of the columns above the board. A
+     * single line has no labels.
+     */
+    pub fn with_labels(mut self, labels: bool) -> BoardFormatter {
+        self.labels = labels;
+        return self;
+    }
+
+    /**
+     * Write the digits with the given symbols, e.g. letters for Wordoku.
+     */
+    pub fn with_symbols(mut self, symbols: DigitSymbols) -> BoardFormatter {
+        self.symbols = symbols;
+        return self;
+    }
+
+    /**
+     * Format the board. Unless it is written on a single line, every line ends with a newline.
+     */
+    pub fn format(&self, board: &Board) -> String {
+        let rows: Vec<String> = (0..9).map(|i| self.format_row(board, i)).collect();
+        if self.single_line {
+            return rows.join(if self.separators { " " } else { "" });
+        }
+
+        let mut out = String::new();
+        if self.labels {
+            let numbers: Vec<String> = (1..=9).map(|col| col.to_string()).collect();
+            out.push_str(&format!("  {}\n", self.join_blocks(&numbers)));
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if self.labels {
+                out.push_str(&format!("{} ", i + 1));
+            }
+            out.push_str(row);
+            out.push('\n');
+            if self.separators && (i == 2 || i == 5) {
+                out.push('\n');
+            }
+        }
+        return out;
+    }
+
+    /**
+     * The cells of row i, with a space between the blocks if there are separators and the board isn't on one line.
+     */
+    fn format_row(&self, board: &Board, i: usize) -> String {
+        let cells: Vec<String> = (0..9)
+            .map(|j| match board.get(i, j) {
+                Some(digit) => self.symbols.symbol(digit.get()),
+                None => self.blank,
+            })
+            .map(String::from)
+            .collect();
+        if self.single_line {
+            return cells.concat();
+        }
+        return self.join_blocks(&cells);
+    }
+
+    /**
+     * Join the 9 parts of a row, with a space between every 3 if there are separators.
+     */
+    fn join_blocks(&self, parts: &[String]) -> String {
+        if !self.separators {
+            return parts.concat();
+        }
+        return parts.chunks(3).map(|block| block.concat()).collect::<Vec<String>>().join(" ");
+    }
+}
+
+impl Default for BoardFormatter {
+    fn default() -> Self {
+        return BoardFormatter::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn get_board() -> Board {
+        return Board::read_from_file("data/sudoku.txt").unwrap();
+    }
+
+    #[rstest]
+    #[case(BoardFormatter::new(), "53. .7. ...\n6.. 195 ...\n.98 ... .6.\n\n8.. .6. ..3\n")]
+    #[case(BoardFormatter::new().with_single_line(true).with_separators(false), "53..7....6..195....98....6.8...6...3")]
+    #[case(BoardFormatter::new().with_single_line(true), "53..7.... 6..195... .98....6. 8...6...3")]
+    #[case(BoardFormatter::new().with_blank('0').with_separators(false), "530070000\n600195000\n098000060\n800060003\n")]
+    #[case(BoardFormatter::new().with_symbols(DigitSymbols::LETTERS), "EC. .G. ...\nF.. AIE ...\n.IH ... .F.\n\nH.. .F. ..C\n")]
+    #[case(BoardFormatter::new().with_labels(true), "  123 456 789\n1 53. .7. ...\n2 6.. 195 ...\n3 .98 ... .6.\n\n4 8.. .6. ..3\n")]
+    #[case(BoardFormatter::new().with_labels(true).with_separators(false).with_blank(' '), "  123456789\n1 53  7    \n2 6  195   \n")]
+    fn test_format(#[case] formatter: BoardFormatter, #[case] expected_start: &str) {
+        let formatted = formatter.format(&get_board());
+
+        assert!(formatted.starts_with(expected_start), "{}", formatted);
+    }
+}
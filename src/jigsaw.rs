@@ -0,0 +1,296 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
+
+use crate::cell_set::CellSet;
+use crate::sudoku::{convert_to_exact_cover_problem_with_regions, convert_to_sudoku_solution, Board, BoardReadError, CandidateGrid, SolveError};
+
+// JigsawError is a custom error type for errors that occur when reading the regions of a Jigsaw Sudoku.
+#[derive(Debug, Error)]
+pub enum JigsawError {
+    #[error("could not read the puzzle file")]
+    FileReadError(#[from] std::io::Error),
+    #[error("invalid givens: {0}")]
+    InvalidGivens(#[from] BoardReadError),
+    #[error("the regions must be 9 rows of 9 labels")]
+    InvalidSize,
+    #[error("there are {0} region labels, there must be 9")]
+    InvalidRegionCount(usize),
+    #[error("region '{label}' has {size} cells, it must have 9")]
+    InvalidRegionSize { label: char, size: usize },
+    #[error("the cells of region '{0}' are not connected")]
+    DisconnectedRegion(char),
+}
+
+/**
+ * The nine regions of a Jigsaw Sudoku, which take the place of the blocks: every region has 9 cells that are connected
+ * through their sides, and every cell is in exactly one region. Regions are numbered 0 to 8 in the order in which
+ * their labels first appear, reading row by row.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Regions {
+    /// The region of every cell, by row and column
+    cells: [[u8; 9]; 9],
+    /// The label of every region, by region number
+    labels: Vec<char>,
+}
+
+impl Regions {
+    /**
+     * The blocks of ordinary Sudoku as regions, labelled 'A' to 'I'.
+     */
+    pub fn standard() -> Regions {
+        let mut cells = [[0; 9]; 9];
+        for (i, row) in cells.iter_mut().enumerate() {
+            for (j, region) in row.iter_mut().enumerate() {
+                *region = (i / 3 * 3 + j / 3) as u8;
+            }
+        }
+        return Regions { cells, labels: "ABCDEFGHI".chars().collect() };
+    }
+
+    /**
+     * Read the regions from 9 lines of 9 labels, one label per cell. A label can be any character other than a space,
+     * and cells with the same label are in the same region. Spaces and empty lines are ignored.
+     */
+    pub fn read_from_str(s: &str) -> Result<Regions, JigsawError> {
+        let rows: Vec<Vec<char>> = s.lines()
+            .map(|line| line.chars().filter(|char| !char.is_whitespace()).collect::<Vec<char>>())
+            .filter(|row| !row.is_empty())
+            .collect();
+        if rows.len() != 9 || rows.iter().any(|row| row.len() != 9) {
+            return Err(JigsawError::InvalidSize);
+        }
+
+        let mut labels: Vec<char> = Vec::new();
+        let mut cells = [[0; 9]; 9];
+        for (i, row) in rows.iter().enumerate() {
+            for (j, label) in row.iter().enumerate() {
+                let region = match labels.iter().position(|other| other == label) {
+                    Some(region) => region,
+                    None => {
+                        labels.push(*label);
+                        labels.len() - 1
+                    }
+                };
+                cells[i][j] = region as u8;
+            }
+        }
+        if labels.len() != 9 {
+            return Err(JigsawError::InvalidRegionCount(labels.len()));
+        }
+
+        let regions = Regions { cells, labels };
+        for region in 0..9 {
+            let label = regions.labels[region as usize];
+            let size = regions.cells(region).len();
+            if size != 9 {
+                return Err(JigsawError::InvalidRegionSize { label, size });
+            }
+            if !regions.is_connected(region) {
+                return Err(JigsawError::DisconnectedRegion(label));
+            }
+        }
+        return Ok(regions);
+    }
+
+    /**
+     * The number of the region that the cell is in, between 0 and 8.
+     */
+    pub fn region_of(&self, row: u8, col: u8) -> u8 {
+        return self.cells[row as usize][col as usize];
+    }
+
+    /**
+     * The cells of a region.
+     */
+    pub fn cells(&self, region: u8) -> CellSet {
+        return (0..9).flat_map(|i| (0..9).map(move |j| (i, j)))
+            .filter(|(i, j)| self.cells[*i][*j] == region)
+            .collect();
+    }
+
+    /**
+     * Whether every cell of the region can be reached from every other by stepping to neighbouring cells of the region.
+     */
+    fn is_connected(&self, region: u8) -> bool {
+        let cells = self.cells(region);
+        let Some((start_row, start_col)) = cells.iter().next() else {
+            return true;
+        };
+        let mut reached = CellSet::empty();
+        reached.insert(start_row, start_col);
+        let mut to_visit = vec![(start_row, start_col)];
+        while let Some((row, col)) = to_visit.pop() {
+            for (other_row, other_col) in cells.iter() {
+                let neighbours = row.abs_diff(other_row) + col.abs_diff(other_col) == 1;
+                if neighbours && !reached.contains(other_row, other_col) {
+                    reached.insert(other_row, other_col);
+                    to_visit.push((other_row, other_col));
+                }
+            }
+        }
+        return reached == cells;
+    }
+}
+
+impl Display for Regions {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for row in self.cells.iter() {
+            writeln!(f, "{}", row.iter().map(|region| self.labels[*region as usize]).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * A Jigsaw Sudoku: a Sudoku in which the blocks are replaced by irregular regions of 9 connected cells.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct JigsawSudoku {
+    /// The givens
+    pub givens: Board,
+    /// The regions that every digit must appear in once
+    pub regions: Regions,
+}
+
+impl JigsawSudoku {
+    /**
+     * Read a Jigsaw Sudoku from a file, see read_from_str.
+     */
+    pub fn read_from_file(filepath: &str) -> Result<JigsawSudoku, JigsawError> {
+        let contents = std::fs::read_to_string(filepath)?;
+        return JigsawSudoku::read_from_str(&contents);
+    }
+
+    /**
+     * Read a Jigsaw Sudoku from the givens in the format of Board::read_from_str, followed by its regions in the
+     * format of Regions::read_from_str: the last 9 lines that are not empty are the regions, everything before them
+     * the givens.
+     */
+    pub fn read_from_str(s: &str) -> Result<JigsawSudoku, JigsawError> {
+        let lines: Vec<&str> = s.lines().filter(|line| !line.trim().is_empty()).collect();
+        if lines.len() < 10 {
+            return Err(JigsawError::InvalidSize);
+        }
+        let (givens, regions) = lines.split_at(lines.len() - 9);
+        return Ok(JigsawSudoku {
+            givens: Board::read_from_str(&givens.join("\n"))?,
+            regions: Regions::read_from_str(&regions.join("\n"))?,
+        });
+    }
+
+    /**
+     * Whether the board is a valid solution that keeps the givens: every row, column and region has every digit once.
+     */
+    pub fn is_valid_solution(&self, board: &Board) -> bool {
+        if !board.is_filled() {
+            return false;
+        }
+        if self.givens.iter_cells().any(|(i, j, digit)| digit.is_some() && board.get(i, j) != digit) {
+            return false;
+        }
+        let mut rows = [0u16; 9];
+        let mut cols = [0u16; 9];
+        let mut regions = [0u16; 9];
+        for (i, j, digit) in board.iter_cells() {
+            let bit = 1 << digit.unwrap().get();
+            rows[i] |= bit;
+            cols[j] |= bit;
+            regions[self.regions.region_of(i as u8, j as u8) as usize] |= bit;
+        }
+        return rows.iter().chain(cols.iter()).chain(regions.iter()).all(|digits| digits.count_ones() == 9);
+    }
+}
+
+/**
+ * Solve a Jigsaw Sudoku with exact cover. Returns None if it has no solution, including when its givens conflict.
+ */
+pub fn solve_jigsaw_sudoku(puzzle: &JigsawSudoku) -> Result<Option<Board>, SolveError> {
+    let region_of = |row: u8, col: u8| puzzle.regions.region_of(row, col);
+    let exact_cover_problem = match convert_to_exact_cover_problem_with_regions(&puzzle.givens, &CandidateGrid::full(), region_of) {
+        Err(SolveError::ConflictingGivens { .. }) => return Ok(None),
+        result => result?,
+    };
+
+    let solution = exact_cover_problem.solve();
+
+    Ok(solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_read_from_file() {
+        let puzzle = JigsawSudoku::read_from_file("data/jigsaw.txt").unwrap();
+
+        assert_eq!(puzzle.regions.region_of(0, 0), 0);
+        assert_eq!(puzzle.regions.region_of(0, 3), 1);
+        assert_eq!(puzzle.regions.cells(1).iter().collect::<Vec<_>>(), vec![(0, 3), (0, 4), (0, 5), (1, 4), (1, 5), (2, 2), (2, 3), (2, 4), (2, 5)]);
+        assert_eq!(puzzle.regions.to_string(), std::fs::read_to_string("data/jigsaw.txt").unwrap().split("\n\n").nth(1).unwrap());
+    }
+
+    #[test]
+    fn test_standard_regions() {
+        let regions = Regions::standard();
+
+        assert_eq!(Regions::read_from_str(&regions.to_string()).unwrap(), regions);
+        assert_eq!(regions.region_of(4, 7), 5);
+        assert_eq!(regions.cells(8), CellSet::block(8));
+    }
+
+    #[rstest]
+    #[case("AAAAAAAAA\n".repeat(8), "the regions must be 9 rows of 9 labels")]
+    #[case("ABCDEFGHI\n".repeat(8) + "ABCDEFGHJ", "there are 10 region labels, there must be 9")]
+    #[case("AAAAAAAAA\n".repeat(9), "there are 1 region labels, there must be 9")]
+    #[case("ABCDEFGHI\n".repeat(8) + "ABCDEFGHH", "region 'H' has 10 cells, it must have 9")]
+    #[case(Regions::standard().to_string().replacen("AAABBB", "ABABAB", 1).replacen("AAABBB", "BABABA", 1), "the cells of region 'A' are not connected")]
+    fn test_read_invalid_regions(#[case] s: String, #[case] expected_error: &str) {
+        assert_eq!(Regions::read_from_str(&s).unwrap_err().to_string(), expected_error);
+    }
+
+    #[test]
+    fn test_read_invalid_givens() {
+        let s = format!("531...4.9\n\n{}", Regions::standard());
+
+        let result = JigsawSudoku::read_from_str(&s);
+
+        assert!(matches!(result, Err(JigsawError::InvalidGivens(BoardReadError::InvalidSize))));
+    }
+
+    #[test]
+    fn test_solve_jigsaw_sudoku() {
+        let puzzle = JigsawSudoku::read_from_file("data/jigsaw.txt").unwrap();
+
+        let solution = solve_jigsaw_sudoku(&puzzle).unwrap().unwrap();
+
+        assert!(puzzle.is_valid_solution(&solution));
+    }
+
+    #[test]
+    fn test_solve_jigsaw_sudoku_with_standard_regions() {
+        let givens = Board::read_from_file("data/sudoku_easy.txt").unwrap();
+        let puzzle = JigsawSudoku { givens: givens.clone(), regions: Regions::standard() };
+
+        let solution = solve_jigsaw_sudoku(&puzzle).unwrap().unwrap();
+
+        assert_eq!(Some(solution), crate::sudoku::solve_sudoku_with_exact_cover(&givens).unwrap());
+    }
+
+    #[test]
+    fn test_solve_jigsaw_sudoku_with_conflicting_givens() {
+        let mut puzzle = JigsawSudoku::read_from_file("data/jigsaw.txt").unwrap();
+        let (row, col) = puzzle.regions.cells(4).iter().next().unwrap();
+        let (other_row, other_col) = puzzle.regions.cells(4).iter().last().unwrap();
+        puzzle.givens = Board::empty();
+        puzzle.givens.set(row, col, 7).unwrap();
+        puzzle.givens.set(other_row, other_col, 7).unwrap();
+
+        assert_eq!(solve_jigsaw_sudoku(&puzzle).unwrap(), None);
+    }
+}
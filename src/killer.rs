@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+
+use exact_cover::{ExactCoverProblem, InvalidProblemError};
+use thiserror::Error;
+
+use crate::cell_set::CellSet;
+use crate::sudoku::{Board, SolveError};
+
+// KillerError is a custom error type for errors that occur when reading or creating a Killer Sudoku.
+#[derive(Debug, Error)]
+pub enum KillerError {
+    #[error("could not read the cage file")]
+    FileReadError(#[from] std::io::Error),
+    #[error("line {line} is not a sum followed by cells: '{text}'")]
+    InvalidLine { line: usize, text: String },
+    #[error("invalid cell '{0}', it must be r<row>c<column> with a row and column between 1 and 9")]
+    InvalidCell(String),
+    #[error("the cell in row {}, column {} is in more than one cage", .row + 1, .col + 1)]
+    SharedCell { row: usize, col: usize },
+    #[error("a cage can't have {0} cells, it must have between 1 and 9")]
+    InvalidCageSize(usize),
+}
+
+/**
+ * A group of cells whose digits add up to the sum and are all different.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cage {
+    /// The sum of the digits in the cage
+    pub sum: u8,
+    /// The cells of the cage
+    pub cells: CellSet,
+}
+
+/**
+ * A Killer Sudoku: a Sudoku with cages on top of the rules, and usually without givens. Cells that are in no cage are
+ * only constrained by the rules of Sudoku.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct KillerSudoku {
+    /// The givens, if any
+    pub givens: Board,
+    /// The cages, of which no two share a cell
+    pub cages: Vec<Cage>,
+}
+
+impl KillerSudoku {
+    /**
+     * Create a Killer Sudoku, checking that every cage has 1 to 9 cells and that no cell is in two cages.
+     */
+    pub fn new(givens: Board, cages: Vec<Cage>) -> Result<KillerSudoku, KillerError> {
+        let mut caged = CellSet::empty();
+        for cage in cages.iter() {
+            if cage.cells.is_empty() || cage.cells.len() > 9 {
+                return Err(KillerError::InvalidCageSize(cage.cells.len()));
+            }
+            if let Some((row, col)) = (caged & cage.cells).iter().next() {
+                return Err(KillerError::SharedCell { row, col });
+            }
+            caged = caged | cage.cells;
+        }
+        return Ok(KillerSudoku { givens, cages });
+    }
+
+    /**
+     * Read the cages of a Killer Sudoku without givens from a file, see read_from_str.
+     */
+    pub fn read_from_file(filepath: &str) -> Result<KillerSudoku, KillerError> {
+        let contents = std::fs::read_to_string(filepath)?;
+        return KillerSudoku::read_from_str(&contents);
+    }
+
+    /**
+     * Read the cages of a Killer Sudoku without givens, one cage per line: its sum followed by its cells as
+     * r<row>c<column>, counting from 1 and separated by spaces, e.g. "10 r1c1 r1c2 r2c1". Empty lines and lines that
+     * start with '#' are ignored.
+     */
+    pub fn read_from_str(s: &str) -> Result<KillerSudoku, KillerError> {
+        let mut cages: Vec<Cage> = Vec::new();
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let Some(sum) = words.next().and_then(|word| word.parse::<u8>().ok()) else {
+                return Err(KillerError::InvalidLine { line: idx + 1, text: line.to_string() });
+            };
+            let mut cells = CellSet::empty();
+            for word in words {
+                let (row, col) = parse_cell(word)?;
+                // A cell listed twice would otherwise be silently counted once
+                if cells.contains(row, col) {
+                    return Err(KillerError::SharedCell { row, col });
+                }
+                cells.insert(row, col);
+            }
+            cages.push(Cage { sum, cells });
+        }
+        return KillerSudoku::new(Board::empty(), cages);
+    }
+
+    /**
+     * Whether the board is a valid Sudoku solution that keeps the givens and in which the digits of every cage are
+     * different and add up to its sum.
+     */
+    pub fn is_valid_solution(&self, board: &Board) -> bool {
+        if !board.is_valid_solution() {
+            return false;
+        }
+        if self.givens.iter_cells().any(|(i, j, digit)| digit.is_some() && board.get(i, j) != digit) {
+            return false;
+        }
+        return self.cages.iter().all(|cage| {
+            let digits: Vec<u8> = cage.cells.iter().map(|(row, col)| board.get(row, col).unwrap().get()).collect();
+            let distinct = digits.iter().fold(0u16, |mask, digit| mask | (1 << digit)).count_ones() as usize == digits.len();
+            distinct && digits.iter().map(|digit| *digit as u32).sum::<u32>() == cage.sum as u32
+        });
+    }
+}
+
+/**
+ * Parse a cell written as r<row>c<column>, counting from 1, to a 0-based (row, col).
+ */
+fn parse_cell(word: &str) -> Result<(usize, usize), KillerError> {
+    let invalid = || KillerError::InvalidCell(word.to_string());
+    let lowercase = word.to_ascii_lowercase();
+    let (row, col) = lowercase.strip_prefix('r').and_then(|rest| rest.split_once('c')).ok_or_else(invalid)?;
+    let row = row.parse::<usize>().ok().filter(|row| (1..=9).contains(row)).ok_or_else(invalid)?;
+    let col = col.parse::<usize>().ok().filter(|col| (1..=9).contains(col)).ok_or_else(invalid)?;
+    return Ok((row - 1, col - 1));
+}
+
+/**
+ * Every way to fill the cells of a cage with different digits that add up to the sum, in which a cell with a given
+ * only gets that digit. Each way is the digits of the cells in reading order.
+ */
+fn cage_assignments(cage: &Cage, givens: &Board) -> Vec<Vec<u8>> {
+    fn extend(cells: &[(usize, usize)], givens: &Board, digits: &mut Vec<u8>, remaining: i32, assignments: &mut Vec<Vec<u8>>) {
+        if digits.len() == cells.len() {
+            if remaining == 0 {
+                assignments.push(digits.clone());
+            }
+            return;
+        }
+        let (row, col) = cells[digits.len()];
+        for digit in 1..=9u8 {
+            if digit as i32 > remaining || digits.contains(&digit) {
+                continue;
+            }
+            if givens.get(row, col).is_some_and(|given| given.get() != digit) {
+                continue;
+            }
+            digits.push(digit);
+            extend(cells, givens, digits, remaining - digit as i32, assignments);
+            digits.pop();
+        }
+    }
+
+    let cells: Vec<(usize, usize)> = cage.cells.iter().collect();
+    let mut assignments: Vec<Vec<u8>> = Vec::new();
+    extend(&cells, givens, &mut Vec::new(), cage.sum as i32, &mut assignments);
+    return assignments;
+}
+
+/**
+ * Convert a Killer Sudoku to an exact cover problem. On top of the items of Sudoku, every cage has an item that must
+ * be covered by one of its combinations: an option that fills all cells of the cage at once with different digits that
+ * add up to its sum. Cells outside cages get an option per digit as in Sudoku. Every option has the (row, col, digit)
+ * of the cells it fills as payload.
+ */
+fn convert_to_exact_cover_problem(puzzle: &KillerSudoku) -> Result<ExactCoverProblem, InvalidProblemError> {
+    let mut required_items: Vec<String> = Vec::new();
+    for i in 0..9 {
+        for j in 0..9 {
+            required_items.push(format!("r{}c{}", i, j));
+        }
+    }
+    for unit in ["r", "c", "b"] {
+        for idx in 0..9 {
+            for digit in 1..10 {
+                required_items.push(format!("{}{}d{}", unit, idx, digit));
+            }
+        }
+    }
+    required_items.extend((0..puzzle.cages.len()).map(|idx| format!("k{}", idx)));
+
+    let mut covered_by: HashMap<String, Vec<String>> = required_items.iter().map(|item| (item.clone(), Vec::new())).collect();
+    let mut required_options: Vec<String> = Vec::new();
+    let mut payloads: HashMap<String, Vec<(u8, u8, u8)>> = HashMap::new();
+    let mut add_option = |option_name: String, placements: Vec<(u8, u8, u8)>, covered_by: &mut HashMap<String, Vec<String>>| {
+        for (row, col, digit) in placements.iter() {
+            for item_name in [
+                format!("r{}c{}", row, col),
+                format!("r{}d{}", row, digit),
+                format!("c{}d{}", col, digit),
+                format!("b{}d{}", row / 3 * 3 + col / 3, digit),
+            ] {
+                covered_by.get_mut(&item_name).unwrap().push(option_name.clone());
+            }
+        }
+        payloads.insert(option_name, placements);
+    };
+
+    let mut caged = CellSet::empty();
+    for (idx, cage) in puzzle.cages.iter().enumerate() {
+        for assignment in cage_assignments(cage, &puzzle.givens) {
+            let option_name = format!("k{}:{}", idx, assignment.iter().map(|digit| digit.to_string()).collect::<String>());
+            covered_by.get_mut(&format!("k{}", idx)).unwrap().push(option_name.clone());
+            let placements = cage.cells.iter().zip(assignment).map(|((row, col), digit)| (row as u8, col as u8, digit)).collect();
+            add_option(option_name, placements, &mut covered_by);
+        }
+        caged = caged | cage.cells;
+    }
+    for i in 0..9u8 {
+        for j in 0..9u8 {
+            if caged.contains(i as usize, j as usize) {
+                continue;
+            }
+            for digit in 1..10 {
+                let option_name = format!("r{}c{}d{}", i, j, digit);
+                if puzzle.givens.get(i as usize, j as usize).is_some_and(|given| given.get() == digit) {
+                    required_options.push(option_name.clone());
+                }
+                add_option(option_name, vec![(i, j, digit)], &mut covered_by);
+            }
+        }
+    }
+
+    return ExactCoverProblem::new(required_items, vec![], required_options, covered_by)?.with_option_payloads(payloads);
+}
+
+/**
+ * Solve a Killer Sudoku with exact cover. Returns None if it has no solution, including when its givens conflict.
+ * Large cages have many combinations, so cages of more than 6 cells make the encoding slow.
+ */
+pub fn solve_killer_sudoku(puzzle: &KillerSudoku) -> Result<Option<Board>, SolveError> {
+    let exact_cover_problem = match convert_to_exact_cover_problem(puzzle) {
+        Err(InvalidProblemError::RequiredOptionConflict { .. }) => return Ok(None),
+        result => result?,
+    };
+
+    let Some(solution) = exact_cover_problem.solve() else {
+        return Ok(None);
+    };
+    let mut board = Board::empty();
+    for placements in exact_cover_problem.solution_payloads::<Vec<(u8, u8, u8)>>(&solution) {
+        for (row, col, digit) in placements.iter() {
+            board.set(*row as usize, *col as usize, *digit).unwrap();
+        }
+    }
+    return Ok(Some(board));
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn cage(sum: u8, cells: &[(usize, usize)]) -> Cage {
+        return Cage { sum, cells: cells.iter().copied().collect() };
+    }
+
+    #[test]
+    fn test_read_from_file() {
+        let puzzle = KillerSudoku::read_from_file("data/killer.txt").unwrap();
+
+        assert_eq!(puzzle.cages.len(), 27);
+        assert_eq!(puzzle.cages[0], cage(8, &[(0, 0), (0, 1)]));
+        assert_eq!(puzzle.givens, Board::empty());
+    }
+
+    #[rstest]
+    #[case("x r1c1", "line 1 is not a sum followed by cells: 'x r1c1'")]
+    #[case("\n3 r1c1 r0c2", "invalid cell 'r0c2', it must be r<row>c<column> with a row and column between 1 and 9")]
+    #[case("3 r1c1 c2", "invalid cell 'c2', it must be r<row>c<column> with a row and column between 1 and 9")]
+    #[case("3 r1c1 r1c2\n4 r1c2 r1c3", "the cell in row 1, column 2 is in more than one cage")]
+    #[case("3", "a cage can't have 0 cells, it must have between 1 and 9")]
+    #[case("3 r1c1 r1c1", "the cell in row 1, column 1 is in more than one cage")]
+    fn test_read_invalid(#[case] s: &str, #[case] expected_error: &str) {
+        assert_eq!(KillerSudoku::read_from_str(s).unwrap_err().to_string(), expected_error);
+    }
+
+    #[test]
+    fn test_cage_assignments() {
+        let mut givens = Board::empty();
+        givens.set(0, 1, 1).unwrap();
+
+        let assignments = cage_assignments(&cage(4, &[(0, 0), (0, 1)]), &Board::empty());
+        let with_given = cage_assignments(&cage(6, &[(0, 0), (0, 1), (0, 2)]), &givens);
+
+        assert_eq!(assignments, vec![vec![1, 3], vec![3, 1]]);
+        assert_eq!(with_given, vec![vec![2, 1, 3], vec![3, 1, 2]]);
+    }
+
+    #[test]
+    fn test_solve_killer_sudoku() {
+        let puzzle = KillerSudoku::read_from_file("data/killer.txt").unwrap();
+
+        let solution = solve_killer_sudoku(&puzzle).unwrap().unwrap();
+
+        assert!(puzzle.is_valid_solution(&solution));
+    }
+
+    #[test]
+    fn test_solve_killer_sudoku_with_givens() {
+        let mut givens = Board::empty();
+        givens.set(0, 0, 5).unwrap();
+        givens.set(8, 8, 9).unwrap();
+        let puzzle = KillerSudoku::new(givens, vec![cage(3, &[(0, 1), (0, 2)])]).unwrap();
+
+        let solution = solve_killer_sudoku(&puzzle).unwrap().unwrap();
+
+        assert!(puzzle.is_valid_solution(&solution));
+        assert_eq!(solution.get(0, 0).unwrap().get(), 5);
+    }
+
+    #[rstest]
+    #[case(vec![cage(2, &[(0, 0), (0, 1)])])]
+    #[case(vec![cage(3, &[(0, 0), (0, 1)]), cage(3, &[(0, 2), (0, 3)])])]
+    fn test_solve_killer_sudoku_without_solution(#[case] cages: Vec<Cage>) {
+        let puzzle = KillerSudoku::new(Board::empty(), cages).unwrap();
+
+        assert_eq!(solve_killer_sudoku(&puzzle).unwrap(), None);
+    }
+}
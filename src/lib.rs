@@ -0,0 +1,16 @@
+pub mod cell_set;
+pub mod formatter;
+pub mod jigsaw;
+pub mod killer;
+pub mod logic;
+pub mod messages;
+pub mod nqueens;
+pub mod puzzle;
+pub mod samurai;
+pub mod sized_board;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod sudoku;
+pub mod transformation;
+pub mod variants;
+pub mod wordoku;
@@ -1,5 +0,0 @@
-mod exact_cover;
-mod test_utils;
-pub(crate) mod basic_example;
-pub(crate) mod sudoku;
-pub(crate) mod nqueens;
@@ -0,0 +1,654 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::sudoku::{Board, CandidateGrid};
+
+/**
+ * A technique a person uses to solve Sudoku without guessing, from the simplest to the most involved, which is also how
+ * techniques are ordered.
+ */
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Technique {
+    /// A cell has only one candidate left
+    NakedSingle,
+    /// A digit has only one cell left in a row, column or block
+    HiddenSingle,
+    /// The candidates of a digit in a block all lie in one row or column, so the digit can't be elsewhere in that line
+    PointingPair,
+    /// The candidates of a digit in a row or column all lie in one block, so the digit can't be elsewhere in that block
+    BoxLineReduction,
+    /// Two cells of a unit have the same two candidates, so those digits can't be in the other cells of the unit
+    NakedPair,
+    /// Two digits of a unit have the same two cells left, so those cells can't have other digits
+    HiddenPair,
+    /// Three cells of a unit have three candidates between them, which can't be in the other cells of the unit
+    NakedTriple,
+    /// Three digits of a unit have three cells left between them, so those cells can't have other digits
+    HiddenTriple,
+    /// A digit has two cells left in each of two rows, in the same two columns (or the other way around), so it can't be
+    /// elsewhere in those columns
+    XWing,
+    /// Like an X-wing, but with three rows whose cells for the digit lie in the same three columns
+    Swordfish,
+    /// A cell with candidates xy sees a cell with xz and a cell with yz, so z can't be in any cell that sees both
+    XYWing,
+    /// The cells of a chain of units in which a digit has two cells left alternate between having the digit or not, so
+    /// a cell that sees both colors of the chain, or a color that sees itself, can't have the digit
+    SimpleColoring,
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Technique::NakedSingle => write!(f, "naked single"),
+            Technique::HiddenSingle => write!(f, "hidden single"),
+            Technique::PointingPair => write!(f, "pointing pair"),
+            Technique::BoxLineReduction => write!(f, "box-line reduction"),
+            Technique::NakedPair => write!(f, "naked pair"),
+            Technique::HiddenPair => write!(f, "hidden pair"),
+            Technique::NakedTriple => write!(f, "naked triple"),
+            Technique::HiddenTriple => write!(f, "hidden triple"),
+            Technique::XWing => write!(f, "X-wing"),
+            Technique::Swordfish => write!(f, "swordfish"),
+            Technique::XYWing => write!(f, "XY-wing"),
+            Technique::SimpleColoring => write!(f, "simple coloring"),
+        }
+    }
+}
+
+/**
+ * One deduction of the logical solver: either a digit that must be placed in a cell, or candidates that can be
+ * removed. Cells are 0-based (row, col) pairs like everywhere else.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Step {
+    /// The technique that made the deduction
+    pub technique: Technique,
+    /// The digit placed by a single as (row, col, digit)
+    pub placement: Option<(u8, u8, u8)>,
+    /// The candidates removed as (row, col, digit)
+    pub eliminations: Vec<(u8, u8, u8)>,
+    /// The cells the deduction is based on, e.g. the two cells of a naked pair
+    pub cells: Vec<(u8, u8)>,
+}
+
+impl Step {
+    /**
+     * Apply the deduction to a board and its candidates. Placing a digit also removes it from the candidates of the
+     * cells in the same row, column and block.
+     */
+    pub fn apply(&self, board: &mut Board, candidates: &mut CandidateGrid) {
+        if let Some((row, col, digit)) = self.placement {
+            board.set(row as usize, col as usize, digit).unwrap();
+            candidates.place(row as usize, col as usize, digit).unwrap();
+        }
+        for (row, col, digit) in self.eliminations.iter() {
+            candidates.remove(*row as usize, *col as usize, *digit).unwrap();
+        }
+    }
+}
+
+/**
+ * How far the logical solver got with a board.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicSolution {
+    /// The board with every digit the solver could place
+    pub board: Board,
+    /// The candidates that are left
+    pub candidates: CandidateGrid,
+    /// The deductions in the order they were made
+    pub steps: Vec<Step>,
+}
+
+impl LogicSolution {
+    /**
+     * Whether the solver placed a digit in every cell.
+     */
+    pub fn is_solved(&self) -> bool {
+        return self.board.is_filled();
+    }
+
+    /**
+     * The techniques that were used, each once, in the order they were first used.
+     */
+    pub fn techniques_used(&self) -> Vec<Technique> {
+        let mut techniques: Vec<Technique> = Vec::new();
+        for step in self.steps.iter() {
+            if !techniques.contains(&step.technique) {
+                techniques.push(step.technique);
+            }
+        }
+        return techniques;
+    }
+}
+
+/**
+ * Solve a board like a person would, repeatedly making the simplest deduction that is possible, until the board is
+ * solved or none of the techniques applies anymore. Unlike the exact cover search, this never guesses, so it can get
+ * stuck on hard boards; the steps tell which techniques a board needs.
+ */
+pub fn solve_logically(board: &Board) -> LogicSolution {
+    let mut board = board.clone();
+    let mut candidates = board.candidates();
+    let mut steps: Vec<Step> = Vec::new();
+    while let Some(step) = find_step(&board, &candidates) {
+        step.apply(&mut board, &mut candidates);
+        steps.push(step);
+    }
+    return LogicSolution { board, candidates, steps };
+}
+
+/**
+ * Find the deduction of the simplest technique that applies to the board with the given candidates, or None if no
+ * technique makes progress.
+ */
+pub fn find_step(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    return find_naked_single(board, candidates)
+        .or_else(|| find_hidden_single(board, candidates))
+        .or_else(|| find_pointing_pair(board, candidates))
+        .or_else(|| find_box_line_reduction(board, candidates))
+        .or_else(|| find_naked_subset(board, candidates, 2))
+        .or_else(|| find_hidden_subset(board, candidates, 2))
+        .or_else(|| find_naked_subset(board, candidates, 3))
+        .or_else(|| find_hidden_subset(board, candidates, 3))
+        .or_else(|| find_fish(board, candidates, 2))
+        .or_else(|| find_fish(board, candidates, 3))
+        .or_else(|| find_xy_wing(board, candidates))
+        .or_else(|| find_simple_coloring(board, candidates));
+}
+
+/**
+ * The 27 units of the board, rows first, then columns, then blocks, each as its 9 cells.
+ */
+fn units() -> Vec<Vec<(usize, usize)>> {
+    let mut units: Vec<Vec<(usize, usize)>> = Vec::new();
+    units.extend((0..9).map(|i| (0..9).map(|j| (i, j)).collect()));
+    units.extend((0..9).map(|j| (0..9).map(|i| (i, j)).collect()));
+    units.extend((0..9).map(|b| (0..9).map(|k| (b / 3 * 3 + k / 3, b % 3 * 3 + k % 3)).collect()));
+    return units;
+}
+
+/**
+ * Whether two different cells are in the same row, column or block, so they can't have the same digit.
+ */
+fn sees(a: (usize, usize), b: (usize, usize)) -> bool {
+    return a != b && (a.0 == b.0 || a.1 == b.1 || (a.0 / 3 == b.0 / 3 && a.1 / 3 == b.1 / 3));
+}
+
+/**
+ * The empty cells of the board that have the digit as a candidate.
+ */
+fn cells_with_candidate(board: &Board, candidates: &CandidateGrid, digit: u8) -> Vec<(usize, usize)> {
+    return board.iter_cells()
+        .filter(|(i, j, cell_digit)| cell_digit.is_none() && candidates.contains(*i, *j, digit))
+        .map(|(i, j, _)| (i, j))
+        .collect();
+}
+
+/**
+ * All ways to choose k of the items, keeping their order.
+ */
+fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    let mut result: Vec<Vec<T>> = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[idx + 1..], k - 1) {
+            rest.insert(0, *item);
+            result.push(rest);
+        }
+    }
+    return result;
+}
+
+fn to_cells(cells: &[(usize, usize)]) -> Vec<(u8, u8)> {
+    return cells.iter().map(|(i, j)| (*i as u8, *j as u8)).collect();
+}
+
+fn find_naked_single(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    for (i, j, digit) in board.iter_cells() {
+        if digit.is_some() {
+            continue;
+        }
+        if let [digit] = candidates.digits(i, j)[..] {
+            return Some(Step {
+                technique: Technique::NakedSingle,
+                placement: Some((i as u8, j as u8, digit)),
+                eliminations: vec![],
+                cells: vec![(i as u8, j as u8)],
+            });
+        }
+    }
+    return None;
+}
+
+fn find_hidden_single(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    for unit in units() {
+        let empty: Vec<(usize, usize)> = unit.iter().copied().filter(|(i, j)| board.get(*i, *j).is_none()).collect();
+        for digit in 1..10 {
+            if let [(i, j)] = empty.iter().copied().filter(|(i, j)| candidates.contains(*i, *j, digit)).collect::<Vec<_>>()[..] {
+                let others: Vec<(usize, usize)> = unit.iter().copied().filter(|cell| *cell != (i, j)).collect();
+                return Some(Step {
+                    technique: Technique::HiddenSingle,
+                    placement: Some((i as u8, j as u8, digit)),
+                    eliminations: vec![],
+                    cells: to_cells(&others),
+                });
+            }
+        }
+    }
+    return None;
+}
+
+/**
+ * Find a digit whose candidates in one unit all lie in a second unit, so that it can be removed from the rest of the
+ * second unit. For pointing pairs the first unit is a block and the second a row or column, for box-line reductions
+ * the other way around.
+ */
+fn find_locked_candidates(board: &Board, candidates: &CandidateGrid, technique: Technique) -> Option<Step> {
+    let units = units();
+    let (lines, blocks) = units.split_at(18);
+    let (from, to) = match technique {
+        Technique::PointingPair => (blocks, lines),
+        _ => (lines, blocks),
+    };
+    for unit in from.iter() {
+        for digit in 1..10 {
+            let cells: Vec<(usize, usize)> = unit.iter().copied()
+                .filter(|(i, j)| board.get(*i, *j).is_none() && candidates.contains(*i, *j, digit))
+                .collect();
+            if cells.len() < 2 {
+                continue;
+            }
+            let Some(other) = to.iter().find(|other| cells.iter().all(|cell| other.contains(cell))) else {
+                continue;
+            };
+            let eliminations: Vec<(u8, u8, u8)> = other.iter()
+                .filter(|(i, j)| !unit.contains(&(*i, *j)) && board.get(*i, *j).is_none() && candidates.contains(*i, *j, digit))
+                .map(|(i, j)| (*i as u8, *j as u8, digit))
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step { technique, placement: None, eliminations, cells: to_cells(&cells) });
+            }
+        }
+    }
+    return None;
+}
+
+fn find_pointing_pair(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    return find_locked_candidates(board, candidates, Technique::PointingPair);
+}
+
+fn find_box_line_reduction(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    return find_locked_candidates(board, candidates, Technique::BoxLineReduction);
+}
+
+/**
+ * Find size empty cells of a unit that have size candidates between them, which can be removed from the other cells
+ * of the unit.
+ */
+fn find_naked_subset(board: &Board, candidates: &CandidateGrid, size: usize) -> Option<Step> {
+    let technique = if size == 2 { Technique::NakedPair } else { Technique::NakedTriple };
+    for unit in units() {
+        let empty: Vec<(usize, usize)> = unit.iter().copied().filter(|(i, j)| board.get(*i, *j).is_none()).collect();
+        for subset in combinations(&empty, size) {
+            let mut digits: Vec<u8> = subset.iter().flat_map(|(i, j)| candidates.digits(*i, *j)).collect();
+            digits.sort();
+            digits.dedup();
+            if digits.len() != size {
+                continue;
+            }
+            let eliminations: Vec<(u8, u8, u8)> = empty.iter()
+                .filter(|cell| !subset.contains(cell))
+                .flat_map(|(i, j)| digits.iter().filter(|digit| candidates.contains(*i, *j, **digit)).map(|digit| (*i as u8, *j as u8, *digit)))
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step { technique, placement: None, eliminations, cells: to_cells(&subset) });
+            }
+        }
+    }
+    return None;
+}
+
+/**
+ * Find size digits of a unit that have size empty cells left between them, so that the other candidates of those
+ * cells can be removed.
+ */
+fn find_hidden_subset(board: &Board, candidates: &CandidateGrid, size: usize) -> Option<Step> {
+    let technique = if size == 2 { Technique::HiddenPair } else { Technique::HiddenTriple };
+    for unit in units() {
+        let empty: Vec<(usize, usize)> = unit.iter().copied().filter(|(i, j)| board.get(*i, *j).is_none()).collect();
+        let missing: Vec<u8> = (1..10).filter(|digit| empty.iter().any(|(i, j)| candidates.contains(*i, *j, *digit))).collect();
+        for digits in combinations(&missing, size) {
+            let cells: Vec<(usize, usize)> = empty.iter().copied()
+                .filter(|(i, j)| digits.iter().any(|digit| candidates.contains(*i, *j, *digit)))
+                .collect();
+            if cells.len() != size {
+                continue;
+            }
+            let eliminations: Vec<(u8, u8, u8)> = cells.iter()
+                .flat_map(|(i, j)| candidates.digits(*i, *j).into_iter().filter(|digit| !digits.contains(digit)).map(|digit| (*i as u8, *j as u8, digit)))
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step { technique, placement: None, eliminations, cells: to_cells(&cells) });
+            }
+        }
+    }
+    return None;
+}
+
+/**
+ * Find size rows in which a digit has at most size cells left, all in the same size columns, so that the digit can be
+ * removed from the rest of those columns: an X-wing for two rows and a swordfish for three. Columns are tried as the
+ * base lines as well.
+ */
+fn find_fish(board: &Board, candidates: &CandidateGrid, size: usize) -> Option<Step> {
+    let technique = if size == 2 { Technique::XWing } else { Technique::Swordfish };
+    for digit in 1..10 {
+        let cells = cells_with_candidate(board, candidates, digit);
+        // Rows as base lines, then columns, by swapping the coordinates
+        for transposed in [false, true] {
+            let line = |cell: &(usize, usize)| if transposed { cell.1 } else { cell.0 };
+            let cross = |cell: &(usize, usize)| if transposed { cell.0 } else { cell.1 };
+            let base_lines: Vec<usize> = (0..9)
+                .filter(|idx| (2..=size).contains(&cells.iter().filter(|cell| line(cell) == *idx).count()))
+                .collect();
+            for lines in combinations(&base_lines, size) {
+                let base_cells: Vec<(usize, usize)> = cells.iter().copied().filter(|cell| lines.contains(&line(cell))).collect();
+                let mut cross_lines: Vec<usize> = base_cells.iter().map(cross).collect();
+                cross_lines.sort();
+                cross_lines.dedup();
+                if cross_lines.len() != size {
+                    continue;
+                }
+                let eliminations: Vec<(u8, u8, u8)> = cells.iter()
+                    .filter(|cell| cross_lines.contains(&cross(cell)) && !lines.contains(&line(cell)))
+                    .map(|(i, j)| (*i as u8, *j as u8, digit))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Step { technique, placement: None, eliminations, cells: to_cells(&base_cells) });
+                }
+            }
+        }
+    }
+    return None;
+}
+
+fn find_xy_wing(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    let bivalue: Vec<((usize, usize), Vec<u8>)> = board.iter_cells()
+        .filter(|(i, j, digit)| digit.is_none() && candidates.digits(*i, *j).len() == 2)
+        .map(|(i, j, _)| ((i, j), candidates.digits(i, j)))
+        .collect();
+    for (pivot, pivot_digits) in bivalue.iter() {
+        let (x, y) = (pivot_digits[0], pivot_digits[1]);
+        let wings: Vec<&((usize, usize), Vec<u8>)> = bivalue.iter().filter(|(cell, _)| sees(*pivot, *cell)).collect();
+        for (a, a_digits) in wings.iter().filter(|(_, digits)| digits.contains(&x) && !digits.contains(&y)) {
+            let z = if a_digits[0] == x { a_digits[1] } else { a_digits[0] };
+            for (b, _) in wings.iter().filter(|(_, digits)| *digits == [y.min(z), y.max(z)]) {
+                let eliminations: Vec<(u8, u8, u8)> = cells_with_candidate(board, candidates, z).into_iter()
+                    .filter(|cell| sees(*cell, *a) && sees(*cell, *b) && cell != pivot)
+                    .map(|(i, j)| (i as u8, j as u8, z))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::XYWing,
+                        placement: None,
+                        eliminations,
+                        cells: to_cells(&[*pivot, *a, *b]),
+                    });
+                }
+            }
+        }
+    }
+    return None;
+}
+
+/**
+ * Color the chains of conjugate pairs of each digit, i.e. pairs of cells that are the only two cells left for the digit
+ * in a unit, with two alternating colors. Exactly one of the colors of a chain has the digit. If two cells of the same
+ * color see each other, that color is wrong; otherwise, cells outside the chain that see both colors can't have the
+ * digit.
+ */
+fn find_simple_coloring(board: &Board, candidates: &CandidateGrid) -> Option<Step> {
+    for digit in 1..10 {
+        let cells = cells_with_candidate(board, candidates, digit);
+        let conjugate_pairs: Vec<((usize, usize), (usize, usize))> = units().into_iter()
+            .filter_map(|unit| match unit.iter().copied().filter(|cell| cells.contains(cell)).collect::<Vec<_>>()[..] {
+                [a, b] => Some((a, b)),
+                _ => None,
+            })
+            .collect();
+
+        let mut colors: Vec<Option<bool>> = vec![None; cells.len()];
+        let idx_of = |cell: (usize, usize)| cells.iter().position(|other| *other == cell).unwrap();
+        for start in 0..cells.len() {
+            if colors[start].is_some() || !conjugate_pairs.iter().any(|(a, b)| *a == cells[start] || *b == cells[start]) {
+                continue;
+            }
+            // Color the chain of the start cell
+            let mut chain: Vec<usize> = vec![start];
+            colors[start] = Some(true);
+            let mut next = 0;
+            while next < chain.len() {
+                let cell = cells[chain[next]];
+                let color = colors[chain[next]].unwrap();
+                for (a, b) in conjugate_pairs.iter() {
+                    let other = if *a == cell { *b } else if *b == cell { *a } else { continue };
+                    let other_idx = idx_of(other);
+                    if colors[other_idx].is_none() {
+                        colors[other_idx] = Some(!color);
+                        chain.push(other_idx);
+                    }
+                }
+                next += 1;
+            }
+
+            let chain_cells: Vec<(usize, usize)> = chain.iter().map(|idx| cells[*idx]).collect();
+            let (on, off): (Vec<_>, Vec<_>) = chain_cells.iter().partition(|cell| colors[idx_of(**cell)] == Some(true));
+            let sees_itself = |color: &Vec<(usize, usize)>| color.iter().any(|a| color.iter().any(|b| sees(*a, *b)));
+            let eliminated: Vec<(usize, usize)> = if sees_itself(&on) {
+                on
+            } else if sees_itself(&off) {
+                off
+            } else {
+                cells.iter().copied()
+                    .filter(|cell| !chain_cells.contains(cell))
+                    .filter(|cell| on.iter().any(|a| sees(*a, *cell)) && off.iter().any(|b| sees(*b, *cell)))
+                    .collect()
+            };
+            if !eliminated.is_empty() {
+                return Some(Step {
+                    technique: Technique::SimpleColoring,
+                    placement: None,
+                    eliminations: eliminated.iter().map(|(i, j)| (*i as u8, *j as u8, digit)).collect(),
+                    cells: to_cells(&chain_cells),
+                });
+            }
+        }
+    }
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::sudoku::solve_sudoku_with_exact_cover;
+
+    use super::*;
+
+    #[rstest]
+    #[case("data/sudoku_easy.txt")]
+    #[case("data/sudoku_medium.txt")]
+    #[case("data/sudoku_hard.txt")]
+    #[case("data/sudoku_evil.txt")]
+    fn test_solve_logically(#[case] filepath: &str) {
+        let board = Board::read_from_file(filepath).unwrap();
+
+        let solution = solve_logically(&board);
+
+        assert!(solution.is_solved());
+        assert_eq!(Some(solution.board), solve_sudoku_with_exact_cover(&board).unwrap());
+    }
+
+    #[test]
+    fn test_solve_logically_gets_stuck() {
+        let board = Board::read_from_file("data/sudoku_ai_escargot.txt").unwrap();
+        let expected = solve_sudoku_with_exact_cover(&board).unwrap().unwrap();
+
+        let solution = solve_logically(&board);
+
+        // Every digit that was placed is right, but the solver can't finish without guessing
+        assert!(!solution.is_solved());
+        for (i, j, digit) in solution.board.iter_cells() {
+            assert!(digit.is_none() || digit == expected.get(i, j));
+        }
+    }
+
+    #[test]
+    fn test_techniques_used() {
+        let board = Board::read_from_file("data/sudoku_easy.txt").unwrap();
+
+        let solution = solve_logically(&board);
+
+        assert_eq!(solution.steps[0].technique, Technique::NakedSingle);
+        assert!(solution.techniques_used().iter().all(|technique| [Technique::NakedSingle, Technique::HiddenSingle].contains(technique)));
+        assert_eq!(solution.steps.iter().filter(|step| step.placement.is_some()).count(), board.empty_cells().len());
+    }
+
+    #[test]
+    fn test_naked_pair() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        candidates.restrict(0, 0, &[1, 2]).unwrap();
+        candidates.restrict(0, 5, &[1, 2]).unwrap();
+
+        let step = find_naked_subset(&board, &candidates, 2).unwrap();
+
+        assert_eq!(step.technique, Technique::NakedPair);
+        assert_eq!(step.cells, vec![(0, 0), (0, 5)]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(step.eliminations.iter().all(|(row, _, digit)| *row == 0 && (*digit == 1 || *digit == 2)));
+    }
+
+    #[test]
+    fn test_hidden_pair() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        for j in 2..9 {
+            candidates.remove(0, j, 1).unwrap();
+            candidates.remove(0, j, 2).unwrap();
+        }
+
+        let step = find_hidden_subset(&board, &candidates, 2).unwrap();
+
+        assert_eq!(step.technique, Technique::HiddenPair);
+        assert_eq!(step.cells, vec![(0, 0), (0, 1)]);
+        assert_eq!(step.eliminations.len(), 14);
+    }
+
+    #[test]
+    fn test_pointing_pair() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        for (i, j) in [(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2), (0, 2)] {
+            candidates.remove(i, j, 5).unwrap();
+        }
+
+        let step = find_pointing_pair(&board, &candidates).unwrap();
+
+        assert_eq!(step.cells, vec![(0, 0), (0, 1)]);
+        assert_eq!(step.eliminations, (3..9).map(|j| (0, j, 5)).collect::<Vec<(u8, u8, u8)>>());
+    }
+
+    #[test]
+    fn test_x_wing() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        for j in [0, 1, 3, 4, 5, 7, 8] {
+            candidates.remove(1, j, 5).unwrap();
+            candidates.remove(4, j, 5).unwrap();
+        }
+
+        let step = find_fish(&board, &candidates, 2).unwrap();
+
+        assert_eq!(step.technique, Technique::XWing);
+        assert_eq!(step.cells, vec![(1, 2), (1, 6), (4, 2), (4, 6)]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(step.eliminations.iter().all(|(row, col, digit)| *row != 1 && *row != 4 && (*col == 2 || *col == 6) && *digit == 5));
+    }
+
+    #[test]
+    fn test_swordfish() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        for (row, cols) in [(0, [1, 4]), (3, [4, 7]), (6, [1, 7])] {
+            for j in (0..9).filter(|j| !cols.contains(j)) {
+                candidates.remove(row, j, 5).unwrap();
+            }
+        }
+
+        let step = find_fish(&board, &candidates, 3).unwrap();
+
+        assert_eq!(step.technique, Technique::Swordfish);
+        assert_eq!(step.cells, vec![(0, 1), (0, 4), (3, 4), (3, 7), (6, 1), (6, 7)]);
+        assert_eq!(step.eliminations.len(), 18);
+        assert!(find_fish(&board, &candidates, 2).is_none());
+    }
+
+    #[test]
+    fn test_xy_wing() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        candidates.restrict(0, 0, &[1, 2]).unwrap();
+        candidates.restrict(0, 4, &[1, 3]).unwrap();
+        candidates.restrict(4, 0, &[2, 3]).unwrap();
+
+        let step = find_xy_wing(&board, &candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::XYWing);
+        assert_eq!(step.cells, vec![(0, 0), (0, 4), (4, 0)]);
+        assert_eq!(step.eliminations, vec![(4, 4, 3)]);
+    }
+
+    #[test]
+    fn test_simple_coloring() {
+        let board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        // Chain (3, 0) - (0, 0) - (0, 5) - (4, 5), so either (3, 0) or (4, 5) has a 7
+        for k in 0..9 {
+            if k != 0 && k != 5 {
+                candidates.remove(0, k, 7).unwrap();
+            }
+            if k != 0 && k != 3 {
+                candidates.remove(k, 0, 7).unwrap();
+            }
+            if k != 0 && k != 4 {
+                candidates.remove(k, 5, 7).unwrap();
+            }
+        }
+
+        let step = find_simple_coloring(&board, &candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::SimpleColoring);
+        assert_eq!(step.cells, vec![(0, 0), (0, 5), (3, 0), (4, 5)]);
+        assert_eq!(step.eliminations, vec![(3, 3, 7), (3, 4, 7), (4, 1, 7), (4, 2, 7)]);
+    }
+
+    #[test]
+    fn test_step_apply() {
+        let mut board = Board::empty();
+        let mut candidates = CandidateGrid::full();
+        let step = Step { technique: Technique::NakedSingle, placement: Some((4, 4, 7)), eliminations: vec![(0, 0, 1)], cells: vec![(4, 4)] };
+
+        step.apply(&mut board, &mut candidates);
+
+        assert_eq!(board.get(4, 4).map(|digit| digit.get()), Some(7));
+        assert_eq!(candidates.digits(4, 4), vec![7]);
+        assert!(!candidates.contains(4, 0, 7) && !candidates.contains(0, 4, 7) && !candidates.contains(3, 3, 7));
+        assert!(candidates.contains(3, 0, 7));
+        assert!(!candidates.contains(0, 0, 1));
+    }
+}
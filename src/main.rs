@@ -1,32 +1,655 @@
-extern crate core;
+use std::error::Error;
+use std::io::IsTerminal;
 
-use crate::lib::sudoku::{Board, convert_to_exact_cover_problem, convert_to_sudoku_solution};
+use exact_cover::{BitsetBackend, ChromeTrace, DancingCellsBackend, GeneralBackend, SearchStats, SolutionCache, SolverBackend};
+use config::Config;
+use rust_sudoku::messages::{Locale, Localize, Message};
+use rust_sudoku::puzzle::Puzzle;
+use rust_sudoku::sudoku::{
+    Board, BoardReadError, CountEngine, DigitSymbols, count_solutions, count_sudoku_solutions, count_sudoku_solutions_with_cache,
+    explain_unsolvable, LogicEffort, SearchEffort, rate_by_search_effort, rate_by_techniques, solve_sudoku_with_backend_and_stats, solve_sudoku_with_exact_cover, solve_sudoku_with_memory_limit,
+    solve_sudoku_with_observers_and_memory_limit, solve_sudokus, verify_solution,
+};
+use rust_sudoku::wordoku::find_hidden_words;
+use serde::Serialize;
 
-mod lib;
+mod config;
+mod selftest;
 
 fn main() {
-    let filename = "data/sudoku.txt";
-    let result = Board::read_from_file(filename);
+    env_logger::init();
+
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mut locale = Locale::from_env();
+    let mut lang_locale: Option<Locale> = None;
+    if let Some(lang_idx) = args.iter().position(|arg| arg == "--lang") {
+        let tag = args.drain(lang_idx..(lang_idx + 2).min(args.len())).nth(1).unwrap_or_default();
+        match Locale::from_tag(&tag) {
+            Some(tag_locale) => lang_locale = Some(tag_locale),
+            None => {
+                println!("{}", Message::InvalidLanguage.text(locale));
+                std::process::exit(1);
+            }
+        }
+    }
+    let mut config_path: Option<String> = None;
+    if let Some(config_idx) = args.iter().position(|arg| arg == "--config") {
+        config_path = args.drain(config_idx..(config_idx + 2).min(args.len())).nth(1);
+    }
+    let config = match Config::load(config_path.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            let locale = lang_locale.unwrap_or(locale);
+            println!("{}", Message::InvalidConfig(&format_error_chain(&e, locale)).text(locale));
+            std::process::exit(1);
+        }
+    };
+    // --lang takes precedence over the config file, which takes precedence over the environment
+    locale = lang_locale.or(config.locale).unwrap_or(locale);
+    let command = args.first().cloned();
+    let command_args = || args.iter().skip(1).cloned().collect();
+    // Every command returns whether it succeeded, so that scripts can tell errors apart by the exit status
+    let success = match command.as_deref() {
+        Some("selftest") => selftest::run_selftest(),
+        Some("rate") => rate_board_file(command_args(), &config, locale),
+        Some("export") => export_board_file(command_args(), &config, locale),
+        Some("verify") => verify_board_files(command_args(), locale),
+        Some("compare-engines") => compare_engines(command_args(), locale),
+        Some("solve-all") => solve_collection(command_args(), locale),
+        Some("count") => count_board_file(command_args(), &config, locale),
+        _ => solve_board_file(args, &config, locale),
+    };
+    if !success {
+        std::process::exit(1);
+    }
+}
+
+/**
+ * Solve the board given by the arguments (see read_board) and print the result. With --trace, the steps of the search
+ * are written to the given file in the Chrome trace event format. With --color, the givens and the digits filled in by
+ * the solver are printed in different colors: always, never, or by default only if standard output is a terminal and
+ * the NO_COLOR environment variable is not set. Returns whether the board was solved.
+ */
+fn solve_board_file(args: Vec<String>, config: &Config, locale: Locale) -> bool {
+    let mut filename: Option<String> = None;
+    let mut max_memory: Option<usize> = config.max_memory;
+    let mut symbols = config.symbols.clone();
+    let mut dictionary: Option<Vec<String>> = None;
+    let mut trace_path: Option<String> = None;
+    let auto_color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let mut color = auto_color;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--symbols" {
+            match DigitSymbols::new(&args.next().unwrap_or_default()) {
+                Ok(digit_symbols) => symbols = digit_symbols,
+                Err(e) => {
+                    println!("{}", Message::InvalidSymbols(&e.localize(locale)).text(locale));
+                    return false;
+                }
+            }
+        } else if arg == "--words" {
+            match std::fs::read_to_string(args.next().unwrap_or_default()) {
+                Ok(contents) => dictionary = Some(contents.split_whitespace().map(String::from).collect()),
+                Err(e) => {
+                    println!("{}", Message::InvalidWords(&e.to_string()).text(locale));
+                    return false;
+                }
+            }
+        } else if arg == "--max-memory" {
+            match args.next().and_then(|value| value.parse::<usize>().ok()).and_then(|megabytes| megabytes.checked_mul(1024 * 1024)) {
+                Some(max_bytes) => max_memory = Some(max_bytes),
+                None => {
+                    println!("{}", Message::InvalidMaxMemory.text(locale));
+                    return false;
+                }
+            }
+        } else if arg == "--trace" {
+            match args.next() {
+                Some(path) => trace_path = Some(path),
+                None => {
+                    println!("{}", Message::MissingTraceFile.text(locale));
+                    return false;
+                }
+            }
+        } else if arg == "--color" {
+            match args.next().as_deref() {
+                Some("auto") => color = auto_color,
+                Some("always") => color = true,
+                Some("never") => color = false,
+                _ => {
+                    println!("{}", Message::InvalidColor.text(locale));
+                    return false;
+                }
+            }
+        } else {
+            filename = Some(arg);
+        }
+    }
+
+    let result = read_board(filename.as_deref(), &config.board, &symbols);
     match result {
         Ok(board) => {
-            println!("Board:");
-            println!("{}", board);
-            let exact_cover_problem = convert_to_exact_cover_problem(&board);
-            let solution = exact_cover_problem.solve();
-            let solution = solution.map(convert_to_sudoku_solution);
+            println!("{}", Message::BoardHeading.text(locale));
+            println!("{}", board.format_with_symbols(&symbols));
+
+            // Report every repeated given up front, instead of only the first one the encoding runs into
+            if let Err(conflicts) = board.validate() {
+                for conflict in conflicts.iter() {
+                    println!("{}", Message::Conflict(conflict).text(locale));
+                }
+                return false;
+            }
 
-            match solution {
-                Some(solution) => {
-                    println!("Solution:");
-                    println!("{}", solution);
+            let mut trace = ChromeTrace::new();
+            let mut trace_written = true;
+            let solution = match (max_memory, &trace_path) {
+                (_, Some(_)) => solve_sudoku_with_observers_and_memory_limit(&board, &mut [&mut trace], max_memory),
+                (Some(max_bytes), None) => solve_sudoku_with_memory_limit(&board, max_bytes),
+                (None, None) => solve_sudoku_with_exact_cover(&board),
+            };
+            if let Some(trace_path) = &trace_path {
+                let written = std::fs::File::create(trace_path)
+                    .and_then(|file| trace.write_json(std::io::BufWriter::new(file)));
+                if let Err(e) = written {
+                    println!("{}", Message::TraceWriteError(&e.to_string()).text(locale));
+                    trace_written = false;
+                }
+            }
+            let solved = match solution {
+                Ok(Some(solution)) => {
+                    println!("{}", Message::SolutionHeading.text(locale));
+                    if color {
+                        println!("{}", solution.format_with_givens(&board, &symbols));
+                    } else {
+                        println!("{}", solution.format_with_symbols(&symbols));
+                    }
+                    if let Some(dictionary) = &dictionary {
+                        println!("{}", Message::HiddenWordsHeading.text(locale));
+                        for hidden_word in find_hidden_words(&solution, &symbols, dictionary) {
+                            let message = Message::HiddenWord {
+                                word: &hidden_word.word,
+                                line: &hidden_word.line,
+                                start: hidden_word.start,
+                            };
+                            println!("{}", message.text(locale));
+                        }
+                    }
+                    true
+                }
+                Ok(None) => {
+                    println!("{}", Message::NoSolutionFound.text(locale));
+                    if let Ok(Some(explanation)) = explain_unsolvable(&board) {
+                        if let Some(reason) = explanation.reason {
+                            println!("{}", Message::Reason(&reason).text(locale));
+                        }
+                        println!("{}", Message::ConflictingGivens(&explanation.givens).text(locale));
+                    }
+                    false
+                }
+                Err(e) => {
+                    println!("{}", Message::SolveError(&format_error_chain(&e, locale)).text(locale));
+                    false
                 }
+            };
+            return solved && trace_written;
+        }
+        Err(e) => {
+            println!("{}", Message::ReadError(&format_error_chain(&e, locale)).text(locale));
+            return false;
+        }
+    }
+}
+
+/**
+ * Count the solutions of the board given by the arguments (see read_board) with the engine chosen by --engine
+ * (backtracking by default). With --cache, counts are stored in the user's cache directory. Returns whether the
+ * solutions were counted.
+ */
+fn count_board_file(args: Vec<String>, config: &Config, locale: Locale) -> bool {
+    let mut filename: Option<String> = None;
+    let mut engine = config.engine;
+    let mut cache: Option<SolutionCache> = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--engine" {
+            match args.next().as_deref() {
+                Some("backtracking") => engine = CountEngine::Backtracking,
+                Some("zdd") => engine = CountEngine::Zdd,
+                _ => {
+                    println!("{}", Message::InvalidEngine.text(locale));
+                    return false;
+                }
+            }
+        } else if arg == "--cache" {
+            let solution_cache = match &config.cache_dir {
+                Some(cache_dir) => Some(SolutionCache::new(cache_dir)),
+                None => SolutionCache::in_user_cache_dir(),
+            };
+            match solution_cache {
+                Some(solution_cache) => cache = Some(solution_cache),
                 None => {
-                    println!("No solution found");
+                    println!("{}", Message::MissingCacheDir.text(locale));
+                    return false;
+                }
+            }
+        } else {
+            filename = Some(arg);
+        }
+    }
+
+    match read_board(filename.as_deref(), &config.board, &DigitSymbols::DIGITS) {
+        Ok(board) => {
+            println!("{}", Message::BoardHeading.text(locale));
+            println!("{}", board);
+            let num_solutions = match &cache {
+                Some(cache) => count_sudoku_solutions_with_cache(&board, engine, cache),
+                None => count_sudoku_solutions(&board, engine),
+            };
+            match num_solutions {
+                Ok(num_solutions) => {
+                    println!("{}", Message::NumSolutions(num_solutions).text(locale));
+                    return true;
+                }
+                Err(e) => {
+                    println!("{}", Message::CountError(&format_error_chain(&e, locale)).text(locale));
+                    return false;
+                }
+            }
+        }
+        Err(e) => {
+            println!("{}", Message::ReadError(&format_error_chain(&e, locale)).text(locale));
+            return false;
+        }
+    }
+}
+
+/**
+ * The formats a board can be exported to.
+ */
+enum ExportFormat {
+    Latex,
+    Sdk,
+}
+
+/**
+ * Export the board given by the arguments (see read_board) in the format chosen by --latex or --sdk. With --solution,
+ * the solution is exported after the board. Returns whether everything was exported.
+ */
+fn export_board_file(args: Vec<String>, config: &Config, locale: Locale) -> bool {
+    let mut filename: Option<String> = None;
+    let mut format: Option<ExportFormat> = None;
+    let mut with_solution = false;
+    let mut symbols = config.symbols.clone();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--latex" {
+            format = Some(ExportFormat::Latex);
+        } else if arg == "--sdk" {
+            format = Some(ExportFormat::Sdk);
+        } else if arg == "--solution" {
+            with_solution = true;
+        } else if arg == "--symbols" {
+            match DigitSymbols::new(&args.next().unwrap_or_default()) {
+                Ok(digit_symbols) => symbols = digit_symbols,
+                Err(e) => {
+                    println!("{}", Message::InvalidSymbols(&e.localize(locale)).text(locale));
+                    return false;
+                }
+            }
+        } else {
+            filename = Some(arg);
+        }
+    }
+    let format = match format {
+        Some(format) => format,
+        None => {
+            println!("{}", Message::MissingExportFormat.text(locale));
+            return false;
+        }
+    };
+    let export = |board: &Board| match format {
+        ExportFormat::Latex => board.format_as_latex(&symbols),
+        ExportFormat::Sdk => Puzzle::new(board.clone()).format_as_sdk().expect("a puzzle without metadata has no header lines"),
+    };
+
+    match read_board(filename.as_deref(), &config.board, &symbols) {
+        Ok(board) => {
+            print!("{}", export(&board));
+            if !with_solution {
+                return true;
+            }
+            match solve_sudoku_with_exact_cover(&board) {
+                Ok(Some(solution)) => {
+                    println!();
+                    print!("{}", export(&solution));
+                    return true;
                 }
+                Ok(None) => println!("{}", Message::NoSolutionFound.text(locale)),
+                Err(e) => println!("{}", Message::SolveError(&format_error_chain(&e, locale)).text(locale)),
             }
+            return false;
         }
         Err(e) => {
-            println!("Error reading file: {:?}", e);
+            println!("{}", Message::ReadError(&format_error_chain(&e, locale)).text(locale));
+            return false;
+        }
+    }
+}
+
+/**
+ * Verify the solutions in the file given by --solutions against the puzzles in the file given by --puzzles, which
+ * contain one board of 81 cells per line. The boards are paired by their position in the files and every mismatch is
+ * reported with the number of the pair. Returns whether all solutions are correct.
+ */
+fn verify_board_files(args: Vec<String>, locale: Locale) -> bool {
+    let mut puzzles_filename: Option<String> = None;
+    let mut solutions_filename: Option<String> = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--puzzles" {
+            puzzles_filename = args.next();
+        } else if arg == "--solutions" {
+            solutions_filename = args.next();
+        }
+    }
+    let (puzzles_filename, solutions_filename) = match (puzzles_filename, solutions_filename) {
+        (Some(puzzles_filename), Some(solutions_filename)) => (puzzles_filename, solutions_filename),
+        _ => {
+            println!("{}", Message::MissingVerifyFiles.text(locale));
+            return false;
+        }
+    };
+    let (puzzles, solutions) = match (std::fs::read_to_string(&puzzles_filename), std::fs::read_to_string(&solutions_filename)) {
+        (Ok(puzzles), Ok(solutions)) => (puzzles, solutions),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("{}", Message::ReadError(&e.to_string()).text(locale));
+            return false;
+        }
+    };
+    let puzzles: Vec<&str> = puzzles.lines().filter(|line| !line.trim().is_empty()).collect();
+    let solutions: Vec<&str> = solutions.lines().filter(|line| !line.trim().is_empty()).collect();
+
+    let mut num_correct: usize = 0;
+    for (idx, (puzzle, solution)) in puzzles.iter().zip(solutions.iter()).enumerate() {
+        let index = idx + 1;
+        let puzzle = match puzzle.parse::<Board>() {
+            Ok(puzzle) => puzzle,
+            Err(e) => {
+                println!("{}", Message::InvalidPuzzle { index, error: &format_error_chain(&e, locale) }.text(locale));
+                continue;
+            }
+        };
+        let solution = match solution.parse::<Board>() {
+            Ok(solution) => solution,
+            Err(e) => {
+                println!("{}", Message::InvalidSolution { index, error: &format_error_chain(&e, locale) }.text(locale));
+                continue;
+            }
+        };
+        match verify_solution(&puzzle, &solution) {
+            Ok(()) => num_correct += 1,
+            Err(mismatch) => println!("{}", Message::SolutionMismatch { index, reason: &mismatch.localize(locale) }.text(locale)),
+        }
+    }
+    if puzzles.len() != solutions.len() {
+        println!("{}", Message::BoardCountMismatch { num_puzzles: puzzles.len(), num_solutions: solutions.len() }.text(locale));
+    }
+    println!("{}", Message::CorrectSolutions { num_correct, num_puzzles: puzzles.len() }.text(locale));
+    return num_correct == puzzles.len() && puzzles.len() == solutions.len();
+}
+
+/**
+ * Solve every puzzle of a collection, one board of 81 cells per line, with every exact cover backend, and print how
+ * long each backend took and how many nodes its own search needed. Every backend must solve the same puzzles as the
+ * first one with a correct solution, and find the same solution for puzzles whose solution is unique. Returns whether
+ * all backends agree.
+ */
+fn compare_engines(args: Vec<String>, locale: Locale) -> bool {
+    let Some(filename) = args.into_iter().next() else {
+        println!("{}", Message::MissingCollectionFile("compare-engines").text(locale));
+        return false;
+    };
+    let contents = match std::fs::read_to_string(&filename) {
+        Ok(contents) => contents,
+        Err(e) => {
+            println!("{}", Message::ReadError(&e.to_string()).text(locale));
+            return false;
+        }
+    };
+    let mut puzzles: Vec<(usize, Board)> = Vec::new();
+    for (idx, line) in contents.lines().filter(|line| !line.trim().is_empty()).enumerate() {
+        match line.parse::<Board>() {
+            Ok(puzzle) => puzzles.push((idx + 1, puzzle)),
+            Err(e) => println!("{}", Message::InvalidPuzzle { index: idx + 1, error: &format_error_chain(&e, locale) }.text(locale)),
+        }
+    }
+
+    // The number of solutions, up to 2, tells which puzzles must get identical solutions from every backend
+    let counts: Vec<u64> = puzzles.iter().map(|(_, puzzle)| count_solutions(puzzle, Some(2))).collect();
+
+    let backends: [&dyn SolverBackend; 3] = [&GeneralBackend, &BitsetBackend, &DancingCellsBackend];
+    let mut first_solutions: Vec<Option<Board>> = Vec::new();
+    let mut first_solved: Vec<bool> = Vec::new();
+    let mut agree = true;
+    println!("{}", Message::EngineTableHeading.text(locale));
+    for backend in backends {
+        let start = std::time::Instant::now();
+        let results: Vec<Option<(Option<Board>, SearchStats)>> = puzzles.iter()
+            .map(|(_, puzzle)| solve_sudoku_with_backend_and_stats(puzzle, backend).ok())
+            .collect();
+        let total_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let num_nodes: u64 = results.iter().flatten().map(|(_, stats)| stats.num_nodes).sum();
+        let solutions: Vec<Option<Board>> = results.into_iter().map(|result| result.and_then(|(solution, _)| solution)).collect();
+
+        let solved: Vec<bool> = puzzles.iter().zip(solutions.iter())
+            .map(|((_, puzzle), solution)| solution.as_ref().is_some_and(|solution| verify_solution(puzzle, solution).is_ok()))
+            .collect();
+        if first_solved.is_empty() {
+            first_solved = solved.clone();
+            first_solutions = solutions.clone();
+        }
+        for (idx, (index, _)) in puzzles.iter().enumerate() {
+            let unique = counts[idx] == 1;
+            if solved[idx] != first_solved[idx] {
+                println!("{}", Message::EngineMismatch { index: *index, engine: backend.name() }.text(locale));
+                agree = false;
+            } else if unique && solutions[idx] != first_solutions[idx] {
+                println!("{}", Message::EngineSolutionMismatch { index: *index, engine: backend.name() }.text(locale));
+                agree = false;
+            }
+        }
+        let num_solved = solved.iter().filter(|solved| **solved).count();
+        let timing = Message::EngineTiming { engine: backend.name(), num_solved, num_puzzles: puzzles.len(), total_ms, num_nodes };
+        println!("{}", timing.text(locale));
+    }
+    return agree;
+}
+
+/**
+ * Solve every board of a collection, one board of 81 cells per line, on as many threads as given by --threads, or one
+ * per core by default. The solutions are printed as lines of 81 cells in the order of the collection, and boards that
+ * can't be read or solved are reported by their number instead. Returns whether every board was solved.
+ */
+fn solve_collection(args: Vec<String>, locale: Locale) -> bool {
+    let mut filename: Option<String> = None;
+    // rayon picks one thread per core for 0
+    let mut num_threads: usize = 0;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--threads" {
+            match args.next().and_then(|value| value.parse::<usize>().ok()) {
+                Some(threads) if threads > 0 => num_threads = threads,
+                _ => {
+                    println!("{}", Message::InvalidThreads.text(locale));
+                    return false;
+                }
+            }
+        } else {
+            filename = Some(arg);
         }
     }
+    let Some(filename) = filename else {
+        println!("{}", Message::MissingCollectionFile("solve-all").text(locale));
+        return false;
+    };
+    let contents = match std::fs::read_to_string(&filename) {
+        Ok(contents) => contents,
+        Err(e) => {
+            println!("{}", Message::ReadError(&e.to_string()).text(locale));
+            return false;
+        }
+    };
+    let puzzles: Vec<Result<Board, BoardReadError>> = contents.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.parse::<Board>())
+        .collect();
+    let boards: Vec<Board> = puzzles.iter().filter_map(|puzzle| puzzle.as_ref().ok().cloned()).collect();
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(num_threads).build().expect("the thread pool can be created");
+    let mut solutions = pool.install(|| solve_sudokus(&boards)).into_iter();
+    let mut all_solved = true;
+    for (idx, puzzle) in puzzles.iter().enumerate() {
+        let index = idx + 1;
+        let message = match puzzle.as_ref().map(|_| solutions.next().unwrap()) {
+            Ok(Ok(Some(solution))) => {
+                println!("{}", solution.to_line());
+                continue;
+            }
+            Ok(Ok(None)) => Message::BoardNoSolution { index }.text(locale),
+            Ok(Err(e)) => Message::BoardSolveError { index, error: &format_error_chain(&e, locale) }.text(locale),
+            Err(e) => Message::InvalidPuzzle { index, error: &format_error_chain(e, locale) }.text(locale),
+        };
+        println!("{}", message);
+        all_solved = false;
+    }
+    return all_solved;
+}
+
+/**
+ * The rating of a board as printed by rate --json, for tools that want the measurements behind the rating rather than
+ * a single score.
+ */
+#[derive(Serialize)]
+struct RateReport<'a> {
+    /// The board as a single line of 81 cells
+    board: String,
+    /// The difficulty score derived from the search effort
+    difficulty: f64,
+    /// The search effort the difficulty is derived from
+    search_effort: &'a SearchEffort,
+    /// The techniques a person needs and the digits they have to guess, or None if the board has no solution
+    logic_effort: Option<&'a LogicEffort>,
+}
+
+/**
+ * The report printed by rate --json for a board that is already filled in, which needs no search: its status is
+ * "already solved", or "invalid" if it breaks a rule.
+ */
+#[derive(Serialize)]
+struct FilledRateReport {
+    /// The board as a single line of 81 cells
+    board: String,
+    /// Whether the board is a valid solution
+    status: &'static str,
+}
+
+/**
+ * Rate the board given by the arguments (see read_board) by the effort the search needs. With --json, the rating is
+ * printed as a JSON object instead, together with the techniques a person needs for the board. A board that is already
+ * filled in is reported as already solved, or as invalid if it breaks a rule, instead of being rated. Returns whether
+ * the board was rated.
+ */
+fn rate_board_file(args: Vec<String>, config: &Config, locale: Locale) -> bool {
+    let mut filename: Option<String> = None;
+    let mut json = false;
+    for arg in args {
+        if arg == "--json" {
+            json = true;
+        } else {
+            filename = Some(arg);
+        }
+    }
+
+    match read_board(filename.as_deref(), &config.board, &DigitSymbols::DIGITS) {
+        Ok(board) if board.is_filled() => {
+            let valid = board.is_valid_solution();
+            if json {
+                let report = FilledRateReport { board: board.to_line(), status: if valid { "already solved" } else { "invalid" } };
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            } else {
+                println!("{}", Message::BoardHeading.text(locale));
+                println!("{}", board);
+                println!("{}", if valid { Message::AlreadySolved } else { Message::InvalidFilledBoard }.text(locale));
+            }
+            return true;
+        }
+        Ok(board) if json => match rate_by_search_effort(&board).and_then(|effort| Ok((effort, rate_by_techniques(&board)?))) {
+            Ok((effort, logic_effort)) => {
+                let report = RateReport {
+                    board: board.to_line(),
+                    difficulty: effort.difficulty(),
+                    search_effort: &effort,
+                    logic_effort: logic_effort.as_ref(),
+                };
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+                return true;
+            }
+            Err(e) => {
+                println!("{}", Message::RateError(&format_error_chain(&e, locale)).text(locale));
+                return false;
+            }
+        },
+        Ok(board) => {
+            println!("{}", Message::BoardHeading.text(locale));
+            println!("{}", board);
+            match rate_by_search_effort(&board) {
+                Ok(effort) => {
+                    println!("{}", Message::SolutionKind(effort.num_solutions).text(locale));
+                    println!("{}", Message::NumForcedDigits(effort.num_forced_digits).text(locale));
+                    let search_effort = Message::SearchEffort {
+                        num_nodes: effort.num_nodes,
+                        num_backtracks: effort.num_backtracks,
+                    };
+                    println!("{}", search_effort.text(locale));
+                    println!("{}", Message::SearchDifficulty(effort.difficulty()).text(locale));
+                    return true;
+                }
+                Err(e) => {
+                    println!("{}", Message::RateError(&format_error_chain(&e, locale)).text(locale));
+                    return false;
+                }
+            }
+        }
+        Err(e) => {
+            println!("{}", Message::ReadError(&format_error_chain(&e, locale)).text(locale));
+            return false;
+        }
+    }
+}
+
+/**
+ * Read the board from the given file, from standard input if the file is "-", or from the default file without a file.
+ * Standard input is only read when asked for, so that a pipe that is left open doesn't block the program.
+ */
+fn read_board(filename: Option<&str>, default_filename: &str, symbols: &DigitSymbols) -> Result<Board, BoardReadError> {
+    return match filename {
+        Some("-") => Board::read_from_reader_with_symbols(std::io::stdin().lock(), symbols),
+        Some(filename) => Board::read_from_file_with_symbols(filename, symbols),
+        None => Board::read_from_file_with_symbols(default_filename, symbols),
+    };
+}
+
+/**
+ * Format an error in the given locale together with all of its sources, e.g. "could not read the board file: No such
+ * file or directory". The sources come from other libraries and are not localized.
+ */
+fn format_error_chain(error: &(impl Error + Localize), locale: Locale) -> String {
+    let mut out = error.localize(locale);
+    let mut source = error.source();
+    while let Some(error) = source {
+        out.push_str(&format!(": {}", error));
+        source = error.source();
+    }
+    return out;
 }
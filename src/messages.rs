@@ -0,0 +1,405 @@
+use crate::sudoku::{BoardReadError, Conflict, DigitSymbolsError, SolutionMismatch, SolveError, Unit, Violation};
+use crate::wordoku::WordLine;
+
+/**
+ * A language in which user-facing text is written.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Locale {
+    English,
+    Dutch,
+}
+
+impl Locale {
+    /**
+     * The locale for a language tag such as "nl", "nl_NL.UTF-8" or "en-GB", or None if the language isn't supported.
+     */
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['_', '-', '.']).next().unwrap_or_default().to_lowercase();
+        return match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::English),
+            "nl" => Some(Locale::Dutch),
+            _ => None,
+        };
+    }
+
+    /**
+     * The locale chosen by the environment, looking at LC_ALL, LC_MESSAGES and LANG in that order like gettext does.
+     * Falls back to English if none of them is set to a supported language.
+     */
+    pub fn from_env() -> Locale {
+        return ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|tag| !tag.is_empty())
+            .and_then(|tag| Locale::from_tag(&tag))
+            .unwrap_or(Locale::English);
+    }
+}
+
+/**
+ * A line of text shown to the user by the command line interface. Values that are formatted the same way in every
+ * locale, such as boards and error details, are passed in already formatted.
+ */
+#[derive(Debug, PartialEq)]
+pub enum Message<'a> {
+    BoardHeading,
+    SolutionHeading,
+    NoSolutionFound,
+    Reason(&'a Violation),
+    ConflictingGivens(&'a [(u8, u8, u8)]),
+    Conflict(&'a Conflict),
+    HiddenWordsHeading,
+    HiddenWord { word: &'a str, line: &'a WordLine, start: usize },
+    NumSolutions(u128),
+    /// Whether a board has no, a unique or multiple solutions, given the number of solutions found (up to 2)
+    SolutionKind(u64),
+    NumForcedDigits(u64),
+    SearchEffort { num_nodes: u64, num_backtracks: u64 },
+    SearchDifficulty(f64),
+    /// The board given to rate is already filled in correctly
+    AlreadySolved,
+    /// The board given to rate is filled in, but breaks a rule
+    InvalidFilledBoard,
+    ReadError(&'a str),
+    SolveError(&'a str),
+    CountError(&'a str),
+    RateError(&'a str),
+    InvalidSymbols(&'a str),
+    InvalidWords(&'a str),
+    InvalidMaxMemory,
+    InvalidEngine,
+    InvalidColor,
+    InvalidLanguage,
+    MissingCacheDir,
+    MissingExportFormat,
+    MissingTraceFile,
+    MissingVerifyFiles,
+    InvalidConfig(&'a str),
+    InvalidPuzzle { index: usize, error: &'a str },
+    InvalidSolution { index: usize, error: &'a str },
+    SolutionMismatch { index: usize, reason: &'a str },
+    BoardCountMismatch { num_puzzles: usize, num_solutions: usize },
+    CorrectSolutions { num_correct: usize, num_puzzles: usize },
+    TraceWriteError(&'a str),
+    /// A command that reads a collection of puzzles, started without one
+    MissingCollectionFile(&'a str),
+    EngineTableHeading,
+    /// A row of the compare-engines table. Only the general engine reports the number of nodes it searched
+    EngineTiming { engine: &'a str, num_solved: usize, num_puzzles: usize, total_ms: f64, num_nodes: u64 },
+    /// An engine whose result for a puzzle, by its 1-based index, disagrees with the first engine
+    EngineMismatch { index: usize, engine: &'a str },
+    /// An engine which found another solution than the first engine for a puzzle with a unique solution
+    EngineSolutionMismatch { index: usize, engine: &'a str },
+    InvalidThreads,
+    BoardNoSolution { index: usize },
+    BoardSolveError { index: usize, error: &'a str },
+}
+
+impl Message<'_> {
+    /**
+     * The text of the message in the given locale.
+     */
+    pub fn text(&self, locale: Locale) -> String {
+        return match locale {
+            Locale::English => self.english(),
+            Locale::Dutch => self.dutch(),
+        };
+    }
+
+    fn english(&self) -> String {
+        return match self {
+            Message::BoardHeading => "Board:".to_string(),
+            Message::SolutionHeading => "Solution:".to_string(),
+            Message::NoSolutionFound => "No solution found".to_string(),
+            Message::Reason(violation) => format!("Reason: {}", violation),
+            Message::ConflictingGivens(givens) => format!("Conflicting givens (row, column, digit): {:?}", givens),
+            Message::Conflict(conflict) => format!("Conflict: {}", conflict),
+            Message::HiddenWordsHeading => "Hidden words:".to_string(),
+            Message::HiddenWord { word, line, start } => format!("{} in {}, starting at position {}", word, line, start + 1),
+            Message::NumSolutions(num_solutions) => format!("Solutions: {}", num_solutions),
+            Message::SolutionKind(num_solutions) => match num_solutions {
+                0 => "Solutions: none".to_string(),
+                1 => "Solutions: unique".to_string(),
+                _ => "Solutions: multiple".to_string(),
+            },
+            Message::NumForcedDigits(num_digits) => format!("Digits placed by propagation: {}", num_digits),
+            Message::SearchEffort { num_nodes, num_backtracks } =>
+                format!("Search effort: {} nodes, {} backtracks", num_nodes, num_backtracks),
+            Message::SearchDifficulty(difficulty) => format!("Search difficulty: {:.1}", difficulty),
+            Message::AlreadySolved => "The board is already solved".to_string(),
+            Message::InvalidFilledBoard => "The board is filled in, but it is not a valid solution".to_string(),
+            Message::ReadError(error) => format!("Error reading file: {}", error),
+            Message::SolveError(error) => format!("Error solving board: {}", error),
+            Message::CountError(error) => format!("Error counting solutions: {}", error),
+            Message::RateError(error) => format!("Error rating board: {}", error),
+            Message::InvalidSymbols(error) => format!("Invalid --symbols: {}", error),
+            Message::InvalidWords(error) => format!("Invalid --words: {}", error),
+            Message::InvalidMaxMemory => "--max-memory expects a number of megabytes".to_string(),
+            Message::InvalidEngine => "--engine expects backtracking or zdd".to_string(),
+            Message::InvalidColor => "--color expects auto, always or never".to_string(),
+            Message::InvalidLanguage => "--lang expects en or nl".to_string(),
+            Message::MissingCacheDir =>
+                "--cache needs the HOME or XDG_CACHE_HOME environment variable to find the cache directory".to_string(),
+            Message::MissingExportFormat => "export expects a format: --latex or --sdk".to_string(),
+            Message::MissingTraceFile => "--trace expects a file to write the trace to".to_string(),
+            Message::TraceWriteError(error) => format!("Error writing trace: {}", error),
+            Message::MissingVerifyFiles => "verify expects --puzzles <file> and --solutions <file>".to_string(),
+            Message::InvalidConfig(error) => format!("Error loading config: {}", error),
+            Message::InvalidPuzzle { index, error } => format!("Board {}: invalid puzzle: {}", index, error),
+            Message::InvalidSolution { index, error } => format!("Board {}: invalid solution: {}", index, error),
+            Message::SolutionMismatch { index, reason } => format!("Board {}: {}", index, reason),
+            Message::BoardCountMismatch { num_puzzles, num_solutions } =>
+                format!("The files contain {} puzzles but {} solutions", num_puzzles, num_solutions),
+            Message::CorrectSolutions { num_correct, num_puzzles } =>
+                format!("Correct solutions: {} of {}", num_correct, num_puzzles),
+            Message::MissingCollectionFile(command) => format!("{} expects a file of puzzles, one per line", command),
+            Message::EngineTableHeading => format!("{:<15} {:>9} {:>12} {:>12} {:>12}", "Engine", "Solved", "Total ms", "Average ms", "Nodes"),
+            Message::EngineTiming { engine, num_solved, num_puzzles, total_ms, num_nodes } =>
+                format_engine_timing(engine, *num_solved, *num_puzzles, *total_ms, *num_nodes),
+            Message::EngineMismatch { index, engine } =>
+                format!("Board {}: the {} engine disagrees with the first engine", index, engine),
+            Message::EngineSolutionMismatch { index, engine } =>
+                format!("Board {}: the {} engine found another solution than the first engine", index, engine),
+            Message::InvalidThreads => "--threads expects a positive number of threads".to_string(),
+            Message::BoardNoSolution { index } => format!("Board {}: no solution found", index),
+            Message::BoardSolveError { index, error } => format!("Board {}: error solving board: {}", index, error),
+        };
+    }
+
+    fn dutch(&self) -> String {
+        return match self {
+            Message::BoardHeading => "Bord:".to_string(),
+            Message::SolutionHeading => "Oplossing:".to_string(),
+            Message::NoSolutionFound => "Geen oplossing gevonden".to_string(),
+            Message::Reason(violation) => format!("Reden: {}", violation.localize(Locale::Dutch)),
+            Message::ConflictingGivens(givens) => format!("Tegenstrijdige gegeven cijfers (rij, kolom, cijfer): {:?}", givens),
+            Message::Conflict(conflict) => format!("Tegenstrijdigheid: {}", conflict.localize(Locale::Dutch)),
+            Message::HiddenWordsHeading => "Verborgen woorden:".to_string(),
+            Message::HiddenWord { word, line, start } =>
+                format!("{} in {}, beginnend op positie {}", word, line.localize(Locale::Dutch), start + 1),
+            Message::NumSolutions(num_solutions) => format!("Oplossingen: {}", num_solutions),
+            Message::SolutionKind(num_solutions) => match num_solutions {
+                0 => "Oplossingen: geen".to_string(),
+                1 => "Oplossingen: uniek".to_string(),
+                _ => "Oplossingen: meerdere".to_string(),
+            },
+            Message::NumForcedDigits(num_digits) => format!("Cijfers geplaatst door propagatie: {}", num_digits),
+            Message::SearchEffort { num_nodes, num_backtracks } =>
+                format!("Zoekinspanning: {} knopen, {} terugstappen", num_nodes, num_backtracks),
+            Message::SearchDifficulty(difficulty) => format!("Zoekmoeilijkheid: {:.1}", difficulty),
+            Message::AlreadySolved => "Het bord is al opgelost".to_string(),
+            Message::InvalidFilledBoard => "Het bord is ingevuld, maar het is geen geldige oplossing".to_string(),
+            Message::ReadError(error) => format!("Fout bij het lezen van het bestand: {}", error),
+            Message::SolveError(error) => format!("Fout bij het oplossen van het bord: {}", error),
+            Message::CountError(error) => format!("Fout bij het tellen van de oplossingen: {}", error),
+            Message::RateError(error) => format!("Fout bij het beoordelen van het bord: {}", error),
+            Message::InvalidSymbols(error) => format!("Ongeldige --symbols: {}", error),
+            Message::InvalidWords(error) => format!("Ongeldige --words: {}", error),
+            Message::InvalidMaxMemory => "--max-memory verwacht een aantal megabytes".to_string(),
+            Message::InvalidEngine => "--engine verwacht backtracking of zdd".to_string(),
+            Message::InvalidColor => "--color verwacht auto, always of never".to_string(),
+            Message::InvalidLanguage => "--lang verwacht en of nl".to_string(),
+            Message::MissingCacheDir =>
+                "--cache heeft de omgevingsvariabele HOME of XDG_CACHE_HOME nodig om de cachemap te vinden".to_string(),
+            Message::MissingExportFormat => "export verwacht een formaat: --latex of --sdk".to_string(),
+            Message::MissingTraceFile => "--trace verwacht een bestand om het spoor naar te schrijven".to_string(),
+            Message::TraceWriteError(error) => format!("Fout bij het schrijven van het spoor: {}", error),
+            Message::MissingVerifyFiles => "verify verwacht --puzzles <bestand> en --solutions <bestand>".to_string(),
+            Message::InvalidConfig(error) => format!("Fout bij het laden van de configuratie: {}", error),
+            Message::InvalidPuzzle { index, error } => format!("Bord {}: ongeldige puzzel: {}", index, error),
+            Message::InvalidSolution { index, error } => format!("Bord {}: ongeldige oplossing: {}", index, error),
+            Message::SolutionMismatch { index, reason } => format!("Bord {}: {}", index, reason),
+            Message::BoardCountMismatch { num_puzzles, num_solutions } =>
+                format!("De bestanden bevatten {} puzzels maar {} oplossingen", num_puzzles, num_solutions),
+            Message::CorrectSolutions { num_correct, num_puzzles } =>
+                format!("Juiste oplossingen: {} van {}", num_correct, num_puzzles),
+            Message::MissingCollectionFile(command) => format!("{} verwacht een bestand met puzzels, één per regel", command),
+            Message::EngineTableHeading => format!("{:<15} {:>9} {:>12} {:>12} {:>12}", "Engine", "Opgelost", "Totaal ms", "Gemiddeld ms", "Knopen"),
+            Message::EngineTiming { engine, num_solved, num_puzzles, total_ms, num_nodes } =>
+                format_engine_timing(engine, *num_solved, *num_puzzles, *total_ms, *num_nodes),
+            Message::EngineMismatch { index, engine } =>
+                format!("Bord {}: de engine {} is het niet eens met de eerste engine", index, engine),
+            Message::EngineSolutionMismatch { index, engine } =>
+                format!("Bord {}: de engine {} vond een andere oplossing dan de eerste engine", index, engine),
+            Message::InvalidThreads => "--threads verwacht een positief aantal threads".to_string(),
+            Message::BoardNoSolution { index } => format!("Bord {}: geen oplossing gevonden", index),
+            Message::BoardSolveError { index, error } => format!("Bord {}: fout bij het oplossen van het bord: {}", index, error),
+        };
+    }
+}
+
+/**
+ * A row of the table printed by compare-engines, aligned with EngineTableHeading.
+ */
+fn format_engine_timing(engine: &str, num_solved: usize, num_puzzles: usize, total_ms: f64, num_nodes: u64) -> String {
+    let average_ms = if num_puzzles == 0 { 0.0 } else { total_ms / num_puzzles as f64 };
+    return format!("{:<15} {:>9} {:>12.3} {:>12.3} {:>12}", engine, format!("{}/{}", num_solved, num_puzzles), total_ms, average_ms, num_nodes);
+}
+
+/**
+ * Values that are shown to the user and can be described in every locale. The English text is the same as the one
+ * given by Display.
+ */
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+}
+
+impl Localize for Unit {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, Unit::Row) => "rij".to_string(),
+            (Locale::Dutch, Unit::Column) => "kolom".to_string(),
+            (Locale::Dutch, Unit::Block) => "blok".to_string(),
+        };
+    }
+}
+
+impl Localize for Violation {
+    fn localize(&self, locale: Locale) -> String {
+        if locale == Locale::English {
+            return self.to_string();
+        }
+        return match self {
+            Violation::DuplicateDigit { digit, unit, idx } =>
+                format!("cijfer {} komt meer dan eens voor in {} {}", digit, unit.localize(locale), idx + 1),
+            Violation::UnplaceableDigit { digit, unit, idx } =>
+                format!("cijfer {} kan nergens in {} {} geplaatst worden", digit, unit.localize(locale), idx + 1),
+            Violation::MultipleDigitsInCell { row, col } =>
+                format!("het vak in rij {}, kolom {} heeft meer dan één cijfer", row + 1, col + 1),
+            Violation::NoDigitForCell { row, col } =>
+                format!("in het vak in rij {}, kolom {} kan geen cijfer geplaatst worden", row + 1, col + 1),
+        };
+    }
+}
+
+impl Localize for Conflict {
+    fn localize(&self, locale: Locale) -> String {
+        if locale == Locale::English {
+            return self.to_string();
+        }
+        return format!("cijfer {} komt meer dan eens voor in {} {}: in rij {}, kolom {} en rij {}, kolom {}",
+                       self.digit, self.unit.localize(locale), self.idx + 1,
+                       self.first.0 + 1, self.first.1 + 1, self.second.0 + 1, self.second.1 + 1);
+    }
+}
+
+impl Localize for WordLine {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, WordLine::Row(i)) => format!("rij {}", i + 1),
+            (Locale::Dutch, WordLine::Column(j)) => format!("kolom {}", j + 1),
+            (Locale::Dutch, WordLine::Diagonal) => "de diagonaal".to_string(),
+            (Locale::Dutch, WordLine::AntiDiagonal) => "de antidiagonaal".to_string(),
+        };
+    }
+}
+
+impl Localize for BoardReadError {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, BoardReadError::FileReadError(_)) => "het bordbestand kon niet gelezen worden".to_string(),
+            (Locale::Dutch, BoardReadError::InvalidCharacter(char)) => format!("ongeldig teken '{}' in het bord", char),
+            (Locale::Dutch, BoardReadError::InvalidSize) => "het bord moet 9 rijen van 9 vakken hebben".to_string(),
+            (Locale::Dutch, BoardReadError::InvalidValue(value)) =>
+                format!("ongeldige waarde '{}' in het bord, het moet een cijfer of leeg zijn", value),
+        };
+    }
+}
+
+impl Localize for SolutionMismatch {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, SolutionMismatch::ChangedGiven { row, col, given, digit }) =>
+                format!("het gegeven cijfer {} in rij {}, kolom {} is veranderd in {}", given, row + 1, col + 1, digit),
+            (Locale::Dutch, SolutionMismatch::EmptyCell { row, col }) =>
+                format!("het vak in rij {}, kolom {} is leeg", row + 1, col + 1),
+            (Locale::Dutch, SolutionMismatch::BrokenRule(violation)) => violation.localize(locale),
+        };
+    }
+}
+
+impl Localize for DigitSymbolsError {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, DigitSymbolsError::WrongNumberOfSymbols(num_symbols)) =>
+                format!("9 symbolen verwacht maar {} gekregen", num_symbols),
+            (Locale::Dutch, DigitSymbolsError::DuplicateSymbol(symbol)) =>
+                format!("symbool '{}' wordt voor meer dan één cijfer gebruikt", symbol),
+            (Locale::Dutch, DigitSymbolsError::ReservedSymbol(symbol)) =>
+                format!("symbool '{}' is gereserveerd voor lege vakken en scheidingstekens", symbol),
+        };
+    }
+}
+
+impl Localize for SolveError {
+    fn localize(&self, locale: Locale) -> String {
+        return match (locale, self) {
+            (Locale::English, _) => self.to_string(),
+            (Locale::Dutch, SolveError::InvalidProblem(_)) =>
+                "het bord kon niet als exact cover-probleem gecodeerd worden".to_string(),
+            (Locale::Dutch, SolveError::SearchAborted(_)) => "het zoeken naar een oplossing is afgebroken".to_string(),
+            (Locale::Dutch, SolveError::Cache(_)) => "de oplossingscache kon niet gebruikt worden".to_string(),
+            (Locale::Dutch, SolveError::ConflictingGivens { first, second, reason }) => format!(
+                "de gegeven cijfers in rij {}, kolom {} en rij {}, kolom {} zijn tegenstrijdig: {}",
+                first.0 + 1, first.1 + 1, second.0 + 1, second.1 + 1, reason.localize(locale)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("nl"), Some(Locale::Dutch));
+        assert_eq!(Locale::from_tag("nl_BE.UTF-8"), Some(Locale::Dutch));
+        assert_eq!(Locale::from_tag("en-GB"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("C"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("fr_FR"), None);
+    }
+
+    #[test]
+    fn test_english_violation_matches_display() {
+        let violation = Violation::UnplaceableDigit { digit: 1, unit: Unit::Block, idx: 4 };
+
+        assert_eq!(violation.localize(Locale::English), "digit 1 cannot be placed in block 5");
+        assert_eq!(violation.localize(Locale::English), violation.to_string());
+    }
+
+    #[test]
+    fn test_dutch_violation() {
+        let violation = Violation::DuplicateDigit { digit: 5, unit: Unit::Row, idx: 0 };
+
+        assert_eq!(Message::Reason(&violation).text(Locale::Dutch), "Reden: cijfer 5 komt meer dan eens voor in rij 1");
+    }
+
+    #[test]
+    fn test_dutch_conflict() {
+        let conflict = Conflict { digit: 7, unit: Unit::Column, idx: 3, first: (1, 3), second: (6, 3) };
+
+        assert_eq!(
+            Message::Conflict(&conflict).text(Locale::Dutch),
+            "Tegenstrijdigheid: cijfer 7 komt meer dan eens voor in kolom 4: in rij 2, kolom 4 en rij 7, kolom 4"
+        );
+    }
+
+    #[test]
+    fn test_dutch_solve_error() {
+        let error = SolveError::ConflictingGivens {
+            first: (0, 0, 5),
+            second: (0, 2, 5),
+            reason: Violation::DuplicateDigit { digit: 5, unit: Unit::Row, idx: 0 },
+        };
+
+        assert_eq!(
+            error.localize(Locale::Dutch),
+            "de gegeven cijfers in rij 1, kolom 1 en rij 1, kolom 3 zijn tegenstrijdig: cijfer 5 komt meer dan eens voor in rij 1"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt::Write;
+
+use crate::sudoku::{Board, BoardReadError, FormatError};
+
+/**
+ * A board together with the metadata that puzzle collections keep about it, as stored in the SadMan Sudoku (.sdk)
+ * format used by many Sudoku apps.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Puzzle {
+    pub board: Board,
+    pub metadata: PuzzleMetadata,
+}
+
+/**
+ * The header lines of an .sdk file, which start with '#' and a letter for the kind of information. All of them are
+ * optional.
+ */
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct PuzzleMetadata {
+    /// Who made the puzzle (#A)
+    pub author: Option<String>,
+    /// A description of the puzzle (#D)
+    pub description: Option<String>,
+    /// A free-form comment (#C)
+    pub comment: Option<String>,
+    /// When the puzzle was published (#B)
+    pub date: Option<String>,
+    /// Where the puzzle comes from (#S)
+    pub source: Option<String>,
+    /// The difficulty level, e.g. "Hard" (#L)
+    pub difficulty: Option<String>,
+    /// The website of the source (#U)
+    pub url: Option<String>,
+    /// Header lines with other letters, kept so that they survive a round trip
+    pub other: Vec<(char, String)>,
+}
+
+impl PuzzleMetadata {
+    /**
+     * The field of the header line with the given letter, if it is one of the known ones.
+     */
+    fn field_mut(&mut self, code: char) -> Option<&mut Option<String>> {
+        return match code {
+            'A' => Some(&mut self.author),
+            'D' => Some(&mut self.description),
+            'C' => Some(&mut self.comment),
+            'B' => Some(&mut self.date),
+            'S' => Some(&mut self.source),
+            'L' => Some(&mut self.difficulty),
+            'U' => Some(&mut self.url),
+            _ => None,
+        };
+    }
+
+    /**
+     * The known header lines as (letter, value), in the order they are written.
+     */
+    fn fields(&self) -> [(char, &Option<String>); 7] {
+        return [
+            ('A', &self.author),
+            ('D', &self.description),
+            ('C', &self.comment),
+            ('B', &self.date),
+            ('S', &self.source),
+            ('L', &self.difficulty),
+            ('U', &self.url),
+        ];
+    }
+}
+
+impl Puzzle {
+    /**
+     * Create a puzzle without metadata.
+     */
+    pub fn new(board: Board) -> Puzzle {
+        return Puzzle { board, metadata: PuzzleMetadata::default() };
+    }
+
+    /**
+     * Read a puzzle from an .sdk file.
+     */
+    pub fn read_from_sdk_file(filepath: &str) -> Result<Puzzle, BoardReadError> {
+        let contents = std::fs::read_to_string(filepath)?;
+        return Self::read_from_sdk_str(&contents);
+    }
+
+    /**
+     * Read a puzzle in the .sdk format from a string: header lines such as "#A Author", followed by the board as
+     * 9 lines of 9 cells in which '.' is an empty cell.
+     */
+    pub fn read_from_sdk_str(s: &str) -> Result<Puzzle, BoardReadError> {
+        let mut metadata = PuzzleMetadata::default();
+        let mut board_lines: Vec<&str> = Vec::new();
+        for line in s.lines() {
+            let line = line.trim_end_matches('\r');
+            let Some(header) = line.strip_prefix('#') else {
+                board_lines.push(line);
+                continue;
+            };
+            let mut chars = header.chars();
+            let Some(code) = chars.next() else {
+                continue;
+            };
+            let value = chars.as_str().trim().to_string();
+            match metadata.field_mut(code) {
+                Some(field) => *field = Some(value),
+                None => metadata.other.push((code, value)),
+            }
+        }
+        let board = Board::read_from_str(&board_lines.join("\n"))?;
+        return Ok(Puzzle { board, metadata });
+    }
+
+    /**
+     * Format the puzzle in the .sdk format, with the header lines of the metadata that is set. Fails if a header
+     * contains a line break, since the rest of it would be read back as part of the board.
+     */
+    pub fn format_as_sdk(&self) -> Result<String, FormatError> {
+        let known_fields = self.metadata.fields().into_iter().filter_map(|(code, value)| Some((code, value.as_ref()?)));
+        let other_fields = self.metadata.other.iter().map(|(code, value)| (*code, value));
+        let mut out = String::new();
+        for (code, value) in known_fields.chain(other_fields) {
+            if code == '\n' || value.contains('\n') {
+                return Err(FormatError::LineBreakInMetadata(code));
+            }
+            writeln!(out, "#{}{}", code, value).unwrap();
+        }
+        let line = self.board.to_line();
+        for row in 0..9 {
+            out.push_str(&line[row * 9..row * 9 + 9]);
+            out.push('\n');
+        }
+        return Ok(out);
+    }
+
+    /**
+     * Write the puzzle to an .sdk file.
+     */
+    pub fn write_to_sdk_file(&self, filepath: &str) -> Result<(), FormatError> {
+        std::fs::write(filepath, self.format_as_sdk()?)?;
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_board1() -> Board {
+        return "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79".parse().unwrap();
+    }
+
+    #[test]
+    fn test_read_from_sdk_file() {
+        let puzzle = Puzzle::read_from_sdk_file("data/sudoku.sdk").unwrap();
+
+        assert_eq!(puzzle.board, get_board1());
+        assert_eq!(puzzle.metadata.author, Some("Wikipedia".to_string()));
+        assert_eq!(puzzle.metadata.difficulty, Some("Easy".to_string()));
+        assert_eq!(puzzle.metadata.comment, Some("The example puzzle of the Sudoku article".to_string()));
+    }
+
+    #[test]
+    fn test_format_as_sdk() {
+        let mut puzzle = Puzzle::new(get_board1());
+        puzzle.metadata.author = Some("Wikipedia".to_string());
+        puzzle.metadata.other.push(('X', "extension".to_string()));
+
+        let formatted = puzzle.format_as_sdk().unwrap();
+
+        assert!(formatted.starts_with("#AWikipedia\n#Xextension\n53..7....\n6..195...\n"));
+    }
+
+    #[test]
+    fn test_sdk_round_trip() {
+        let puzzle = Puzzle::read_from_sdk_file("data/sudoku.sdk").unwrap();
+
+        let read_back = Puzzle::read_from_sdk_str(&puzzle.format_as_sdk().unwrap());
+
+        assert_eq!(read_back.unwrap(), puzzle);
+    }
+
+    #[test]
+    fn test_format_as_sdk_with_line_break() {
+        let mut puzzle = Puzzle::new(get_board1());
+        puzzle.metadata.comment = Some("first line\nsecond line".to_string());
+
+        let result = puzzle.format_as_sdk();
+
+        assert!(matches!(result, Err(FormatError::LineBreakInMetadata('C'))));
+        assert!(matches!(puzzle.write_to_sdk_file("/nonexistent/puzzle.sdk"), Err(FormatError::LineBreakInMetadata('C'))));
+    }
+
+    #[test]
+    fn test_read_from_sdk_str_without_board() {
+        let result = Puzzle::read_from_sdk_str("#AWikipedia\n");
+
+        assert!(matches!(result, Err(BoardReadError::InvalidSize)));
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroU8;
+use std::str::FromStr;
+
+use exact_cover::{ExactCoverProblem, ExactCoverSolution, InvalidProblemError};
+use thiserror::Error;
+
+use crate::sudoku::{Board, SolveError};
+
+/// The number of rows and columns of the board that the five grids are laid out on
+const SIZE: usize = 21;
+
+/// The top left cell of each grid: the four corners and then the middle, which shares a box with each corner grid
+const GRID_OFFSETS: [(usize, usize); 5] = [(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)];
+
+// SamuraiError is a custom error type for errors that occur when reading or changing a Samurai Sudoku.
+#[derive(Debug, Error)]
+pub enum SamuraiError {
+    #[error("could not read the board file")]
+    FileReadError(#[from] std::io::Error),
+    #[error("invalid character '{0}' in the board")]
+    InvalidCharacter(char),
+    #[error("the board must have 21 rows with the cells of the grids that cross them")]
+    InvalidSize,
+    #[error("row {row}, column {col} is not in one of the grids")]
+    InvalidCell { row: usize, col: usize },
+    #[error("invalid digit {0}, it must be between 1 and 9 or 0 for an empty cell")]
+    InvalidDigit(u8),
+}
+
+/**
+ * A Samurai Sudoku: five 9x9 grids on a 21x21 board, one in every corner and one in the middle whose corner boxes are
+ * the inner corner boxes of the others. Every grid follows the rules of Sudoku, so a digit in a shared box counts for
+ * two grids. Cells are addressed by their row and column on the whole board, of which the cells outside the grids,
+ * like the ones between the top two grids, are not used.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct SamuraiBoard(Vec<Vec<u8>>);
+
+impl SamuraiBoard {
+    /**
+     * Create a Samurai board without digits.
+     */
+    pub fn empty() -> SamuraiBoard {
+        return SamuraiBoard(vec![vec![0; SIZE]; SIZE]);
+    }
+
+    /**
+     * Read a Samurai board from a file, see read_from_str.
+     */
+    pub fn read_from_file(filepath: &str) -> Result<SamuraiBoard, SamuraiError> {
+        let contents = std::fs::read_to_string(filepath)?;
+        return SamuraiBoard::read_from_str(&contents);
+    }
+
+    /**
+     * Read a Samurai board with a line for each of the 21 rows, in which '.' or '0' is an empty cell and spaces and
+     * empty lines are ignored. A line only has the cells of the grids that cross its row, from left to right: 18 for
+     * the rows of only the corner grids, 9 for the rows of only the middle grid and 21 for the rows of all three. The
+     * layout of Display, which leaves the unused cells blank, can be read back.
+     */
+    pub fn read_from_str(s: &str) -> Result<SamuraiBoard, SamuraiError> {
+        let mut board = SamuraiBoard::empty();
+        let mut i = 0;
+        for line in s.lines() {
+            let chars: Vec<char> = line.chars().filter(|char| !char.is_whitespace()).collect();
+            if chars.is_empty() {
+                continue;
+            }
+            let cols: Vec<usize> = (0..SIZE).filter(|j| i < SIZE && is_in_grid(i, *j)).collect();
+            if chars.len() != cols.len() {
+                return Err(SamuraiError::InvalidSize);
+            }
+            for (j, char) in cols.into_iter().zip(chars) {
+                board.0[i][j] = match char {
+                    '.' | '0' => 0,
+                    '1'..='9' => char.to_digit(10).unwrap() as u8,
+                    _ => return Err(SamuraiError::InvalidCharacter(char)),
+                };
+            }
+            i += 1;
+        }
+        if i < SIZE {
+            return Err(SamuraiError::InvalidSize);
+        }
+        return Ok(board);
+    }
+
+    /**
+     * The digit in the cell at row i and column j of the whole board, or None if the cell is empty or not in a grid.
+     */
+    pub fn get(&self, i: usize, j: usize) -> Option<NonZeroU8> {
+        return NonZeroU8::new(self.0[i][j]);
+    }
+
+    /**
+     * Put a digit in the cell at row i and column j of the whole board, or empty the cell with 0.
+     */
+    pub fn set(&mut self, i: usize, j: usize, digit: u8) -> Result<(), SamuraiError> {
+        if i >= SIZE || j >= SIZE || !is_in_grid(i, j) {
+            return Err(SamuraiError::InvalidCell { row: i, col: j });
+        }
+        if digit > 9 {
+            return Err(SamuraiError::InvalidDigit(digit));
+        }
+        self.0[i][j] = digit;
+        Ok(())
+    }
+
+    /**
+     * One of the five grids as a board: 0 to 4 are the top left, top right, middle, bottom left and bottom right grid.
+     */
+    pub fn grid(&self, idx: usize) -> Board {
+        let (row_offset, col_offset) = GRID_OFFSETS[idx];
+        let mut board = Board::empty();
+        for i in 0..9 {
+            for j in 0..9 {
+                if let Some(digit) = self.get(row_offset + i, col_offset + j) {
+                    board.set(i, j, digit.get()).unwrap();
+                }
+            }
+        }
+        return board;
+    }
+
+    /**
+     * Whether every cell of every grid contains a digit.
+     */
+    pub fn is_filled(&self) -> bool {
+        return (0..GRID_OFFSETS.len()).all(|idx| self.grid(idx).is_filled());
+    }
+
+    /**
+     * Whether every grid is a valid Sudoku solution.
+     */
+    pub fn is_valid_solution(&self) -> bool {
+        return (0..GRID_OFFSETS.len()).all(|idx| self.grid(idx).is_valid_solution());
+    }
+}
+
+/**
+ * Whether the cell at row i and column j of the whole board is in at least one grid.
+ */
+fn is_in_grid(i: usize, j: usize) -> bool {
+    return GRID_OFFSETS.iter().any(|(row_offset, col_offset)| {
+        (*row_offset..row_offset + 9).contains(&i) && (*col_offset..col_offset + 9).contains(&j)
+    });
+}
+
+impl FromStr for SamuraiBoard {
+    type Err = SamuraiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return SamuraiBoard::read_from_str(s);
+    }
+}
+
+impl Display for SamuraiBoard {
+    /**
+     * Write the board like Board does, with a line per row, the boxes of a row separated by spaces and the bands of
+     * boxes separated by empty lines. Cells outside the grids are blank, and lines end at their last cell.
+     */
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for i in 0..SIZE {
+            let mut line = String::new();
+            for j in 0..SIZE {
+                line.push(match self.0[i][j] {
+                    _ if !is_in_grid(i, j) => ' ',
+                    0 => '.',
+                    digit => char::from(b'0' + digit),
+                });
+                if j % 3 == 2 && j < SIZE - 1 {
+                    line.push(' ');
+                }
+            }
+            writeln!(f, "{}", line.trim_end())?;
+            if i % 3 == 2 && i < SIZE - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Convert a Samurai board to one exact cover problem for all five grids. Every grid has its own items for the digits
+ * in its rows and columns, as a row of the whole board crosses two grids that each need all digits in it. The items
+ * for cells and for the digits in boxes are named by their position on the whole board instead, so the grids that
+ * share a box share those items, and one option per digit in a cell covers the items of every grid the cell is in.
+ */
+fn convert_to_exact_cover_problem(board: &SamuraiBoard) -> Result<ExactCoverProblem, InvalidProblemError> {
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+    let mut required_options: Vec<String> = Vec::new();
+    let mut payloads: HashMap<String, (u8, u8, u8)> = HashMap::new();
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            if !is_in_grid(i, j) {
+                continue;
+            }
+            for digit in 1..=9u8 {
+                let option_name = format!("r{}c{}d{}", i, j, digit);
+                let mut item_names = vec![format!("r{}c{}", i, j), format!("b{}.{}d{}", i / 3, j / 3, digit)];
+                for (idx, (row_offset, col_offset)) in GRID_OFFSETS.iter().enumerate() {
+                    if (*row_offset..row_offset + 9).contains(&i) && (*col_offset..col_offset + 9).contains(&j) {
+                        item_names.push(format!("g{}r{}d{}", idx, i - row_offset, digit));
+                        item_names.push(format!("g{}c{}d{}", idx, j - col_offset, digit));
+                    }
+                }
+                for item_name in item_names {
+                    covered_by.entry(item_name).or_default().push(option_name.clone());
+                }
+                if board.0[i][j] == digit {
+                    required_options.push(option_name.clone());
+                }
+                payloads.insert(option_name, (i as u8, j as u8, digit));
+            }
+        }
+    }
+    let mut required_items: Vec<String> = covered_by.keys().cloned().collect();
+    required_items.sort();
+
+    return ExactCoverProblem::new(required_items, vec![], required_options, covered_by)?.with_option_payloads(payloads);
+}
+
+/**
+ * Convert a solution of the exact cover problem back to a board, using the (row, col, digit) payload of each option.
+ */
+fn convert_to_samurai_board(exact_cover_problem: &ExactCoverProblem, solution: &ExactCoverSolution) -> SamuraiBoard {
+    let mut board = SamuraiBoard::empty();
+    for (row, col, digit) in exact_cover_problem.solution_payloads::<(u8, u8, u8)>(solution) {
+        board.0[*row as usize][*col as usize] = *digit;
+    }
+    return board;
+}
+
+/**
+ * Solve a Samurai Sudoku with exact cover. Returns None if it has no solution, including when its givens conflict.
+ * With few givens the search can run into long dead ends, so a board without givens may take minutes to fill.
+ */
+pub fn solve_samurai_sudoku(board: &SamuraiBoard) -> Result<Option<SamuraiBoard>, SolveError> {
+    let exact_cover_problem = match convert_to_exact_cover_problem(board) {
+        Err(InvalidProblemError::RequiredOptionConflict { .. }) => return Ok(None),
+        result => result?,
+    };
+
+    let solution = exact_cover_problem.solve();
+
+    Ok(solution.map(|solution| convert_to_samurai_board(&exact_cover_problem, &solution)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_read_and_display() {
+        let text = std::fs::read_to_string("data/samurai.txt").unwrap();
+
+        let board = SamuraiBoard::read_from_str(&text).unwrap();
+
+        assert_eq!(board.get(0, 0).unwrap().get(), 1);
+        assert_eq!(board.get(9, 5), None);
+        assert_eq!(board.get(9, 8).unwrap().get(), 1);
+        assert_eq!(board.to_string(), text);
+    }
+
+    #[test]
+    fn test_read_without_spaces() {
+        let text = std::fs::read_to_string("data/samurai.txt").unwrap();
+        let compact: String = text.lines().map(|line| line.replace(' ', "") + "\n").collect();
+
+        assert_eq!(SamuraiBoard::read_from_str(&compact).unwrap(), SamuraiBoard::read_from_str(&text).unwrap());
+    }
+
+    #[rstest]
+    #[case("... ... ...\n".repeat(21), "the board must have 21 rows with the cells of the grids that cross them")]
+    #[case(SamuraiBoard::empty().to_string().replacen('.', "x", 1), "invalid character 'x' in the board")]
+    #[case(SamuraiBoard::empty().to_string() + "...", "the board must have 21 rows with the cells of the grids that cross them")]
+    fn test_read_invalid(#[case] s: String, #[case] expected_error: &str) {
+        assert_eq!(SamuraiBoard::read_from_str(&s).unwrap_err().to_string(), expected_error);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut board = SamuraiBoard::empty();
+
+        board.set(8, 8, 5).unwrap();
+
+        assert_eq!(board.grid(0).get(8, 8).unwrap().get(), 5);
+        assert_eq!(board.grid(2).get(2, 2).unwrap().get(), 5);
+        assert!(matches!(board.set(0, 9, 1), Err(SamuraiError::InvalidCell { row: 0, col: 9 })));
+        assert!(matches!(board.set(0, 0, 10), Err(SamuraiError::InvalidDigit(10))));
+    }
+
+    #[test]
+    fn test_solve_samurai_sudoku() {
+        let board = SamuraiBoard::read_from_file("data/samurai.txt").unwrap();
+
+        let solution = solve_samurai_sudoku(&board).unwrap().unwrap();
+
+        assert!(solution.is_valid_solution());
+        for i in 0..SIZE {
+            for j in 0..SIZE {
+                assert!(board.get(i, j).is_none() || board.get(i, j) == solution.get(i, j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_samurai_sudoku_with_conflict_in_shared_box() {
+        let mut board = SamuraiBoard::empty();
+        // The middle grid's top left box is the bottom right box of the top left grid, so these share a box
+        board.set(6, 6, 3).unwrap();
+        board.set(8, 8, 3).unwrap();
+
+        assert_eq!(solve_samurai_sudoku(&board).unwrap(), None);
+    }
+}
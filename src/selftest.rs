@@ -0,0 +1,100 @@
+use rust_sudoku::nqueens::{NQueensProblem, count_all_nqueens_solutions_with_exact_cover};
+use rust_sudoku::sudoku::{Board, solve_sudoku_with_exact_cover};
+
+/// Fixtures compiled into the binary, so the self-test doesn't depend on the data directory being present
+const SOLVABLE_FIXTURES: [(&str, &str); 7] = [
+    ("sudoku.txt", include_str!("../data/sudoku.txt")),
+    ("sudoku_easy.txt", include_str!("../data/sudoku_easy.txt")),
+    ("sudoku_medium.txt", include_str!("../data/sudoku_medium.txt")),
+    ("sudoku_hard.txt", include_str!("../data/sudoku_hard.txt")),
+    ("sudoku_evil.txt", include_str!("../data/sudoku_evil.txt")),
+    ("sudoku_hardest.txt", include_str!("../data/sudoku_hardest.txt")),
+    ("sudoku_ai_escargot.txt", include_str!("../data/sudoku_ai_escargot.txt")),
+];
+const UNSOLVABLE_FIXTURE: (&str, &str) = ("sudoku_unsolvable.txt", include_str!("../data/sudoku_unsolvable.txt"));
+
+/// The number of solutions of the n queens problem for n = 1 to 8
+const NQUEENS_COUNTS: [u64; 8] = [1, 0, 0, 2, 10, 4, 40, 92];
+
+/**
+ * Run a quick battery of checks against the solver and print a line per check. Returns whether all checks passed.
+ */
+pub fn run_selftest() -> bool {
+    let mut num_passed = 0;
+    let mut num_failed = 0;
+    let mut report = |name: String, result: Result<(), String>| {
+        match result {
+            Ok(()) => {
+                println!("ok      {}", name);
+                num_passed += 1;
+            }
+            Err(reason) => {
+                println!("FAILED  {}: {}", name, reason);
+                num_failed += 1;
+            }
+        }
+    };
+
+    for (filename, contents) in SOLVABLE_FIXTURES {
+        report(format!("solve {}", filename), check_solvable(contents));
+    }
+    report(format!("solve {}", UNSOLVABLE_FIXTURE.0), check_unsolvable(UNSOLVABLE_FIXTURE.1));
+    for (n, expected_count) in (1..).zip(NQUEENS_COUNTS) {
+        report(format!("count {} queens solutions", n), check_nqueens_count(n, expected_count));
+    }
+    for (filename, contents) in SOLVABLE_FIXTURES {
+        report(format!("round-trip {}", filename), check_round_trip(contents));
+    }
+
+    println!();
+    println!("{} passed, {} failed", num_passed, num_failed);
+    return num_failed == 0;
+}
+
+/**
+ * Check that the board has a solution which is valid and keeps all givens.
+ */
+fn check_solvable(contents: &str) -> Result<(), String> {
+    let board = Board::read_from_str(contents).map_err(|e| e.to_string())?;
+    let solution = solve_sudoku_with_exact_cover(&board).map_err(|e| e.to_string())?
+        .ok_or("no solution found")?;
+
+    // A filled board is only returned as its own solution if it is valid
+    if solve_sudoku_with_exact_cover(&solution).map_err(|e| e.to_string())? != Some(solution.clone()) {
+        return Err("the solution is not valid".to_string());
+    }
+    let keeps_givens = board.to_string().chars().zip(solution.to_string().chars())
+        .all(|(given, digit)| given == '.' || given == digit);
+    if !keeps_givens {
+        return Err("the solution doesn't keep the givens".to_string());
+    }
+    Ok(())
+}
+
+fn check_unsolvable(contents: &str) -> Result<(), String> {
+    let board = Board::read_from_str(contents).map_err(|e| e.to_string())?;
+    return match solve_sudoku_with_exact_cover(&board).map_err(|e| e.to_string())? {
+        Some(_) => Err("found a solution to an unsolvable board".to_string()),
+        None => Ok(()),
+    };
+}
+
+fn check_nqueens_count(n: u16, expected_count: u64) -> Result<(), String> {
+    let count = count_all_nqueens_solutions_with_exact_cover(&NQueensProblem::new(n));
+    if count != expected_count {
+        return Err(format!("expected {} solutions, found {}", expected_count, count));
+    }
+    Ok(())
+}
+
+/**
+ * Check that printing a board and reading it back gives the same board.
+ */
+fn check_round_trip(contents: &str) -> Result<(), String> {
+    let board = Board::read_from_str(contents).map_err(|e| e.to_string())?;
+    let read_back = Board::read_from_str(&board.to_string()).map_err(|e| e.to_string())?;
+    if read_back != board {
+        return Err("the board read back differs from the original".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use std::error::Error;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::sudoku::{Board, BoardReadError, SolveError};
+
+impl Serialize for Board {
+    /**
+     * Serialize the board as a single line of all 81 cells, like Board::to_line.
+     */
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&self.to_line());
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    /**
+     * Deserialize a board from a line of 81 cells or 9 lines of 9 cells, validating it like Board::read_from_str.
+     */
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        return Board::read_from_str(&s).map_err(serde::de::Error::custom);
+    }
+}
+
+/**
+ * Serialize an error as its kind, e.g. "invalid_size", and a message which includes the errors that caused it. Errors
+ * can't be deserialized, since their causes such as IO errors can't be reconstructed.
+ */
+fn serialize_error<S: Serializer>(serializer: S, kind: &str, error: &dyn Error) -> Result<S::Ok, S::Error> {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(e) = source {
+        message.push_str(&format!(": {}", e));
+        source = e.source();
+    }
+    let mut state = serializer.serialize_struct("Error", 2)?;
+    state.serialize_field("kind", kind)?;
+    state.serialize_field("message", &message)?;
+    return state.end();
+}
+
+impl Serialize for BoardReadError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let kind = match self {
+            BoardReadError::FileReadError(_) => "file_read_error",
+            BoardReadError::InvalidCharacter(_) => "invalid_character",
+            BoardReadError::InvalidSize => "invalid_size",
+            BoardReadError::InvalidValue(_) => "invalid_value",
+        };
+        return serialize_error(serializer, kind, self);
+    }
+}
+
+impl Serialize for SolveError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let kind = match self {
+            SolveError::InvalidProblem(_) => "invalid_problem",
+            SolveError::SearchAborted(_) => "search_aborted",
+            SolveError::Cache(_) => "cache",
+            SolveError::ConflictingGivens { .. } => "conflicting_givens",
+        };
+        return serialize_error(serializer, kind, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sudoku::{solve_sudoku, SolutionMismatch, SolveResult, Unit, Violation};
+
+    use super::*;
+
+    fn get_board1() -> Board {
+        return "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79".parse().unwrap();
+    }
+
+    #[test]
+    fn test_serialize_board() {
+        let json = serde_json::to_string(&get_board1()).unwrap();
+
+        assert_eq!(json, "\"53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79\"");
+    }
+
+    #[test]
+    fn test_deserialize_invalid_board() {
+        let result = serde_json::from_str::<Board>("\"53..7\"");
+
+        assert!(result.unwrap_err().to_string().contains("9 rows of 9 cells"));
+    }
+
+    #[test]
+    fn test_solve_result_round_trip() {
+        let result = solve_sudoku(&get_board1()).unwrap();
+
+        let json = serde_json::to_string(&result).unwrap();
+        let read_back: SolveResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read_back, result);
+    }
+
+    #[test]
+    fn test_solution_mismatch_round_trip() {
+        let mismatch = SolutionMismatch::BrokenRule(Violation::DuplicateDigit { digit: 5, unit: Unit::Row, idx: 0 });
+
+        let json = serde_json::to_string(&mismatch).unwrap();
+        let read_back: SolutionMismatch = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read_back, mismatch);
+    }
+
+    #[test]
+    fn test_serialize_board_read_error() {
+        let error = Board::read_from_file("data/does_not_exist.txt").unwrap_err();
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(json["kind"], "file_read_error");
+        assert!(json["message"].as_str().unwrap().starts_with("could not read the board file: "));
+    }
+}
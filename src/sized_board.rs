@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroU8;
+use std::str::FromStr;
+
+use exact_cover::{ExactCoverProblem, ExactCoverSolution, InvalidProblemError};
+use thiserror::Error;
+
+use crate::sudoku::{format_with_box_drawing, Board, SolveError};
+
+/// The symbols of the digits 1 to 25: 1 to 9 and then letters, so a 16x16 board uses 1-9 and A-G
+const SYMBOLS: &str = "123456789ABCDEFGHIJKLMNOP";
+
+/// The largest board that can be written with the symbols
+const MAX_SIZE: usize = 25;
+
+// SizedBoardError is a custom error type for errors that occur when creating, reading or changing a sized board.
+#[derive(Debug, PartialEq, Error)]
+pub enum SizedBoardError {
+    #[error("boxes of {rows}x{cols} cells are not supported, they must have at least 2 rows and columns and at most 25 cells")]
+    UnsupportedBoxShape { rows: usize, cols: usize },
+    #[error("a board can't have {0} cells, it must be square with a side of at most 25 that is not a prime")]
+    InvalidSize(usize),
+    #[error("invalid character '{0}' in the board")]
+    InvalidCharacter(char),
+    #[error("index {0} is out of range for the board")]
+    InvalidIndex(usize),
+    #[error("invalid digit {0} for the board")]
+    InvalidDigit(u8),
+}
+
+/**
+ * A Sudoku board with boxes of any shape, square like 4x4 with 2x2 boxes, 9x9, 16x16 and 25x25, or rectangular like
+ * 6x6 with boxes of 2 rows by 3 columns and 12x12 with 3x4 boxes. The board has as many rows and columns as a box has
+ * cells. Digits go up to the size of the board and are written as 1 to 9 followed by letters. Board is the 9x9 board
+ * that the rest of the crate works with, and converts to and from a sized board.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct SizedBoard {
+    box_rows: usize,
+    box_cols: usize,
+    cells: Vec<Vec<u8>>,
+}
+
+impl SizedBoard {
+    /**
+     * Create a board without digits with boxes of box_rows by box_cols cells.
+     */
+    pub fn empty(box_rows: usize, box_cols: usize) -> Result<SizedBoard, SizedBoardError> {
+        if box_rows < 2 || box_cols < 2 || box_rows * box_cols > MAX_SIZE {
+            return Err(SizedBoardError::UnsupportedBoxShape { rows: box_rows, cols: box_cols });
+        }
+        let size = box_rows * box_cols;
+        return Ok(SizedBoard { box_rows, box_cols, cells: vec![vec![0; size]; size] });
+    }
+
+    /**
+     * Read a board from a string of all its cells, in which '.' or '0' is an empty cell and whitespace is ignored, so
+     * the cells can be written on one line or on a line per row. The size of the board follows from the number of
+     * cells, and letters may be lowercase. The boxes are as square as possible and wider than high, e.g. 3x3 for a 9x9
+     * board and 2x3 for a 6x6 board; use read_from_str_with_boxes for other shapes.
+     */
+    pub fn read_from_str(s: &str) -> Result<SizedBoard, SizedBoardError> {
+        let num_cells = s.chars().filter(|char| !char.is_whitespace()).count();
+        let size = (1..=MAX_SIZE).find(|size| size * size == num_cells).ok_or(SizedBoardError::InvalidSize(num_cells))?;
+        let box_rows = (2..=size).rev().find(|rows| rows * rows <= size && size % rows == 0)
+            .ok_or(SizedBoardError::InvalidSize(num_cells))?;
+        return SizedBoard::read_from_str_with_boxes(s, box_rows, size / box_rows);
+    }
+
+    /**
+     * Read a board like read_from_str, but with boxes of box_rows by box_cols cells.
+     */
+    pub fn read_from_str_with_boxes(s: &str, box_rows: usize, box_cols: usize) -> Result<SizedBoard, SizedBoardError> {
+        let mut board = SizedBoard::empty(box_rows, box_cols)?;
+        let size = board.size();
+        let chars: Vec<char> = s.chars().filter(|char| !char.is_whitespace()).collect();
+        if chars.len() != size * size {
+            return Err(SizedBoardError::InvalidSize(chars.len()));
+        }
+        for (idx, char) in chars.into_iter().enumerate() {
+            board.cells[idx / size][idx % size] = match char {
+                '.' | '0' => 0,
+                _ => match SYMBOLS[..size].find(char.to_ascii_uppercase()) {
+                    Some(pos) => pos as u8 + 1,
+                    None => return Err(SizedBoardError::InvalidCharacter(char)),
+                },
+            };
+        }
+        return Ok(board);
+    }
+
+    /**
+     * The number of rows of a box.
+     */
+    pub fn box_rows(&self) -> usize {
+        return self.box_rows;
+    }
+
+    /**
+     * The number of columns of a box.
+     */
+    pub fn box_cols(&self) -> usize {
+        return self.box_cols;
+    }
+
+    /**
+     * The number of rows and columns of the board, which is also the largest digit.
+     */
+    pub fn size(&self) -> usize {
+        return self.cells.len();
+    }
+
+    /**
+     * The digit in the cell at row i and column j, or None if the cell is empty.
+     */
+    pub fn get(&self, i: usize, j: usize) -> Option<NonZeroU8> {
+        return NonZeroU8::new(self.cells[i][j]);
+    }
+
+    /**
+     * Put a digit in the cell at row i and column j, or empty the cell with 0.
+     */
+    pub fn set(&mut self, i: usize, j: usize, digit: u8) -> Result<(), SizedBoardError> {
+        if let Some(idx) = [i, j].into_iter().find(|idx| *idx >= self.size()) {
+            return Err(SizedBoardError::InvalidIndex(idx));
+        }
+        if digit as usize > self.size() {
+            return Err(SizedBoardError::InvalidDigit(digit));
+        }
+        self.cells[i][j] = digit;
+        Ok(())
+    }
+
+    /**
+     * Whether every cell of the board contains a digit.
+     */
+    pub fn is_filled(&self) -> bool {
+        return self.cells.iter().all(|row| row.iter().all(|cell| *cell != 0));
+    }
+
+    /**
+     * The index of the box of the cell at row i and column j, counting the boxes in reading order. A band of boxes
+     * across the board has box_rows boxes, as each box is box_cols wide.
+     */
+    fn box_of(&self, i: usize, j: usize) -> usize {
+        return i / self.box_rows * self.box_rows + j / self.box_cols;
+    }
+
+    /**
+     * Whether the board is filled and every row, column and box contains each digit exactly once.
+     */
+    pub fn is_valid_solution(&self) -> bool {
+        if !self.is_filled() {
+            return false;
+        }
+        let size = self.size();
+        let mut seen = vec![vec![false; size + 1]; 3 * size];
+        for i in 0..size {
+            for j in 0..size {
+                let digit = self.cells[i][j] as usize;
+                for unit in [i, size + j, 2 * size + self.box_of(i, j)] {
+                    if seen[unit][digit] {
+                        return false;
+                    }
+                    seen[unit][digit] = true;
+                }
+            }
+        }
+        return true;
+    }
+
+    /**
+     * Format the board with box-drawing characters like Board::to_pretty_string, which keeps the boxes apart on large
+     * boards. Empty cells are blank.
+     */
+    pub fn to_pretty_string(&self) -> String {
+        let cells: Vec<Vec<char>> = self.cells.iter()
+            .map(|row| row.iter().map(|digit| if *digit == 0 { ' ' } else { SYMBOLS.as_bytes()[*digit as usize - 1] as char }).collect())
+            .collect();
+        return format_with_box_drawing(&cells, self.box_rows, self.box_cols);
+    }
+}
+
+impl FromStr for SizedBoard {
+    type Err = SizedBoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return SizedBoard::read_from_str(s);
+    }
+}
+
+impl From<&Board> for SizedBoard {
+    fn from(board: &Board) -> SizedBoard {
+        let cells = (0..9)
+            .map(|i| (0..9).map(|j| board.get(i, j).map_or(0, NonZeroU8::get)).collect())
+            .collect();
+        return SizedBoard { box_rows: 3, box_cols: 3, cells };
+    }
+}
+
+impl TryFrom<&SizedBoard> for Board {
+    type Error = SizedBoardError;
+
+    fn try_from(board: &SizedBoard) -> Result<Board, SizedBoardError> {
+        if board.box_rows != 3 || board.box_cols != 3 {
+            return Err(SizedBoardError::InvalidSize(board.size() * board.size()));
+        }
+        let mut result = Board::empty();
+        for (i, row) in board.cells.iter().enumerate() {
+            for (j, digit) in row.iter().enumerate() {
+                result.set(i, j, *digit).unwrap();
+            }
+        }
+        return Ok(result);
+    }
+}
+
+impl Display for SizedBoard {
+    /**
+     * Write the board like Board does, with a line per row, the boxes of a row separated by spaces and the bands of
+     * boxes separated by empty lines.
+     */
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let size = self.size();
+        for (i, row) in self.cells.iter().enumerate() {
+            for (j, digit) in row.iter().enumerate() {
+                match digit {
+                    0 => write!(f, ".")?,
+                    _ => write!(f, "{}", &SYMBOLS[*digit as usize - 1..*digit as usize])?,
+                }
+                if j % self.box_cols == self.box_cols - 1 && j < size - 1 {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+            if i % self.box_rows == self.box_rows - 1 && i < size - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Convert a board to an exact cover problem like a 9x9 board: an item for every cell and for every digit in every row,
+ * column and box, so size * size * 4 items, and an option for every digit in every cell, of which the givens are
+ * required.
+ */
+fn convert_to_exact_cover_problem(board: &SizedBoard) -> Result<ExactCoverProblem, InvalidProblemError> {
+    let size = board.size();
+    let mut required_items: Vec<String> = Vec::new();
+    for i in 0..size {
+        for j in 0..size {
+            required_items.push(format!("r{}c{}", i, j));
+        }
+    }
+    for unit in ["r", "c", "b"] {
+        for idx in 0..size {
+            for digit in 1..=size {
+                required_items.push(format!("{}{}d{}", unit, idx, digit));
+            }
+        }
+    }
+
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+    let mut required_options: Vec<String> = Vec::new();
+    let mut payloads: HashMap<String, (u8, u8, u8)> = HashMap::new();
+    for i in 0..size {
+        for j in 0..size {
+            for digit in 1..=size as u8 {
+                let option_name = format!("r{}c{}d{}", i, j, digit);
+                for item_name in [
+                    format!("r{}c{}", i, j),
+                    format!("r{}d{}", i, digit),
+                    format!("c{}d{}", j, digit),
+                    format!("b{}d{}", board.box_of(i, j), digit),
+                ] {
+                    covered_by.entry(item_name).or_default().push(option_name.clone());
+                }
+                if board.cells[i][j] == digit {
+                    required_options.push(option_name.clone());
+                }
+                payloads.insert(option_name, (i as u8, j as u8, digit));
+            }
+        }
+    }
+
+    return ExactCoverProblem::new(required_items, vec![], required_options, covered_by)?.with_option_payloads(payloads);
+}
+
+/**
+ * Convert a solution of the exact cover problem back to a board, using the (row, col, digit) payload of each option.
+ */
+fn convert_to_sized_board(board: &SizedBoard, exact_cover_problem: &ExactCoverProblem, solution: &ExactCoverSolution) -> SizedBoard {
+    let mut solved = board.clone();
+    for (row, col, digit) in exact_cover_problem.solution_payloads::<(u8, u8, u8)>(solution) {
+        solved.cells[*row as usize][*col as usize] = *digit;
+    }
+    return solved;
+}
+
+/**
+ * Solve a board of any size with exact cover. Returns None if the board has no solution, including when its givens
+ * conflict.
+ */
+pub fn solve_sized_sudoku_with_exact_cover(board: &SizedBoard) -> Result<Option<SizedBoard>, SolveError> {
+    let exact_cover_problem = match convert_to_exact_cover_problem(board) {
+        Err(InvalidProblemError::RequiredOptionConflict { .. }) => return Ok(None),
+        result => result?,
+    };
+
+    let solution = exact_cover_problem.solve();
+
+    Ok(solution.map(|solution| convert_to_sized_board(board, &exact_cover_problem, &solution)))
+}
+
+/**
+ * Count the solutions of a board of any size, stopping once the limit is reached if one is given.
+ */
+pub fn count_sized_sudoku_solutions(board: &SizedBoard, limit: Option<u64>) -> Result<u64, SolveError> {
+    let exact_cover_problem = match convert_to_exact_cover_problem(board) {
+        Err(InvalidProblemError::RequiredOptionConflict { .. }) => return Ok(0),
+        result => result?,
+    };
+
+    Ok(exact_cover_problem.count_solutions_up_to(limit.unwrap_or(u64::MAX)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::sudoku::solve_sudoku_with_exact_cover;
+
+    use super::*;
+
+    #[test]
+    fn test_to_pretty_string() {
+        let mut board = SizedBoard::empty(2, 3).unwrap();
+        board.set(0, 0, 6).unwrap();
+        board.set(5, 5, 1).unwrap();
+
+        let expected = "\
+┌───────┬───────┐
+│ 6     │       │
+│       │       │
+├───────┼───────┤
+│       │       │
+│       │       │
+├───────┼───────┤
+│       │       │
+│       │     1 │
+└───────┴───────┘
+";
+        assert_eq!(board.to_pretty_string(), expected);
+    }
+
+    #[test]
+    fn test_read_and_display() {
+        let text = std::fs::read_to_string("data/sudoku_16x16.txt").unwrap();
+
+        let board = SizedBoard::read_from_str(&text).unwrap();
+
+        assert_eq!((board.box_rows(), board.box_cols()), (4, 4));
+        assert_eq!(board.size(), 16);
+        assert_eq!(board.get(0, 0).unwrap().get(), 2);
+        assert_eq!(board.get(0, 1).unwrap().get(), 10);
+        assert_eq!(board.get(0, 2), None);
+        assert_eq!(board.to_string(), text);
+        assert_eq!(text.to_lowercase().parse::<SizedBoard>().unwrap(), board);
+    }
+
+    #[rstest]
+    #[case("1234", SizedBoardError::InvalidSize(4))]
+    #[case(&"1".repeat(50), SizedBoardError::InvalidSize(50))]
+    #[case(&"1".repeat(49), SizedBoardError::InvalidSize(49))]
+    #[case(&format!("5{}", ".".repeat(15)), SizedBoardError::InvalidCharacter('5'))]
+    #[case(&format!("H{}", ".".repeat(255)), SizedBoardError::InvalidCharacter('H'))]
+    fn test_read_invalid(#[case] s: &str, #[case] expected_error: SizedBoardError) {
+        assert_eq!(SizedBoard::read_from_str(s), Err(expected_error));
+    }
+
+    #[test]
+    fn test_rectangular_boxes() {
+        let text = std::fs::read_to_string("data/sudoku_6x6.txt").unwrap();
+
+        let board = SizedBoard::read_from_str(&text).unwrap();
+        let tall = SizedBoard::read_from_str_with_boxes(&text, 3, 2).unwrap();
+
+        assert_eq!((board.box_rows(), board.box_cols()), (2, 3));
+        assert_eq!(board.to_string(), text);
+        assert_eq!(tall.to_string().lines().next(), Some("3. .. 1."));
+        assert_eq!(board.box_of(1, 3), 1);
+        assert_eq!(board.box_of(2, 2), 2);
+        assert_eq!(tall.box_of(2, 2), 1);
+        assert_eq!(tall.box_of(3, 0), 3);
+        assert_eq!(SizedBoard::read_from_str(&".".repeat(144)).unwrap().box_cols(), 4);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut board = SizedBoard::empty(2, 2).unwrap();
+
+        assert_eq!(board.set(3, 3, 4), Ok(()));
+        assert_eq!(board.set(4, 0, 1), Err(SizedBoardError::InvalidIndex(4)));
+        assert_eq!(board.set(0, 0, 5), Err(SizedBoardError::InvalidDigit(5)));
+        assert_eq!(board.get(3, 3).unwrap().get(), 4);
+        assert_eq!(SizedBoard::empty(6, 6), Err(SizedBoardError::UnsupportedBoxShape { rows: 6, cols: 6 }));
+        assert_eq!(SizedBoard::empty(1, 9), Err(SizedBoardError::UnsupportedBoxShape { rows: 1, cols: 9 }));
+    }
+
+    #[rstest]
+    #[case("1... ..3. .... ...4")]
+    #[case(&std::fs::read_to_string("data/sudoku_16x16.txt").unwrap())]
+    #[case(&".".repeat(625))]
+    #[case(&std::fs::read_to_string("data/sudoku_6x6.txt").unwrap())]
+    #[case(&".".repeat(144))]
+    fn test_solve_sized_sudoku(#[case] s: &str) {
+        let board = SizedBoard::read_from_str(s).unwrap();
+
+        let solution = solve_sized_sudoku_with_exact_cover(&board).unwrap().unwrap();
+
+        assert!(solution.is_valid_solution());
+        for i in 0..board.size() {
+            for j in 0..board.size() {
+                assert!(board.get(i, j).is_none() || board.get(i, j) == solution.get(i, j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_sized_sudoku_9x9() {
+        let board = Board::read_from_file("data/sudoku.txt").unwrap();
+
+        let solution = solve_sized_sudoku_with_exact_cover(&SizedBoard::from(&board)).unwrap().unwrap();
+
+        assert_eq!(SizedBoard::from(&board).to_string(), board.to_string());
+        assert_eq!(Board::try_from(&solution).ok(), solve_sudoku_with_exact_cover(&board).unwrap());
+    }
+
+    #[test]
+    fn test_solve_sized_sudoku_conflicting_givens() {
+        let board = SizedBoard::read_from_str("11.. .... .... ....").unwrap();
+
+        assert_eq!(solve_sized_sudoku_with_exact_cover(&board).unwrap(), None);
+        assert_eq!(count_sized_sudoku_solutions(&board, None).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_count_sized_sudoku_solutions() {
+        let board = SizedBoard::empty(2, 2).unwrap();
+
+        assert_eq!(count_sized_sudoku_solutions(&board, None).unwrap(), 288);
+        assert_eq!(count_sized_sudoku_solutions(&board, Some(10)).unwrap(), 10);
+    }
+}
@@ -0,0 +1,3278 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::num::NonZeroU8;
+use std::ops::ControlFlow;
+use std::str::FromStr;
+
+use exact_cover::{CacheError, Contradiction, CountMethod, EncodingIssue, ExactCoverProblem, ExactCoverSolution, InvalidProblemError, Observer, SearchError, SearchStats, SolutionCache, SolutionMultiplicity, SolverBackend};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cell_set::CellSet;
+use crate::formatter::BoardFormatter;
+use crate::logic::{solve_logically, Step, Technique};
+use crate::transformation::{Transformation, TransformationError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Board(Vec<Vec<u8>>);
+
+// BoardReadError is a custom error type for errors that occur when reading a board from a file.
+#[derive(Debug, Error)]
+pub enum BoardReadError {
+    #[error("could not read the board file")]
+    FileReadError(#[from] std::io::Error),
+    #[error("invalid character '{0}' in the board")]
+    InvalidCharacter(char),
+    #[error("the board must have 9 rows of 9 cells")]
+    InvalidSize,
+    #[error("invalid value '{0}' in the board, it must be a digit or empty")]
+    InvalidValue(String),
+}
+
+// FormatError is a custom error type for errors that occur when writing a board or puzzle to a file.
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error("could not write the file")]
+    FileWriteError(#[from] std::io::Error),
+    #[error("the #{0} header contains a line break, which the .sdk format can't store")]
+    LineBreakInMetadata(char),
+}
+
+// BoardEditError is a custom error type for errors that occur when changing the cells of a board.
+#[derive(Debug, PartialEq, Error)]
+pub enum BoardEditError {
+    #[error("index {0} is out of range, it must be between 0 and 8")]
+    InvalidIndex(usize),
+    #[error("invalid digit {0}, it must be between 1 and 9 or 0 for an empty cell")]
+    InvalidDigit(u8),
+    #[error("digit {0} appears more than once")]
+    DuplicateDigit(u8),
+}
+
+// BoardError is a custom error type for digits that don't make up a board.
+#[derive(Debug, PartialEq, Error)]
+pub enum BoardError {
+    #[error("the board must have 9 rows of 9 cells")]
+    InvalidSize,
+    #[error("invalid digit {digit} in row {}, column {}, it must be between 1 and 9 or 0 for an empty cell",
+            .row + 1, .col + 1)]
+    InvalidDigit { row: u8, col: u8, digit: u8 },
+}
+
+// DigitSymbolsError is a custom error type for digit symbol mappings that can't be used to read and write boards.
+#[derive(Debug, PartialEq, Error)]
+pub enum DigitSymbolsError {
+    #[error("expected 9 symbols but got {0}")]
+    WrongNumberOfSymbols(usize),
+    #[error("symbol '{0}' is used for more than one digit")]
+    DuplicateSymbol(char),
+    #[error("symbol '{0}' is reserved for empty cells and separators")]
+    ReservedSymbol(char),
+}
+
+// SolveError is a custom error type for errors that occur when solving a board.
+#[derive(Debug, Error)]
+pub enum SolveError {
+    #[error("the board could not be encoded as an exact cover problem")]
+    InvalidProblem(#[from] InvalidProblemError),
+    #[error("the search for a solution was aborted")]
+    SearchAborted(#[from] SearchError),
+    #[error("the solution cache could not be used")]
+    Cache(#[from] CacheError),
+    /// Two givens as (row, col, digit) that can't both be part of a solution, e.g. the same digit twice in a row
+    #[error("the givens in row {}, column {} and row {}, column {} conflict: {reason}",
+            .first.0 + 1, .first.1 + 1, .second.0 + 1, .second.1 + 1)]
+    ConflictingGivens {
+        first: (u8, u8, u8),
+        second: (u8, u8, u8),
+        reason: Violation,
+    },
+}
+
+// SolutionMismatch is a custom error type for boards that are not a solution of the puzzle they are checked against.
+#[derive(Debug, PartialEq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SolutionMismatch {
+    /// A given of the puzzle at (row, col) has a different digit in the solution
+    #[error("the given {given} in row {}, column {} is changed to {digit}", .row + 1, .col + 1)]
+    ChangedGiven { row: u8, col: u8, given: u8, digit: u8 },
+    /// The cell at (row, col) of the solution is empty
+    #[error("the cell in row {}, column {} is empty", .row + 1, .col + 1)]
+    EmptyCell { row: u8, col: u8 },
+    /// The solution breaks a rule of Sudoku
+    #[error("{0}")]
+    BrokenRule(Violation),
+}
+
+/**
+ * The symbols used to write the digits 1 to 9 when reading and printing boards, e.g. letters for Wordoku puzzles.
+ * Empty cells are always written as '.'.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct DigitSymbols([char; 9]);
+
+impl DigitSymbols {
+    /// The digits 1 to 9 themselves
+    pub const DIGITS: DigitSymbols = DigitSymbols(['1', '2', '3', '4', '5', '6', '7', '8', '9']);
+    /// The digits 0 to 8, as used by zero-based notations
+    pub const ZERO_BASED: DigitSymbols = DigitSymbols(['0', '1', '2', '3', '4', '5', '6', '7', '8']);
+    /// The letters A to I
+    pub const LETTERS: DigitSymbols = DigitSymbols(['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I']);
+
+    /**
+     * Create a mapping from the symbols for the digits 1 to 9, in order, e.g. "ABCDEFGHI".
+     */
+    pub fn new(symbols: &str) -> Result<DigitSymbols, DigitSymbolsError> {
+        let symbols: Vec<char> = symbols.chars().collect();
+        if symbols.len() != 9 {
+            return Err(DigitSymbolsError::WrongNumberOfSymbols(symbols.len()));
+        }
+        for (idx, symbol) in symbols.iter().enumerate() {
+            if *symbol == '.' || symbol.is_whitespace() {
+                return Err(DigitSymbolsError::ReservedSymbol(*symbol));
+            }
+            if symbols[..idx].contains(symbol) {
+                return Err(DigitSymbolsError::DuplicateSymbol(*symbol));
+            }
+        }
+        return Ok(DigitSymbols(symbols.try_into().unwrap()));
+    }
+
+    /**
+     * The symbol of a digit from 1 to 9.
+     */
+    pub fn symbol(&self, digit: u8) -> char {
+        return self.0[digit as usize - 1];
+    }
+
+    /**
+     * The digit a symbol stands for, if any.
+     */
+    pub fn digit(&self, symbol: char) -> Option<u8> {
+        return self.0.iter().position(|other_symbol| *other_symbol == symbol).map(|idx| idx as u8 + 1);
+    }
+}
+
+impl Default for DigitSymbols {
+    fn default() -> Self {
+        return DigitSymbols::DIGITS;
+    }
+}
+
+impl Board {
+    /**
+     * Create a board of empty cells.
+     */
+    pub fn empty() -> Board {
+        return Board(vec![vec![0; 9]; 9]);
+    }
+
+    /**
+     * Create a board from 9 rows of 9 digits, using 0 for empty cells. The digits are checked to be in range, but not
+     * whether they follow the rules of Sudoku.
+     */
+    pub fn try_from_rows(rows: Vec<Vec<u8>>) -> Result<Board, BoardError> {
+        if rows.len() != 9 || rows.iter().any(|row| row.len() != 9) {
+            return Err(BoardError::InvalidSize);
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if let Some(j) = row.iter().position(|digit| *digit > 9) {
+                return Err(BoardError::InvalidDigit { row: i as u8, col: j as u8, digit: row[j] });
+            }
+        }
+        return Ok(Board(rows));
+    }
+
+    pub fn read_from_file(filepath: &str) -> Result<Self, BoardReadError> {
+        return Self::read_from_file_with_symbols(filepath, &DigitSymbols::DIGITS);
+    }
+
+    /**
+     * Read a board from a file in which the digits are written with the given symbols.
+     */
+    pub fn read_from_file_with_symbols(filepath: &str, symbols: &DigitSymbols) -> Result<Self, BoardReadError> {
+        let file = File::open(filepath)?;
+        return Self::read_from_reader_with_symbols(BufReader::new(file), symbols);
+    }
+
+    /**
+     * Read a board in the same format as read_from_file from a reader, e.g. standard input.
+     */
+    pub fn read_from_reader(reader: impl BufRead) -> Result<Self, BoardReadError> {
+        return Self::read_from_reader_with_symbols(reader, &DigitSymbols::DIGITS);
+    }
+
+    /**
+     * Read a board from a reader in which the digits are written with the given symbols.
+     */
+    pub fn read_from_reader_with_symbols(reader: impl BufRead, symbols: &DigitSymbols) -> Result<Self, BoardReadError> {
+        return Self::read_from_lines(reader.lines(), symbols);
+    }
+
+    /**
+     * Read a board in the same format as read_from_file from a string.
+     */
+    pub fn read_from_str(s: &str) -> Result<Self, BoardReadError> {
+        return Self::read_from_str_with_symbols(s, &DigitSymbols::DIGITS);
+    }
+
+    /**
+     * Read a board from a string in which the digits are written with the given symbols.
+     */
+    pub fn read_from_str_with_symbols(s: &str, symbols: &DigitSymbols) -> Result<Self, BoardReadError> {
+        return Self::read_from_lines(s.lines().map(|line| Ok(line.to_string())), symbols);
+    }
+
+    /**
+     * Read a board from lines of 9 cells each, or from a single line of all 81 cells, in which '.' is an empty cell and
+     * spaces and empty lines are ignored. A '0' is an empty cell as well, unless it is one of the digit symbols.
+     */
+    fn read_from_lines(lines: impl Iterator<Item=std::io::Result<String>>, symbols: &DigitSymbols) -> Result<Self, BoardReadError> {
+        let mut vecs = vec![vec![0; 9]; 9];
+        let mut i = 0;
+        for result in lines {
+            let s = result?;
+            if s.is_empty() {
+                continue;
+            }
+
+            let mut digits: Vec<u8> = Vec::new();
+            for char in s.chars() {
+                if char == ' ' {
+                    continue;
+                }
+                let digit = match symbols.digit(char) {
+                    Some(digit) => digit,
+                    None if char == '.' || char == '0' => 0,
+                    None => return Err(BoardReadError::InvalidCharacter(char)),
+                };
+                digits.push(digit);
+            }
+            if i == 0 && digits.len() == 81 {
+                for (row, row_digits) in vecs.iter_mut().zip(digits.chunks(9)) {
+                    row.copy_from_slice(row_digits);
+                }
+                i = 9;
+                continue;
+            }
+            if i >= 9 || digits.len() != 9 {
+                return Err(BoardReadError::InvalidSize);
+            }
+            vecs[i].copy_from_slice(&digits);
+
+            i += 1;
+        }
+        if i < 9 {
+            return Err(BoardReadError::InvalidSize);
+        }
+
+        let board = Board(vecs);
+        Ok(board)
+    }
+
+    /**
+     * Set all cells of row i (0-8) from left to right. Use 0 for empty cells.
+     */
+    pub fn set_row(&mut self, i: usize, digits: [u8; 9]) -> Result<(), BoardEditError> {
+        Self::validate_unit(i, &digits)?;
+        for (j, digit) in digits.into_iter().enumerate() {
+            self.0[i][j] = digit;
+        }
+        Ok(())
+    }
+
+    /**
+     * Set all cells of column j (0-8) from top to bottom. Use 0 for empty cells.
+     */
+    pub fn set_col(&mut self, j: usize, digits: [u8; 9]) -> Result<(), BoardEditError> {
+        Self::validate_unit(j, &digits)?;
+        for (i, digit) in digits.into_iter().enumerate() {
+            self.0[i][j] = digit;
+        }
+        Ok(())
+    }
+
+    /**
+     * Set all cells of block b (0-8, numbered left to right and top to bottom) in reading order. Use 0 for empty cells.
+     */
+    pub fn set_box(&mut self, b: usize, digits: [u8; 9]) -> Result<(), BoardEditError> {
+        Self::validate_unit(b, &digits)?;
+        for (k, digit) in digits.into_iter().enumerate() {
+            self.0[b / 3 * 3 + k / 3][b % 3 * 3 + k % 3] = digit;
+        }
+        Ok(())
+    }
+
+    /**
+     * Check that a row, column or block index is in range and that its digits are valid and different.
+     */
+    fn validate_unit(idx: usize, digits: &[u8; 9]) -> Result<(), BoardEditError> {
+        if idx >= 9 {
+            return Err(BoardEditError::InvalidIndex(idx));
+        }
+        let mut seen_digits = 0u16;
+        for &digit in digits.iter() {
+            if digit > 9 {
+                return Err(BoardEditError::InvalidDigit(digit));
+            }
+            if digit != 0 && seen_digits & (1 << digit) != 0 {
+                return Err(BoardEditError::DuplicateDigit(digit));
+            }
+            seen_digits |= 1 << digit;
+        }
+        Ok(())
+    }
+
+    /**
+     * The digit in the cell at row i and column j (0-8), or None if the cell is empty or out of range.
+     */
+    pub fn get(&self, i: usize, j: usize) -> Option<NonZeroU8> {
+        return self.0.get(i).and_then(|row| row.get(j)).and_then(|digit| NonZeroU8::new(*digit));
+    }
+
+    /**
+     * Put a digit (1-9) in the cell at row i and column j (0-8), replacing any digit that was there.
+     */
+    pub fn set(&mut self, i: usize, j: usize, digit: u8) -> Result<(), BoardEditError> {
+        Self::validate_cell(i, j)?;
+        if !(1..=9).contains(&digit) {
+            return Err(BoardEditError::InvalidDigit(digit));
+        }
+        self.0[i][j] = digit;
+        Ok(())
+    }
+
+    /**
+     * Empty the cell at row i and column j (0-8).
+     */
+    pub fn clear(&mut self, i: usize, j: usize) -> Result<(), BoardEditError> {
+        Self::validate_cell(i, j)?;
+        self.0[i][j] = 0;
+        Ok(())
+    }
+
+    /**
+     * All cells as (row, column, digit) in reading order, with None for empty cells.
+     */
+    pub fn iter_cells(&self) -> impl Iterator<Item=(usize, usize, Option<NonZeroU8>)> + '_ {
+        return self.0.iter().enumerate()
+            .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, digit)| (i, j, NonZeroU8::new(*digit))));
+    }
+
+    /**
+     * Check that the row and column index of a cell are in range.
+     */
+    fn validate_cell(i: usize, j: usize) -> Result<(), BoardEditError> {
+        if i >= 9 {
+            return Err(BoardEditError::InvalidIndex(i));
+        }
+        if j >= 9 {
+            return Err(BoardEditError::InvalidIndex(j));
+        }
+        Ok(())
+    }
+
+    /**
+     * The digit in the cell at row i and column j, or 0 if it is empty.
+     */
+    pub(crate) fn cell(&self, i: usize, j: usize) -> u8 {
+        return self.0[i][j];
+    }
+
+    /**
+     * Create a board from the digits of its cells, given as a function of the row and column (0-8), with 0 for empty
+     * cells. The digits must be valid.
+     */
+    pub(crate) fn from_fn(digit: impl Fn(usize, usize) -> u8) -> Board {
+        return Board((0..9).map(|i| (0..9).map(|j| digit(i, j)).collect()).collect());
+    }
+
+    /**
+     * Check that no digit appears more than once in a row, column or block, which would make the board unsolvable.
+     * Returns every conflict, by row, column and block, with each repeated digit paired with its first occurrence in
+     * the unit.
+     */
+    pub fn validate(&self) -> Result<(), Vec<Conflict>> {
+        let mut conflicts: Vec<Conflict> = Vec::new();
+        for unit in [Unit::Row, Unit::Column, Unit::Block] {
+            for idx in 0..9 {
+                let mut first_cells: [Option<(u8, u8)>; 10] = [None; 10];
+                for k in 0..9 {
+                    let (i, j) = match unit {
+                        Unit::Row => (idx, k),
+                        Unit::Column => (k, idx),
+                        Unit::Block => (idx / 3 * 3 + k / 3, idx % 3 * 3 + k % 3),
+                    };
+                    let digit = self.0[i][j];
+                    if digit == 0 {
+                        continue;
+                    }
+                    match first_cells[digit as usize] {
+                        Some(first) => conflicts.push(Conflict { digit, unit, idx: idx as u8, first, second: (i as u8, j as u8) }),
+                        None => first_cells[digit as usize] = Some((i as u8, j as u8)),
+                    }
+                }
+            }
+        }
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        return Err(conflicts);
+    }
+
+    /**
+     * Whether every cell of the board contains a digit.
+     */
+    pub fn is_filled(&self) -> bool {
+        return self.0.iter().all(|row| row.iter().all(|cell| *cell != 0));
+    }
+
+    /**
+     * The pencil marks of the board: the digit of every filled cell, and every digit that isn't in the same row, column
+     * or block yet for every empty cell. Filled cells that share their digit with another cell of a unit are left without
+     * candidates, so CandidateGrid::contradiction reports them.
+     */
+    pub fn candidates(&self) -> CandidateGrid {
+        let mut candidates = CandidateGrid::full();
+        for (i, j, digit) in self.iter_cells() {
+            if let Some(digit) = digit {
+                candidates.restrict(i, j, &[digit.get()]).unwrap();
+            }
+        }
+        for (i, j, digit) in self.iter_cells() {
+            if let Some(digit) = digit {
+                candidates.remove_from_peers(i, j, digit.get());
+            }
+        }
+        return candidates;
+    }
+
+    /**
+     * The cells that don't contain a digit yet.
+     */
+    pub fn empty_cells(&self) -> CellSet {
+        return !self.filled_cells();
+    }
+
+    /**
+     * The cells that contain a digit.
+     */
+    pub fn filled_cells(&self) -> CellSet {
+        return (0..9).flat_map(|i| (0..9).map(move |j| (i, j)))
+            .filter(|(i, j)| self.0[*i][*j] != 0)
+            .collect();
+    }
+
+    /**
+     * Whether the board is filled and every row, column and block contains each digit exactly once.
+     */
+    pub fn is_valid_solution(&self) -> bool {
+        for i in 0..9 {
+            let mut row_digits = 0u16;
+            let mut col_digits = 0u16;
+            let mut block_digits = 0u16;
+            for j in 0..9 {
+                row_digits |= 1 << self.0[i][j];
+                col_digits |= 1 << self.0[j][i];
+                block_digits |= 1 << self.0[i / 3 * 3 + j / 3][i % 3 * 3 + j % 3];
+            }
+            if row_digits != ALL_DIGITS || col_digits != ALL_DIGITS || block_digits != ALL_DIGITS {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+/**
+ * Explanation of why a board has no solution.
+ */
+#[derive(Debug, PartialEq)]
+pub struct UnsolvableExplanation {
+    /// A minimal set of givens as (row, col, digit) which together already make the board unsolvable
+    pub givens: Vec<(u8, u8, u8)>,
+    /// The violated rule, e.g. "digit 5 cannot be placed in row 3", if it can be pinpointed to one constraint
+    pub reason: Option<Violation>,
+}
+
+/**
+ * A row, column or block of the board.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Unit {
+    Row,
+    Column,
+    Block,
+}
+
+/**
+ * A rule of Sudoku that is violated by the givens of a board. Indices are 0-based like everywhere else, but rows,
+ * columns and blocks are numbered from 1 to 9 when displayed.
+ */
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Violation {
+    /// The digit appears more than once in the unit with the given index
+    DuplicateDigit { digit: u8, unit: Unit, idx: u8 },
+    /// The digit can't be placed in any cell of the unit with the given index
+    UnplaceableDigit { digit: u8, unit: Unit, idx: u8 },
+    /// The cell at (row, col) has more than one digit
+    MultipleDigitsInCell { row: u8, col: u8 },
+    /// No digit can be placed in the cell at (row, col)
+    NoDigitForCell { row: u8, col: u8 },
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Unit::Row => write!(f, "row"),
+            Unit::Column => write!(f, "column"),
+            Unit::Block => write!(f, "block"),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Violation::DuplicateDigit { digit, unit, idx } =>
+                write!(f, "digit {} appears more than once in {} {}", digit, unit, idx + 1),
+            Violation::UnplaceableDigit { digit, unit, idx } =>
+                write!(f, "digit {} cannot be placed in {} {}", digit, unit, idx + 1),
+            Violation::MultipleDigitsInCell { row, col } =>
+                write!(f, "the cell in row {}, column {} has more than one digit", row + 1, col + 1),
+            Violation::NoDigitForCell { row, col } =>
+                write!(f, "no digit can be placed in the cell in row {}, column {}", row + 1, col + 1),
+        }
+    }
+}
+
+/**
+ * Two givens with the same digit in a row, column or block, which make a board unsolvable.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Conflict {
+    /// The digit of both givens
+    pub digit: u8,
+    /// The kind of unit both givens are in
+    pub unit: Unit,
+    /// The index of the unit (0-8)
+    pub idx: u8,
+    /// The first given as (row, col) in reading order
+    pub first: (u8, u8),
+    /// The second given as (row, col) in reading order
+    pub second: (u8, u8),
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "digit {} appears more than once in {} {}: in row {}, column {} and row {}, column {}",
+               self.digit, self.unit, self.idx + 1, self.first.0 + 1, self.first.1 + 1, self.second.0 + 1, self.second.1 + 1)
+    }
+}
+
+/**
+ * Pencil marks for a board: the set of digits that are still allowed in each cell.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct CandidateGrid(Vec<Vec<u16>>);
+
+/// ANSI escape code for the color of the givens in format_with_givens
+const GIVEN_COLOR: &str = "\x1b[1;34m";
+
+/// ANSI escape code for the color of the digits filled in by the solver in format_with_givens
+const FILLED_COLOR: &str = "\x1b[32m";
+
+/// ANSI escape code that resets the color
+const RESET_COLOR: &str = "\x1b[0m";
+
+/// Bit mask with a bit set for each of the digits 1 to 9
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+impl CandidateGrid {
+    /**
+     * Create a candidate grid in which every digit is allowed in every cell.
+     */
+    pub fn full() -> CandidateGrid {
+        return CandidateGrid(vec![vec![ALL_DIGITS; 9]; 9]);
+    }
+
+    /**
+     * Read the candidates of a Sukaku (pencil mark Sudoku) from a file, see read_from_str.
+     */
+    pub fn read_from_file(filepath: &str) -> Result<CandidateGrid, BoardReadError> {
+        let contents = std::fs::read_to_string(filepath)?;
+        return CandidateGrid::read_from_str(&contents);
+    }
+
+    /**
+     * Read the candidates of a Sukaku, a puzzle given as the candidates of every cell instead of givens, in reading
+     * order. The candidates are either 81 lists of digits separated by whitespace, e.g. "125 7 3469 ...", or 729
+     * characters in which every 9 characters are a cell with the digit or a '.' or '0' at each position, like
+     * "1.3......". In the lists '.' and '0' are ignored as well, so both formats can be written with spaces between
+     * cells.
+     */
+    pub fn read_from_str(s: &str) -> Result<CandidateGrid, BoardReadError> {
+        let mut cells: Vec<String> = s.split_whitespace().map(String::from).collect();
+        if cells.len() != 81 {
+            let chars: Vec<char> = s.chars().filter(|char| !char.is_whitespace()).collect();
+            if chars.len() != 729 {
+                return Err(BoardReadError::InvalidSize);
+            }
+            cells = chars.chunks(9).map(|cell| cell.iter().collect()).collect();
+        }
+
+        let mut candidates = CandidateGrid::full();
+        for (idx, cell) in cells.iter().enumerate() {
+            let mut digits: Vec<u8> = Vec::new();
+            for char in cell.chars() {
+                match char {
+                    '1'..='9' => digits.push(char.to_digit(10).unwrap() as u8),
+                    '.' | '0' => {}
+                    _ => return Err(BoardReadError::InvalidCharacter(char)),
+                }
+            }
+            candidates.restrict(idx / 9, idx % 9, &digits).unwrap();
+        }
+        return Ok(candidates);
+    }
+
+    /**
+     * Whether the digit is allowed in the cell. A cell outside the board or a digit other than 1 to 9 is never allowed.
+     */
+    pub fn contains(&self, row: usize, col: usize, digit: u8) -> bool {
+        let Some(mask) = self.0.get(row).and_then(|cells| cells.get(col)) else {
+            return false;
+        };
+        return (1..=9).contains(&digit) && mask & (1 << digit) != 0;
+    }
+
+    /**
+     * Disallow the digit (1-9) in the cell at row and col (0-8).
+     */
+    pub fn remove(&mut self, row: usize, col: usize, digit: u8) -> Result<(), BoardEditError> {
+        Self::validate_candidate(row, col, digit)?;
+        self.0[row][col] &= !(1 << digit);
+        Ok(())
+    }
+
+    /**
+     * Only allow the given digits (1-9) in the cell at row and col (0-8).
+     */
+    pub fn restrict(&mut self, row: usize, col: usize, digits: &[u8]) -> Result<(), BoardEditError> {
+        Board::validate_cell(row, col)?;
+        if let Some(digit) = digits.iter().find(|digit| !(1..=9).contains(*digit)) {
+            return Err(BoardEditError::InvalidDigit(*digit));
+        }
+        self.0[row][col] = digits.iter().fold(0, |mask, digit| mask | (1 << digit));
+        Ok(())
+    }
+
+    /**
+     * The digits that are allowed in the cell, in increasing order.
+     */
+    pub fn digits(&self, row: usize, col: usize) -> Vec<u8> {
+        return (1..10).filter(|digit| self.contains(row, col, *digit)).collect();
+    }
+
+    /**
+     * Place the digit (1-9) in the cell at row and col (0-8): only allow the digit in the cell, and disallow it in the
+     * other cells of the same row, column and block.
+     */
+    pub fn place(&mut self, row: usize, col: usize, digit: u8) -> Result<(), BoardEditError> {
+        Self::validate_candidate(row, col, digit)?;
+        self.remove_from_peers(row, col, digit);
+        self.0[row][col] = 1 << digit;
+        Ok(())
+    }
+
+    /**
+     * Disallow the digit in the other cells of the same row, column and block as the cell. The cell and digit must be
+     * valid.
+     */
+    fn remove_from_peers(&mut self, row: usize, col: usize, digit: u8) {
+        for k in 0..9 {
+            let block_cell = (row / 3 * 3 + k / 3, col / 3 * 3 + k % 3);
+            for (i, j) in [(row, k), (k, col), block_cell] {
+                if (i, j) != (row, col) {
+                    self.0[i][j] &= !(1 << digit);
+                }
+            }
+        }
+    }
+
+    /**
+     * Check that the cell is on the board and the digit is between 1 and 9.
+     */
+    fn validate_candidate(row: usize, col: usize, digit: u8) -> Result<(), BoardEditError> {
+        Board::validate_cell(row, col)?;
+        if !(1..=9).contains(&digit) {
+            return Err(BoardEditError::InvalidDigit(digit));
+        }
+        Ok(())
+    }
+
+    /**
+     * Find a rule of Sudoku that the candidates can no longer satisfy: a cell without candidates, or a digit that is
+     * allowed in no cell of a row, column or block. Cells are checked first, in reading order, then the units. Returns
+     * None if there is no such contradiction, which doesn't mean that the candidates allow a solution.
+     */
+    pub fn contradiction(&self) -> Option<Violation> {
+        for i in 0..9 {
+            for j in 0..9 {
+                if self.0[i][j] == 0 {
+                    return Some(Violation::NoDigitForCell { row: i as u8, col: j as u8 });
+                }
+            }
+        }
+        for unit in [Unit::Row, Unit::Column, Unit::Block] {
+            for idx in 0..9 {
+                let cells: Vec<(usize, usize)> = (0..9)
+                    .map(|k| match unit {
+                        Unit::Row => (idx, k),
+                        Unit::Column => (k, idx),
+                        Unit::Block => (idx / 3 * 3 + k / 3, idx % 3 * 3 + k % 3),
+                    })
+                    .collect();
+                for digit in 1..10 {
+                    if cells.iter().all(|(i, j)| !self.contains(*i, *j, digit)) {
+                        return Some(Violation::UnplaceableDigit { digit, unit, idx: idx as u8 });
+                    }
+                }
+            }
+        }
+        return None;
+    }
+}
+
+impl Display for CandidateGrid {
+    /**
+     * Write the candidates in the 729 character Sukaku format of read_from_str, with a line per row and a space
+     * between the cells.
+     */
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for i in 0..9 {
+            let cells: Vec<String> = (0..9)
+                .map(|j| (1..10).map(|digit| if self.contains(i, j, digit) { char::from(b'0' + digit) } else { '.' }).collect())
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * A text format in which a board can be written.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BoardFormat {
+    /// 9 lines of 9 cells, with the blocks separated by spaces and empty lines, as printed by Display
+    Grid,
+    /// A single line of all 81 cells, as used by collections of puzzles with one puzzle per line
+    Line,
+    /// 9 lines of 9 comma-separated digits with 0 for empty cells, as saved by spreadsheets. Read with read_from_csv
+    Csv,
+}
+
+impl Board {
+    /**
+     * Read a board from a CSV file of 9 rows of 9 comma-separated values, e.g. saved by a spreadsheet.
+     */
+    pub fn read_from_csv(filepath: &str) -> Result<Self, BoardReadError> {
+        let contents = std::fs::read_to_string(filepath)?;
+        return Self::read_from_csv_str(&contents);
+    }
+
+    /**
+     * Read a board from a string of 9 rows of 9 comma-separated values, in which an empty cell is a blank value or 0.
+     * Values may be quoted and surrounded by spaces, and empty lines are ignored.
+     */
+    pub fn read_from_csv_str(s: &str) -> Result<Self, BoardReadError> {
+        let mut vecs: Vec<Vec<u8>> = Vec::new();
+        // Spreadsheets often start UTF-8 files with a byte order mark
+        for line in s.trim_start_matches('\u{feff}').lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut row: Vec<u8> = Vec::new();
+            for value in line.split(',') {
+                let value = value.trim().trim_matches('"').trim();
+                let digit = match value {
+                    "" => 0,
+                    _ => match value.parse::<u8>() {
+                        Ok(digit) if digit <= 9 => digit,
+                        _ => return Err(BoardReadError::InvalidValue(value.to_string())),
+                    },
+                };
+                row.push(digit);
+            }
+            if row.len() != 9 || vecs.len() >= 9 {
+                return Err(BoardReadError::InvalidSize);
+            }
+            vecs.push(row);
+        }
+        if vecs.len() < 9 {
+            return Err(BoardReadError::InvalidSize);
+        }
+        return Ok(Board(vecs));
+    }
+
+    /**
+     * Format the board as 9 rows of 9 comma-separated digits, with 0 for empty cells.
+     */
+    pub fn format_as_csv(&self) -> String {
+        let mut out = String::new();
+        for row in self.0.iter() {
+            out.push_str(&row.iter().map(|cell| cell.to_string()).collect::<Vec<_>>().join(","));
+            out.push('\n');
+        }
+        return out;
+    }
+
+    /**
+     * Write the board to a CSV file, which spreadsheets can open.
+     */
+    pub fn write_to_csv(&self, filepath: &str) -> Result<(), FormatError> {
+        return self.write_to_file(filepath, BoardFormat::Csv);
+    }
+
+    /**
+     * Format the board with box-drawing characters: a frame around the board and lines between the blocks, which is
+     * easier to read than Display in a terminal or a screenshot. Empty cells are blank.
+     */
+    pub fn to_pretty_string(&self) -> String {
+        return self.pretty().to_string();
+    }
+
+    /**
+     * The board in a wrapper that displays it like to_pretty_string, e.g. for println!("{}", board.pretty()).
+     */
+    pub fn pretty(&self) -> PrettyBoard<'_> {
+        return PrettyBoard(self);
+    }
+
+    /**
+     * Format the board as a single line of all 81 cells in reading order, with '.' for empty cells.
+     */
+    pub fn to_line(&self) -> String {
+        return BoardFormatter::new().with_single_line(true).with_separators(false).format(self);
+    }
+
+    /**
+     * Write the board to a file in the given format, ending with a newline.
+     */
+    pub fn write_to_file(&self, filepath: &str, format: BoardFormat) -> Result<(), FormatError> {
+        let contents = match format {
+            BoardFormat::Grid => self.to_string(),
+            BoardFormat::Line => format!("{}\n", self.to_line()),
+            BoardFormat::Csv => self.format_as_csv(),
+        };
+        std::fs::write(filepath, contents)?;
+        return Ok(());
+    }
+
+    /**
+     * The board transposed, i.e. mirrored in its main diagonal so that rows become columns. Like the other
+     * transformations below, it turns a valid board into a valid board and a puzzle into one with as many solutions.
+     */
+    pub fn transpose(&self) -> Board {
+        return Transformation::transpose().apply(self);
+    }
+
+    /**
+     * The board rotated a quarter turn clockwise.
+     */
+    pub fn rotate90(&self) -> Board {
+        return Transformation::rotate90().apply(self);
+    }
+
+    /**
+     * The board mirrored left to right.
+     */
+    pub fn mirror(&self) -> Board {
+        return Transformation::mirror().apply(self);
+    }
+
+    /**
+     * The board with every digit d replaced by map[d - 1]. Returns an error if the map is not a permutation of 1 to 9.
+     */
+    pub fn permute_digits(&self, map: [u8; 9]) -> Result<Board, TransformationError> {
+        return Ok(Transformation::relabel_digits(map)?.apply(self));
+    }
+
+    /**
+     * The board with two bands (groups of 3 rows, numbered 0 to 2) swapped.
+     */
+    pub fn swap_bands(&self, band: usize, other_band: usize) -> Result<Board, TransformationError> {
+        return Ok(Transformation::swap_bands(band, other_band)?.apply(self));
+    }
+
+    /**
+     * The board with two stacks (groups of 3 columns, numbered 0 to 2) swapped.
+     */
+    pub fn swap_stacks(&self, stack: usize, other_stack: usize) -> Result<Board, TransformationError> {
+        return Ok(Transformation::swap_stacks(stack, other_stack)?.apply(self));
+    }
+
+    /**
+     * The canonical form of the board under the symmetries of Sudoku: transposing, permuting the bands, stacks, rows
+     * within a band and columns within a stack, and relabeling the digits. Boards that can be transformed into each
+     * other have the same canonical form, so it can be used to deduplicate collections of puzzles.
+     */
+    pub fn canonical_form(&self) -> Board {
+        return Transformation::to_canonical_form(self).apply(self);
+    }
+
+    /**
+     * Whether the board can be transformed into the other one by the symmetries of Sudoku, see canonical_form.
+     */
+    pub fn is_isomorphic_to(&self, other: &Board) -> bool {
+        // Transformations keep the number of givens, which is much quicker to compare
+        let num_givens = |board: &Board| board.iter_cells().filter(|(_, _, digit)| digit.is_some()).count();
+        if num_givens(self) != num_givens(other) {
+            return false;
+        }
+        return self.canonical_form() == other.canonical_form();
+    }
+
+    /**
+     * Format the board like Display does, but writing the digits with the given symbols.
+     */
+    pub fn format_with_symbols(&self, symbols: &DigitSymbols) -> String {
+        return BoardFormatter::new().with_symbols(symbols.clone()).format(self);
+    }
+
+    /**
+     * Format a solution like format_with_symbols, with ANSI escape codes that color the digits of the givens bold blue
+     * and the digits filled in by the solver green, for terminals that support colors.
+     */
+    pub fn format_with_givens(&self, givens: &Board, symbols: &DigitSymbols) -> String {
+        let mut out = String::new();
+        let mut cells = self.0.iter().zip(givens.0.iter()).flat_map(|(row, given_row)| row.iter().zip(given_row.iter()));
+        for char in self.format_with_symbols(symbols).chars() {
+            if char.is_whitespace() {
+                out.push(char);
+                continue;
+            }
+            let (digit, given) = cells.next().unwrap();
+            match (*given != 0, *digit != 0) {
+                (true, _) => out.push_str(&format!("{}{}{}", GIVEN_COLOR, char, RESET_COLOR)),
+                (false, true) => out.push_str(&format!("{}{}{}", FILLED_COLOR, char, RESET_COLOR)),
+                (false, false) => out.push(char),
+            }
+        }
+        return out;
+    }
+
+    /**
+     * Format the board as an environment of the sudoku package for LaTeX, in which each row is written as
+     * |5|3| | |7| | | | |. with blank empty cells. Symbols that have a special meaning in LaTeX are escaped.
+     */
+    pub fn format_as_latex(&self, symbols: &DigitSymbols) -> String {
+        let mut out = String::from("\\begin{sudoku}\n");
+        for row in self.0.iter() {
+            for cell in row.iter() {
+                out.push('|');
+                if *cell == 0 {
+                    out.push(' ');
+                } else {
+                    out.push_str(&escape_latex(symbols.symbol(*cell)));
+                }
+            }
+            out.push_str("|.\n");
+        }
+        out.push_str("\\end{sudoku}\n");
+        return out;
+    }
+
+    /**
+     * Write the board to a LaTeX file as an environment of the sudoku package, like format_as_latex.
+     */
+    pub fn write_to_latex(&self, filepath: &str, symbols: &DigitSymbols) -> Result<(), FormatError> {
+        std::fs::write(filepath, self.format_as_latex(symbols))?;
+        return Ok(());
+    }
+}
+
+/**
+ * Escape a character for LaTeX text, including the | which separates cells in the sudoku environment.
+ */
+fn escape_latex(symbol: char) -> String {
+    return match symbol {
+        '#' | '$' | '%' | '&' | '_' | '{' | '}' => format!("\\{}", symbol),
+        '~' => "\\textasciitilde{}".to_string(),
+        '^' => "\\textasciicircum{}".to_string(),
+        '\\' => "\\textbackslash{}".to_string(),
+        '|' => "\\textbar{}".to_string(),
+        _ => symbol.to_string(),
+    };
+}
+
+/**
+ * Parse a board in the format of read_from_file, i.e. 9 lines of 9 cells or one line of 81 cells.
+ */
+impl FromStr for Board {
+    type Err = BoardReadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Board::read_from_str(s);
+    }
+}
+
+/**
+ * Create a board from 9 rows of 9 digits like Board::try_from_rows.
+ */
+impl TryFrom<[[u8; 9]; 9]> for Board {
+    type Error = BoardError;
+
+    fn try_from(rows: [[u8; 9]; 9]) -> Result<Self, Self::Error> {
+        return Board::try_from_rows(rows.iter().map(|row| row.to_vec()).collect());
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.format_with_symbols(&DigitSymbols::DIGITS))
+    }
+}
+
+/**
+ * A board that is displayed with box-drawing characters, see Board::pretty.
+ */
+pub struct PrettyBoard<'a>(&'a Board);
+
+impl Display for PrettyBoard<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let cells: Vec<Vec<char>> = self.0.0.iter()
+            .map(|row| row.iter().map(|cell| if *cell == 0 { ' ' } else { (b'0' + cell) as char }).collect())
+            .collect();
+        write!(f, "{}", format_with_box_drawing(&cells, 3, 3))
+    }
+}
+
+/**
+ * Draw a square grid of cells with a frame around it and lines between its boxes of box_rows by box_cols cells, e.g.
+ * "┌───────┬" for the top of a 9x9 board. Every cell is written as a character with a space before it.
+ */
+pub(crate) fn format_with_box_drawing(cells: &[Vec<char>], box_rows: usize, box_cols: usize) -> String {
+    let segment = "─".repeat(box_cols * 2 + 1);
+    let boxes_per_row = cells.len() / box_cols;
+    let border = |left: char, middle: char, right: char| {
+        format!("{}{}{}\n", left, vec![segment.as_str(); boxes_per_row].join(&middle.to_string()), right)
+    };
+
+    let mut out = border('┌', '┬', '┐');
+    for (i, row) in cells.iter().enumerate() {
+        if i > 0 && i % box_rows == 0 {
+            out.push_str(&border('├', '┼', '┤'));
+        }
+        out.push('│');
+        for (j, cell) in row.iter().enumerate() {
+            out.push(' ');
+            out.push(*cell);
+            if j % box_cols == box_cols - 1 {
+                out.push_str(" │");
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&border('└', '┴', '┘'));
+    return out;
+}
+
+pub fn convert_to_exact_cover_problem(board: &Board) -> Result<ExactCoverProblem, SolveError> {
+    return convert_to_exact_cover_problem_with_candidates(board, &CandidateGrid::full());
+}
+
+/**
+ * Convert a board to an exact cover problem which only contains the options permitted by the candidate grid.
+ */
+pub(crate) fn convert_to_exact_cover_problem_with_candidates(board: &Board, candidates: &CandidateGrid) -> Result<ExactCoverProblem, SolveError> {
+    return convert_to_exact_cover_problem_with_regions(board, candidates, cell_to_block);
+}
+
+/**
+ * Convert a board to an exact cover problem in which the blocks are the regions that region_of assigns the cells to,
+ * each a number between 0 and 8. The regions must have 9 cells each, e.g. the irregular regions of Jigsaw Sudoku.
+ */
+pub(crate) fn convert_to_exact_cover_problem_with_regions(board: &Board, candidates: &CandidateGrid, region_of: impl Fn(u8, u8) -> u8) -> Result<ExactCoverProblem, SolveError> {
+    let mut required_items: Vec<String> = Vec::new();
+    // One item for each cell (81) because each cell must have a digit
+    for i in 0..9 {
+        for j in 0..9 {
+            required_items.push(cell_item_to_name(i as u8, j as u8));
+        }
+    }
+    // One item for every digit in every row (9 * 9) because each digit must appear in each row
+    for i in 0..9 {
+        for d in 1..10 {
+            required_items.push(row_item_to_name(i as u8, d as u8));
+        }
+    }
+    // One item for every digit in every column (9 * 9) because each digit must appear in each column
+    for i in 0..9 {
+        for d in 1..10 {
+            required_items.push(col_item_to_name(i as u8, d as u8));
+        }
+    }
+    // One item for every digit in every block (9 * 9) because each digit must appear in each block
+    for i in 0..9 {
+        for d in 1..10 {
+            required_items.push(block_item_to_name(i as u8, d as u8));
+        }
+    }
+    // One item for initial state (1) to ensure that the initial state is preserved
+    // required_items.push(initial_state_item_name);
+
+    let mut covered_by: HashMap<String, Vec<String>> = HashMap::new();
+    for item_name in required_items.iter() {
+        covered_by.insert(item_name.clone(), Vec::new());
+    }
+    let mut required_options: Vec<String> = Vec::new();
+    let mut payloads: HashMap<String, (u8, u8, u8)> = HashMap::new();
+    // One option for every permitted digit in every cell (at most 81 * 9) because each cell must have a digit
+    for i in 0..9 {
+        for j in 0..9 {
+            for d in 1..10 {
+                if !candidates.contains(i, j, d) {
+                    continue;
+                }
+                let option_name = cell_option_to_name(i as u8, j as u8, d);
+                covered_by.entry(cell_item_to_name(i as u8, j as u8)).or_default().push(option_name.clone());
+                covered_by.entry(row_item_to_name(i as u8, d)).or_default().push(option_name.clone());
+                covered_by.entry(col_item_to_name(j as u8, d)).or_default().push(option_name.clone());
+                covered_by.entry(block_item_to_name(region_of(i as u8, j as u8), d)).or_default().push(option_name.clone());
+
+                if board.0[i][j] == d {
+                    required_options.push(option_name.clone());
+                }
+                payloads.insert(option_name, (i as u8, j as u8, d));
+            }
+        }
+    }
+    // One option for the initial state (1) to ensure that the initial state is preserved
+    // covered_by.entry(initial_state_item_name).or_default().push(initial_state_option_name);
+
+    return match ExactCoverProblem::new(required_items, vec![], required_options, covered_by) {
+        Err(InvalidProblemError::RequiredOptionConflict { option_a, option_b, item }) => Err(SolveError::ConflictingGivens {
+            first: payloads[&option_a],
+            second: payloads[&option_b],
+            reason: violated_rule(&item, true),
+        }),
+        result => Ok(result?.with_option_payloads(payloads)?),
+    };
+}
+
+fn cell_item_to_name(row: u8, col: u8) -> String {
+    return format!("r{}c{}", row, col);
+}
+
+fn row_item_to_name(row: u8, digit: u8) -> String {
+    return format!("r{}d{}", row, digit);
+}
+
+fn col_item_to_name(col: u8, digit: u8) -> String {
+    return format!("c{}d{}", col, digit);
+}
+
+fn cell_to_block(row: u8, col: u8) -> u8 {
+    return row / 3 * 3 + col / 3;
+}
+
+fn block_item_to_name(block: u8, digit: u8) -> String {
+    return format!("b{}d{}", block, digit);
+}
+
+/**
+ * The constraint that an item of the exact cover encoding of Sudoku stands for.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum SudokuItem {
+    /// The cell must have exactly one digit
+    Cell { row: u8, col: u8 },
+    /// The digit must appear exactly once in the unit with the given index
+    Digit { unit: Unit, idx: u8, digit: u8 },
+}
+
+/**
+ * Every item of the encoding by its name, named like the encoder names them, so that the rule behind an item can be
+ * found without parsing its name.
+ */
+fn sudoku_items() -> HashMap<String, SudokuItem> {
+    let mut items: HashMap<String, SudokuItem> = HashMap::new();
+    for i in 0..9u8 {
+        for j in 0..9u8 {
+            items.insert(cell_item_to_name(i, j), SudokuItem::Cell { row: i, col: j });
+        }
+        for d in 1..10u8 {
+            items.insert(row_item_to_name(i, d), SudokuItem::Digit { unit: Unit::Row, idx: i, digit: d });
+            items.insert(col_item_to_name(i, d), SudokuItem::Digit { unit: Unit::Column, idx: i, digit: d });
+            items.insert(block_item_to_name(i, d), SudokuItem::Digit { unit: Unit::Block, idx: i, digit: d });
+        }
+    }
+    return items;
+}
+
+fn cell_option_to_name(row: u8, col: u8, digit: u8) -> String {
+    return format!("r{}c{}d{}", row, col, digit);
+}
+
+/**
+ * Convert a solution of the exact cover problem of a board back to a board, using the cell and digit that each option
+ * stands for.
+ */
+pub fn convert_to_sudoku_solution(exact_cover_problem: &ExactCoverProblem, solution: &ExactCoverSolution) -> Board {
+    let mut board = vec![vec![0; 9]; 9];
+    for (row, col, digit) in exact_cover_problem.solution_payloads::<(u8, u8, u8)>(solution) {
+        board[*row as usize][*col as usize] = *digit;
+    }
+    return Board(board);
+}
+
+/**
+ * Check the exact cover encoding of a board against a solution of the board that is known to be valid, and list every
+ * reason why the encoding doesn't accept the solution. An empty list means that the encoding is consistent with the
+ * solution.
+ */
+pub fn diagnose_encoding(board: &Board, solution: &Board) -> Result<Vec<EncodingIssue>, SolveError> {
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?;
+
+    let mut selected_options: Vec<String> = Vec::new();
+    for i in 0..9 {
+        for j in 0..9 {
+            if solution.0[i][j] != 0 {
+                selected_options.push(cell_option_to_name(i as u8, j as u8, solution.0[i][j]));
+            }
+        }
+    }
+    Ok(exact_cover_problem.diagnose_solution(&selected_options))
+}
+
+/**
+ * Solve Sudoku with exact cover.
+ */
+pub fn solve_sudoku_with_exact_cover(board: &Board) -> Result<Option<Board>, SolveError> {
+    // A filled board is its own solution if it is valid, so there is nothing to search for
+    if board.is_filled() {
+        return Ok(board.is_valid_solution().then(|| board.clone()));
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?;
+
+    let solution = exact_cover_problem.solve();
+
+    Ok(solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)))
+}
+
+/**
+ * Solve every board with exact cover, spreading the boards over the threads of the current rayon thread pool. The
+ * results are in the order of the boards. Run it inside ThreadPool::install to choose the number of threads.
+ */
+#[cfg(feature = "parallel")]
+pub fn solve_sudokus(boards: &[Board]) -> Vec<Result<Option<Board>, SolveError>> {
+    use rayon::prelude::*;
+
+    return boards.par_iter().map(solve_sudoku_with_exact_cover).collect();
+}
+
+/**
+ * Solve Sudoku with exact cover, returning the smallest solution if there are several: the one with the smallest digit
+ * in the first cell, in reading order, in which the solutions differ. Unlike solve_sudoku_with_exact_cover, this
+ * always returns the same solution for the same board.
+ */
+pub fn solve_sudoku_lexicographically(board: &Board) -> Result<Option<Board>, SolveError> {
+    if board.is_filled() {
+        return Ok(board.is_valid_solution().then(|| board.clone()));
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?.with_lexicographic_order(true);
+
+    let solution = exact_cover_problem.solve();
+
+    Ok(solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)))
+}
+
+/**
+ * The outcome of solving a board, e.g. to return it from a service.
+ */
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolveResult {
+    /// The board that was solved
+    pub puzzle: Board,
+    /// A solution of the board, or None if it has no solution
+    pub solution: Option<Board>,
+    /// Whether the solution is the only solution of the board
+    pub unique: bool,
+}
+
+impl SolveResult {
+    /**
+     * Format the solution with its givens and filled in digits in different colors, see Board::format_with_givens.
+     * Returns None if there is no solution.
+     */
+    pub fn format_colored(&self, symbols: &DigitSymbols) -> Option<String> {
+        return self.solution.as_ref().map(|solution| solution.format_with_givens(&self.puzzle, symbols));
+    }
+}
+
+/**
+ * Solve a board and check whether its solution is unique, which takes a search for a second solution on top of
+ * solve_sudoku_with_exact_cover.
+ */
+pub fn solve_sudoku(board: &Board) -> Result<SolveResult, SolveError> {
+    if board.is_filled() {
+        let solution = board.is_valid_solution().then(|| board.clone());
+        let unique = solution.is_some();
+        return Ok(SolveResult { puzzle: board.clone(), solution, unique });
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?;
+
+    // Keep the first solution and stop at the second one, so that a board with several solutions is searched once
+    let mut solution: Option<ExactCoverSolution> = None;
+    let mut num_solutions = 0;
+    let _: ControlFlow<()> = exact_cover_problem.for_each_solution(|selected_options| {
+        num_solutions += 1;
+        if solution.is_none() {
+            solution = Some(ExactCoverSolution { selected_options: selected_options.to_vec() });
+        }
+        if num_solutions >= 2 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    });
+    Ok(SolveResult {
+        puzzle: board.clone(),
+        solution: solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)),
+        unique: num_solutions == 1,
+    })
+}
+
+/**
+ * Whether a board has no solution, exactly one or more than one.
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Uniqueness {
+    /// The board has no solution
+    None,
+    /// The board has exactly one solution
+    Unique,
+    /// The board has at least two solutions
+    Multiple,
+}
+
+/**
+ * Check whether a board has a unique solution, as a proper puzzle should. The search stops at the second solution,
+ * so this is much faster than counting all solutions of boards with many.
+ */
+pub fn has_unique_solution(board: &Board) -> Uniqueness {
+    if board.is_filled() {
+        return if board.is_valid_solution() { Uniqueness::Unique } else { Uniqueness::None };
+    }
+
+    // Only boards with conflicting givens can't be encoded, and those have no solution
+    let Ok(exact_cover_problem) = convert_to_exact_cover_problem(board) else {
+        return Uniqueness::None;
+    };
+
+    return match exact_cover_problem.solution_multiplicity(2) {
+        SolutionMultiplicity::None => Uniqueness::None,
+        SolutionMultiplicity::Unique(_) => Uniqueness::Unique,
+        SolutionMultiplicity::Multiple(_) => Uniqueness::Multiple,
+    };
+}
+
+/**
+ * Remove every given that isn't needed for the solution to be unique, so that no given of the returned puzzle can be
+ * removed without allowing more solutions. The givens are tried in reading order, and one pass is enough because a
+ * given that is needed stays needed when others are removed. Boards without a unique solution are returned unchanged.
+ */
+pub fn minimize(board: &Board) -> Board {
+    let mut minimal = board.clone();
+    if has_unique_solution(board) != Uniqueness::Unique {
+        return minimal;
+    }
+
+    for (i, j, digit) in board.iter_cells() {
+        let Some(digit) = digit else {
+            continue;
+        };
+        minimal.clear(i, j).unwrap();
+        if has_unique_solution(&minimal) != Uniqueness::Unique {
+            minimal.set(i, j, digit.get()).unwrap();
+        }
+    }
+    return minimal;
+}
+
+/**
+ * How much work the exact cover search needs for a board. The search tries the items and options in a fixed order, so
+ * the same board always takes the same effort.
+ */
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchEffort {
+    /// The number of solutions, counted up to 2 to check whether the solution is unique
+    pub num_solutions: u64,
+    /// The number of digits placed by propagation alone, i.e. because a cell or unit had only one option left
+    pub num_forced_digits: u64,
+    /// The number of digits placed while branching
+    pub num_nodes: u64,
+    /// The number of dead ends the search backtracked from
+    pub num_backtracks: u64,
+}
+
+impl SearchEffort {
+    /**
+     * A difficulty score derived from the search effort: the base 2 logarithm of one plus the number of backtracks.
+     * It is 0 for boards which are solved and proven unique without running into a dead end.
+     */
+    pub fn difficulty(&self) -> f64 {
+        return (1.0 + self.num_backtracks as f64).log2();
+    }
+}
+
+/**
+ * Rate a board by the effort the exact cover search needs to solve it and prove that the solution is unique, after
+ * placing all digits that follow from propagation. Unlike a rating by human solving techniques, this can rate every
+ * board, though the score doesn't correspond to how hard the board is for a person. A filled board needs no search
+ * and is rated 0, with 1 solution if it is valid and none otherwise.
+ */
+pub fn rate_by_search_effort(board: &Board) -> Result<SearchEffort, SolveError> {
+    if board.is_filled() {
+        let num_solutions = board.is_valid_solution() as u64;
+        return Ok(SearchEffort { num_solutions, num_forced_digits: 0, num_nodes: 0, num_backtracks: 0 });
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?.with_forced_option_propagation(true);
+
+    let (num_solutions, stats) = exact_cover_problem.count_solutions_with_stats(2)?;
+    Ok(SearchEffort {
+        num_solutions,
+        num_forced_digits: stats.num_forced_options,
+        num_nodes: stats.num_nodes,
+        num_backtracks: stats.num_backtracks,
+    })
+}
+
+/**
+ * The algorithm used to count the solutions of a board.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CountEngine {
+    /// Visit every solution with the backtracking search
+    Backtracking,
+    /// Build a zero-suppressed decision diagram, which shares the solutions of identical subproblems so that it can
+    /// count far more solutions than could be visited one by one
+    Zdd,
+}
+
+/**
+ * Count the solutions of a board with the given engine.
+ */
+pub fn count_sudoku_solutions(board: &Board, engine: CountEngine) -> Result<u128, SolveError> {
+    if board.is_filled() {
+        return Ok(board.is_valid_solution() as u128);
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?;
+
+    Ok(engine.count_solutions(&exact_cover_problem))
+}
+
+/**
+ * Count the solutions of a board with the backtracking search, stopping once the limit is reached if one is given.
+ * This tells how far a partial board is from being a proper puzzle. Without a limit, boards with many solutions, such
+ * as the empty board, take far too long to count this way; count_sudoku_solutions with the ZDD engine can count those.
+ * Panics if the limit is zero, since the count of 0 it would give can't be told apart from a board without solutions.
+ */
+pub fn count_solutions(board: &Board, limit: Option<u64>) -> u64 {
+    assert!(limit != Some(0), "the limit of the number of solutions to count must be at least 1");
+    if board.is_filled() {
+        return board.is_valid_solution() as u64;
+    }
+
+    // Only boards with conflicting givens can't be encoded, and those have no solution
+    let Ok(exact_cover_problem) = convert_to_exact_cover_problem(board) else {
+        return 0;
+    };
+
+    return exact_cover_problem.count_solutions_up_to(limit.unwrap_or(u64::MAX));
+}
+
+/**
+ * Count the solutions of a board like count_sudoku_solutions, but look the count up in the cache first and store it
+ * there if it wasn't found, so that counting the same board again is instant.
+ */
+pub fn count_sudoku_solutions_with_cache(board: &Board, engine: CountEngine, cache: &SolutionCache) -> Result<u128, SolveError> {
+    if board.is_filled() {
+        return Ok(board.is_valid_solution() as u128);
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?;
+
+    Ok(cache.count_solutions(&exact_cover_problem, engine.count_method())?)
+}
+
+impl CountEngine {
+    fn count_solutions(&self, exact_cover_problem: &ExactCoverProblem) -> u128 {
+        return match self {
+            CountEngine::Backtracking => exact_cover_problem.count_all_solutions() as u128,
+            CountEngine::Zdd => exact_cover_problem.build_zdd().count_solutions(),
+        };
+    }
+
+    fn count_method(&self) -> CountMethod {
+        return match self {
+            CountEngine::Backtracking => CountMethod::Search,
+            CountEngine::Zdd => CountMethod::Zdd,
+        };
+    }
+}
+
+/**
+ * Solve Sudoku with exact cover, aborting the search if it would use more than about max_bytes of memory.
+ */
+pub fn solve_sudoku_with_memory_limit(board: &Board, max_bytes: usize) -> Result<Option<Board>, SolveError> {
+    if board.is_filled() {
+        return Ok(board.is_valid_solution().then(|| board.clone()));
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?.with_memory_limit(max_bytes);
+
+    let solution = exact_cover_problem.try_solve()?;
+
+    Ok(solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)))
+}
+
+/**
+ * Solve Sudoku with the given exact cover backend instead of the one chosen automatically, e.g. to compare backends.
+ */
+pub fn solve_sudoku_with_backend(board: &Board, backend: &dyn SolverBackend) -> Result<Option<Board>, SolveError> {
+    return solve_sudoku_with_backend_and_stats(board, backend).map(|(solution, _)| solution);
+}
+
+/**
+ * Solve Sudoku with the given exact cover backend like solve_sudoku_with_backend, and also return the statistics of
+ * the search, e.g. the number of nodes the backend needed. A filled board needs no search.
+ */
+pub fn solve_sudoku_with_backend_and_stats(board: &Board, backend: &dyn SolverBackend) -> Result<(Option<Board>, SearchStats), SolveError> {
+    if board.is_filled() {
+        return Ok((board.is_valid_solution().then(|| board.clone()), SearchStats::default()));
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem(board)?;
+
+    let (solution, stats) = exact_cover_problem.try_solve_with_backend_and_stats(backend)?;
+
+    Ok((solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)), stats))
+}
+
+/**
+ * Solve Sudoku with exact cover, notifying the observers of every step of the search, e.g. to record a trace of it.
+ */
+pub fn solve_sudoku_with_observers(board: &Board, observers: &mut [&mut dyn Observer]) -> Result<Option<Board>, SolveError> {
+    return solve_sudoku_with_observers_and_memory_limit(board, observers, None);
+}
+
+/**
+ * Solve Sudoku with exact cover like solve_sudoku_with_observers, aborting the search if it would use more than about
+ * max_bytes of memory if a limit is given.
+ */
+pub fn solve_sudoku_with_observers_and_memory_limit(
+    board: &Board,
+    observers: &mut [&mut dyn Observer],
+    max_bytes: Option<usize>) -> Result<Option<Board>, SolveError>
+{
+    if board.is_filled() {
+        return Ok(board.is_valid_solution().then(|| board.clone()));
+    }
+
+    let mut exact_cover_problem = convert_to_exact_cover_problem(board)?;
+    if let Some(max_bytes) = max_bytes {
+        exact_cover_problem = exact_cover_problem.with_memory_limit(max_bytes);
+    }
+
+    let solution = exact_cover_problem.try_solve_with_observers(observers)?;
+
+    Ok(solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)))
+}
+
+/**
+ * Check that a board is a solution of a puzzle: it keeps the givens of the puzzle, is filled and contains every digit
+ * once in every row, column and block. Reports the first mismatch, in that order of checks.
+ */
+pub fn verify_solution(puzzle: &Board, solution: &Board) -> Result<(), SolutionMismatch> {
+    for i in 0..9 {
+        for j in 0..9 {
+            let given = puzzle.0[i][j];
+            let digit = solution.0[i][j];
+            if given != 0 && digit != given {
+                return Err(SolutionMismatch::ChangedGiven { row: i as u8, col: j as u8, given, digit });
+            }
+        }
+    }
+    if let Some((row, col)) = solution.empty_cells().iter().next() {
+        return Err(SolutionMismatch::EmptyCell { row: row as u8, col: col as u8 });
+    }
+    for unit in [Unit::Row, Unit::Column, Unit::Block] {
+        for idx in 0..9 {
+            let mut seen_digits = 0u16;
+            for k in 0..9 {
+                let digit = match unit {
+                    Unit::Row => solution.0[idx][k],
+                    Unit::Column => solution.0[k][idx],
+                    Unit::Block => solution.0[idx / 3 * 3 + k / 3][idx % 3 * 3 + k % 3],
+                };
+                if seen_digits & (1 << digit) != 0 {
+                    return Err(SolutionMismatch::BrokenRule(Violation::DuplicateDigit { digit, unit, idx: idx as u8 }));
+                }
+                seen_digits |= 1 << digit;
+            }
+        }
+    }
+    return Ok(());
+}
+
+/**
+ * Explain why a board has no solution. Returns None if the board does have a solution.
+ */
+pub fn explain_unsolvable(board: &Board) -> Result<Option<UnsolvableExplanation>, SolveError> {
+    let exact_cover_problem = match convert_to_exact_cover_problem(board) {
+        Err(SolveError::ConflictingGivens { first, second, reason }) => {
+            return Ok(Some(UnsolvableExplanation { givens: vec![first, second], reason: Some(reason) }));
+        }
+        result => result?,
+    };
+
+    let core = match exact_cover_problem.explain_unsatisfiability() {
+        Some(core) => core,
+        None => return Ok(None),
+    };
+
+    let givens = core.required_options.iter()
+        .map(|option_name| *exact_cover_problem.option_payload::<(u8, u8, u8)>(option_name).unwrap())
+        .collect();
+    let reason = core.contradiction.map(|contradiction| match contradiction {
+        Contradiction::CoveredTwice(item_name) => violated_rule(&item_name, true),
+        Contradiction::Uncoverable(item_name) => violated_rule(&item_name, false),
+    });
+    Ok(Some(UnsolvableExplanation { givens, reason }))
+}
+
+/**
+ * The rule behind the constraint of an item that is violated, either because it is covered twice or not at all.
+ */
+fn violated_rule(item_name: &str, covered_twice: bool) -> Violation {
+    return match (sudoku_items()[item_name], covered_twice) {
+        (SudokuItem::Digit { unit, idx, digit }, true) => Violation::DuplicateDigit { digit, unit, idx },
+        (SudokuItem::Digit { unit, idx, digit }, false) => Violation::UnplaceableDigit { digit, unit, idx },
+        (SudokuItem::Cell { row, col }, true) => Violation::MultipleDigitsInCell { row, col },
+        (SudokuItem::Cell { row, col }, false) => Violation::NoDigitForCell { row, col },
+    };
+}
+
+/**
+ * Solve Sudoku with exact cover, only allowing the digits in the candidate grid.
+ */
+pub fn solve_with_candidates(board: &Board, candidates: &CandidateGrid) -> Result<Option<Board>, SolveError> {
+    // A given that is not a candidate can never be part of the solution
+    for i in 0..9 {
+        for j in 0..9 {
+            let digit = board.0[i][j];
+            if digit != 0 && !candidates.contains(i, j, digit) {
+                return Ok(None);
+            }
+        }
+    }
+
+    if board.is_filled() {
+        return Ok(board.is_valid_solution().then(|| board.clone()));
+    }
+
+    let exact_cover_problem = convert_to_exact_cover_problem_with_candidates(board, candidates)?;
+
+    let solution = exact_cover_problem.solve();
+
+    Ok(solution.map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution)))
+}
+
+/**
+ * Solve a Sukaku, a Sudoku without givens in which every cell may only have one of its candidates. A cell with a
+ * single candidate acts as a given.
+ */
+pub fn solve_sukaku(candidates: &CandidateGrid) -> Result<Option<Board>, SolveError> {
+    return solve_with_candidates(&Board::empty(), candidates);
+}
+
+/**
+ * The next digit that can be deduced on a board, and how.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Hint {
+    /// The row of the cell, 0-based
+    pub row: u8,
+    /// The column of the cell, 0-based
+    pub col: u8,
+    /// The digit that must be in the cell
+    pub digit: u8,
+    /// The technique that deduces the digit, a naked or hidden single
+    pub technique: Technique,
+    /// The cells the deduction is based on, e.g. the other cells of the unit for a hidden single
+    pub cells: Vec<(u8, u8)>,
+    /// The deductions that remove candidates and have to be made before the digit can be placed, in order
+    pub eliminations: Vec<Step>,
+}
+
+/**
+ * Find the next digit a person can deduce on a board with the techniques of the logical solver, or None if the board
+ * is filled, has conflicting givens or needs guessing to make progress.
+ */
+pub fn hint(board: &Board) -> Option<Hint> {
+    if board.is_filled() || board.validate().is_err() {
+        return None;
+    }
+
+    let mut steps = solve_logically(board).steps;
+    let idx = steps.iter().position(|step| step.placement.is_some())?;
+    steps.truncate(idx + 1);
+    let step = steps.pop().unwrap();
+    let (row, col, digit) = step.placement.unwrap();
+    return Some(Hint { row, col, digit, technique: step.technique, cells: step.cells, eliminations: steps });
+}
+
+/**
+ * Why a digit was placed in a solution trace.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MoveReason {
+    /// The digit follows from the technique
+    Technique(Technique),
+    /// No technique applied anymore, so the digit was taken from the solution found by the backtracking search
+    Guess,
+}
+
+impl Display for MoveReason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MoveReason::Technique(technique) => write!(f, "{}", technique),
+            MoveReason::Guess => write!(f, "backtracking guess"),
+        }
+    }
+}
+
+/**
+ * A digit placed in a cell while solving a board.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Move {
+    /// The row of the cell, 0-based
+    pub row: u8,
+    /// The column of the cell, 0-based
+    pub col: u8,
+    /// The digit placed in the cell
+    pub digit: u8,
+    /// How the digit was found
+    pub reason: MoveReason,
+}
+
+/**
+ * A solution of a board as the digits placed one by one, so that the solve can be replayed move by move.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolutionTrace {
+    /// The board that was solved
+    pub puzzle: Board,
+    /// A move for every empty cell of the puzzle, in the order they were made
+    pub moves: Vec<Move>,
+}
+
+impl SolutionTrace {
+    /**
+     * The board after all moves are made.
+     */
+    pub fn solution(&self) -> Board {
+        let mut board = self.puzzle.clone();
+        for m in self.moves.iter() {
+            board.0[m.row as usize][m.col as usize] = m.digit;
+        }
+        return board;
+    }
+
+    /**
+     * The number of digits that couldn't be deduced with a technique.
+     */
+    pub fn num_guesses(&self) -> usize {
+        return self.moves.iter().filter(|m| m.reason == MoveReason::Guess).count();
+    }
+}
+
+/**
+ * Solve a board while recording every digit placed. Digits are deduced with the techniques of the logical solver as
+ * long as possible. When it gets stuck, the empty cell with the fewest candidates gets its digit from the solution of
+ * the exact cover search, and the logical solver continues from there. Returns None if the board has no solution.
+ */
+pub fn solve_with_steps(board: &Board) -> Result<Option<SolutionTrace>, SolveError> {
+    let Some(solution) = solve_sudoku_with_exact_cover(board)? else {
+        return Ok(None);
+    };
+
+    let (moves, _) = solve_with_guesses(board, &solution);
+    return Ok(Some(SolutionTrace { puzzle: board.clone(), moves }));
+}
+
+/**
+ * Solve a board with the logical solver, guessing the digit of the empty cell with the fewest candidates from the
+ * given solution whenever it gets stuck. Returns the moves and the deductions of the logical solver, in order.
+ */
+fn solve_with_guesses(board: &Board, solution: &Board) -> (Vec<Move>, Vec<Step>) {
+    let mut moves: Vec<Move> = Vec::new();
+    let mut steps: Vec<Step> = Vec::new();
+    let mut current = board.clone();
+    while !current.is_filled() {
+        let logic_solution = solve_logically(&current);
+        for step in logic_solution.steps.iter() {
+            if let Some((row, col, digit)) = step.placement {
+                moves.push(Move { row, col, digit, reason: MoveReason::Technique(step.technique) });
+            }
+        }
+        steps.extend(logic_solution.steps);
+        current = logic_solution.board;
+
+        let guess = current.iter_cells()
+            .filter(|(_, _, digit)| digit.is_none())
+            .min_by_key(|(i, j, _)| logic_solution.candidates.digits(*i, *j).len());
+        if let Some((i, j, _)) = guess {
+            let digit = solution.0[i][j];
+            moves.push(Move { row: i as u8, col: j as u8, digit, reason: MoveReason::Guess });
+            current.0[i][j] = digit;
+        }
+    }
+    return (moves, steps);
+}
+
+/**
+ * How much work a person needs for a board with the techniques of the logical solver, guessing a digit whenever none
+ * of them applies like solve_with_steps does.
+ */
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogicEffort {
+    /// The number of deductions made with each technique, by the name of the technique, for the techniques used
+    pub technique_counts: BTreeMap<String, u64>,
+    /// The number of bifurcations, i.e. the digits that had to be guessed because no technique applied
+    pub num_bifurcations: u64,
+    /// The number of deductions made with the techniques
+    pub num_steps: usize,
+    /// The index of the first deduction that uses the hardest technique needed, or None if no deduction was made
+    pub hardest_step: Option<usize>,
+    /// The name of the hardest technique needed, or None if no deduction was made
+    pub hardest_technique: Option<String>,
+}
+
+/**
+ * Rate a board by the techniques a person needs to solve it and the number of digits that have to be guessed, which
+ * complements the rating by search effort. Returns None if the board has no solution.
+ */
+pub fn rate_by_techniques(board: &Board) -> Result<Option<LogicEffort>, SolveError> {
+    let Some(solution) = solve_sudoku_with_exact_cover(board)? else {
+        return Ok(None);
+    };
+
+    let (moves, steps) = solve_with_guesses(board, &solution);
+    let mut technique_counts: BTreeMap<String, u64> = BTreeMap::new();
+    for step in steps.iter() {
+        *technique_counts.entry(step.technique.to_string()).or_default() += 1;
+    }
+    // min_by_key keeps the first of the steps with the hardest technique
+    let hardest_step = steps.iter().enumerate()
+        .min_by_key(|(_, step)| std::cmp::Reverse(step.technique))
+        .map(|(idx, _)| idx);
+    return Ok(Some(LogicEffort {
+        technique_counts,
+        num_bifurcations: moves.iter().filter(|m| m.reason == MoveReason::Guess).count() as u64,
+        num_steps: steps.len(),
+        hardest_step,
+        hardest_technique: hardest_step.map(|idx| steps[idx].technique.to_string()),
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use exact_cover::{BitsetBackend, DancingCellsBackend, GeneralBackend};
+    use rstest::rstest;
+
+    use super::*;
+
+    fn get_board1() -> Board {
+        return Board(vec![
+            vec![5, 3, 0, 0, 7, 0, 0, 0, 0],
+            vec![6, 0, 0, 1, 9, 5, 0, 0, 0],
+            vec![0, 9, 8, 0, 0, 0, 0, 6, 0],
+            vec![8, 0, 0, 0, 6, 0, 0, 0, 3],
+            vec![4, 0, 0, 8, 0, 3, 0, 0, 1],
+            vec![7, 0, 0, 0, 2, 0, 0, 0, 6],
+            vec![0, 6, 0, 0, 0, 7, 2, 8, 0],
+            vec![0, 0, 0, 4, 1, 9, 0, 0, 5],
+            vec![0, 0, 0, 0, 8, 0, 0, 7, 9],
+        ]);
+    }
+
+    fn get_board1_solved() -> Board {
+        return Board(vec![
+            vec![5, 3, 4, 6, 7, 8, 9, 1, 2],
+            vec![6, 7, 2, 1, 9, 5, 3, 4, 8],
+            vec![1, 9, 8, 3, 4, 2, 5, 6, 7],
+            vec![8, 5, 9, 7, 6, 1, 4, 2, 3],
+            vec![4, 2, 6, 8, 5, 3, 7, 9, 1],
+            vec![7, 1, 3, 9, 2, 4, 8, 5, 6],
+            vec![9, 6, 1, 5, 3, 7, 2, 8, 4],
+            vec![2, 8, 7, 4, 1, 9, 6, 3, 5],
+            vec![3, 4, 5, 2, 8, 6, 1, 7, 9],
+        ]);
+    }
+
+    #[test]
+    fn test_read_from_file() {
+        let file_path = "data/sudoku.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        let expected_board = get_board1();
+        assert_eq!(board.unwrap(), expected_board);
+    }
+
+    #[test]
+    fn test_read_from_file_no_spaces() {
+        let file_path = "data/sudoku_no_spaces.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        let expected_board = get_board1();
+        assert_eq!(board.unwrap(), expected_board);
+    }
+
+    #[test]
+    fn test_read_from_file_no_newlines() {
+        let file_path = "data/sudoku_no_newlines.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        let expected_board = get_board1();
+        assert_eq!(board.unwrap(), expected_board);
+    }
+
+    #[test]
+    fn test_read_from_file_extra_spaces() {
+        let file_path = "data/sudoku_extra_spaces.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        let expected_board = get_board1();
+        assert_eq!(board.unwrap(), expected_board);
+    }
+
+    #[test]
+    fn test_read_from_file_extra_newlines() {
+        let file_path = "data/sudoku_extra_newlines.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        let expected_board = get_board1();
+        assert_eq!(board.unwrap(), expected_board);
+    }
+
+    #[test]
+    fn test_read_from_str() {
+        let board = Board::read_from_str(&get_board1().to_string());
+
+        assert_eq!(board.unwrap(), get_board1());
+    }
+
+    #[test]
+    fn test_read_from_reader() {
+        let reader = std::io::Cursor::new(get_board1().to_string());
+
+        let board = Board::read_from_reader(reader);
+
+        assert_eq!(board.unwrap(), get_board1());
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let board = get_board1().to_string().parse::<Board>();
+
+        assert_eq!(board.unwrap(), get_board1());
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let line = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79";
+
+        let board: Board = line.parse().unwrap();
+
+        assert_eq!(board, get_board1());
+    }
+
+    #[rstest]
+    #[case("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..7")]
+    #[case("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79\n.........")]
+    #[case("53..7....\n6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79")]
+    fn test_parse_invalid_size(#[case] s: &str) {
+        let result = s.parse::<Board>();
+
+        assert!(matches!(result, Err(BoardReadError::InvalidSize)));
+    }
+
+    #[test]
+    fn test_parse_invalid_character() {
+        let result = "53..7...x6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79".parse::<Board>();
+
+        assert!(matches!(result, Err(BoardReadError::InvalidCharacter('x'))));
+    }
+
+    #[rstest]
+    #[case(DigitSymbols::DIGITS)]
+    #[case(DigitSymbols::ZERO_BASED)]
+    #[case(DigitSymbols::LETTERS)]
+    #[case(DigitSymbols::new("abcdefghi").unwrap())]
+    fn test_digit_symbols_round_trip(#[case] symbols: DigitSymbols) {
+        let board = get_board1();
+
+        let formatted = board.format_with_symbols(&symbols);
+        let read_back = Board::read_from_str_with_symbols(&formatted, &symbols);
+
+        assert_eq!(read_back.unwrap(), board);
+    }
+
+    /**
+     * A SplitMix64 step, so that the random boards are the same on every run.
+     */
+    fn next_random(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    fn shuffle<T>(values: &mut [T], state: &mut u64) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, (next_random(state) % (i as u64 + 1)) as usize);
+        }
+    }
+
+    /**
+     * A random valid board, made by relabeling the digits of a solved board, shuffling its bands, stacks and the rows
+     * and columns within them, and emptying about half of the cells.
+     */
+    fn get_random_board(seed: u64) -> Board {
+        let mut state = seed;
+        let mut digits: Vec<u8> = (1..10).collect();
+        shuffle(&mut digits, &mut state);
+        let mut line_orders: Vec<Vec<usize>> = Vec::new();
+        for _ in 0..2 {
+            let mut bands: Vec<usize> = (0..3).collect();
+            shuffle(&mut bands, &mut state);
+            let mut order: Vec<usize> = Vec::new();
+            for band in bands {
+                let mut lines: Vec<usize> = (band * 3..band * 3 + 3).collect();
+                shuffle(&mut lines, &mut state);
+                order.extend(lines);
+            }
+            line_orders.push(order);
+        }
+
+        let solved = get_board1_solved();
+        let mut rows = vec![vec![0; 9]; 9];
+        for i in 0..9 {
+            for j in 0..9 {
+                if next_random(&mut state) & 1 == 0 {
+                    rows[i][j] = digits[solved.0[line_orders[0][i]][line_orders[1][j]] as usize - 1];
+                }
+            }
+        }
+        return Board(rows);
+    }
+
+    #[test]
+    fn test_line_format_round_trip_on_random_boards() {
+        for seed in 0..200 {
+            let board = get_random_board(seed);
+
+            let read_back = board.to_line().parse::<Board>();
+
+            assert_eq!(read_back.unwrap(), board, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_to_line() {
+        let line = get_board1().to_line();
+
+        assert_eq!(line, "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79");
+    }
+
+    #[test]
+    fn test_csv_round_trip_on_random_boards() {
+        for seed in 0..200 {
+            let board = get_random_board(seed);
+
+            let read_back = Board::read_from_csv_str(&board.format_as_csv());
+
+            assert_eq!(read_back.unwrap(), board, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_read_from_csv() {
+        let board = Board::read_from_csv("data/sudoku.csv");
+
+        assert_eq!(board.unwrap(), get_board1());
+    }
+
+    #[rstest]
+    #[case("1,2,3\n")]
+    #[case(&"0,0,0,0,0,0,0,0,0\n".repeat(10))]
+    #[case(&"0,0,0,0,0,0,0,0,0,0\n".repeat(9))]
+    fn test_read_from_csv_invalid_size(#[case] s: &str) {
+        let result = Board::read_from_csv_str(s);
+
+        assert!(matches!(result, Err(BoardReadError::InvalidSize)));
+    }
+
+    #[test]
+    fn test_read_from_csv_invalid_value() {
+        let result = Board::read_from_csv_str(&"0,0,0,0,0,0,0,0,10\n".repeat(9));
+
+        assert!(matches!(result, Err(BoardReadError::InvalidValue(value)) if value == "10"));
+    }
+
+    #[rstest]
+    #[case(BoardFormat::Grid)]
+    #[case(BoardFormat::Line)]
+    #[case(BoardFormat::Csv)]
+    fn test_write_to_file(#[case] format: BoardFormat) {
+        let file_path = std::env::temp_dir().join(format!("rust-sudoku-{:?}-{}.txt", format, std::process::id()));
+        let file_path = file_path.to_str().unwrap();
+
+        get_board1().write_to_file(file_path, format).unwrap();
+        let board = match format {
+            BoardFormat::Csv => Board::read_from_csv(file_path),
+            _ => Board::read_from_file(file_path),
+        };
+        std::fs::remove_file(file_path).unwrap();
+
+        assert_eq!(board.unwrap(), get_board1());
+    }
+
+    #[test]
+    fn test_random_boards_are_valid() {
+        for seed in 0..20 {
+            let board = get_random_board(seed);
+
+            assert!(solve_sudoku_with_exact_cover(&board).unwrap().is_some(), "seed {}", seed);
+        }
+    }
+
+    #[rstest]
+    #[case(DigitSymbols::DIGITS)]
+    #[case(DigitSymbols::ZERO_BASED)]
+    #[case(DigitSymbols::LETTERS)]
+    fn test_grid_format_round_trip_on_random_boards(#[case] symbols: DigitSymbols) {
+        for seed in 0..200 {
+            let board = get_random_board(seed);
+
+            let formatted = board.format_with_symbols(&symbols);
+            let read_back = Board::read_from_str_with_symbols(&formatted, &symbols);
+
+            assert_eq!(read_back.unwrap(), board, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_format_with_letters() {
+        let board = get_board1();
+
+        let formatted = board.format_with_symbols(&DigitSymbols::LETTERS);
+
+        assert!(formatted.starts_with("EC. .G. ...\nF.. AIE ...\n"));
+    }
+
+    #[test]
+    fn test_verify_solution() {
+        assert_eq!(verify_solution(&get_board1(), &get_board1_solved()), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_solution_with_changed_given() {
+        let mut solution = get_board1_solved();
+        solution.0[0].swap(0, 1);
+
+        let result = verify_solution(&get_board1(), &solution);
+
+        assert_eq!(result, Err(SolutionMismatch::ChangedGiven { row: 0, col: 0, given: 5, digit: 3 }));
+    }
+
+    #[test]
+    fn test_verify_solution_with_empty_cell() {
+        let mut solution = get_board1_solved();
+        solution.0[8][0] = 0;
+
+        let result = verify_solution(&get_board1(), &solution);
+
+        assert_eq!(result, Err(SolutionMismatch::EmptyCell { row: 8, col: 0 }));
+    }
+
+    #[test]
+    fn test_verify_solution_with_duplicate_digit() {
+        let mut solution = get_board1_solved();
+        solution.0[0][2] = 6;
+
+        let result = verify_solution(&get_board1(), &solution);
+
+        let violation = Violation::DuplicateDigit { digit: 6, unit: Unit::Row, idx: 0 };
+        assert_eq!(result, Err(SolutionMismatch::BrokenRule(violation)));
+    }
+
+    #[test]
+    fn test_empty_and_filled_cells() {
+        let board = get_board1();
+
+        let empty_cells = board.empty_cells();
+
+        assert_eq!(board.filled_cells().len(), 31);
+        assert_eq!(empty_cells.len(), 50);
+        assert!(empty_cells.contains(0, 2));
+        assert!(!empty_cells.contains(0, 0));
+        assert_eq!(empty_cells | board.filled_cells(), CellSet::full());
+    }
+
+    #[test]
+    fn test_format_as_latex() {
+        let board = get_board1();
+
+        let latex = board.format_as_latex(&DigitSymbols::DIGITS);
+
+        assert!(latex.starts_with("\\begin{sudoku}\n|5|3| | |7| | | | |.\n|6| | |1|9|5| | | |.\n"));
+        assert!(latex.ends_with("| | | | |8| | |7|9|.\n\\end{sudoku}\n"));
+        assert_eq!(latex.lines().count(), 11);
+    }
+
+    #[test]
+    fn test_write_to_latex() {
+        let file_path = std::env::temp_dir().join(format!("rust-sudoku-latex-{}.tex", std::process::id()));
+        let file_path = file_path.to_str().unwrap();
+
+        get_board1().write_to_latex(file_path, &DigitSymbols::DIGITS).unwrap();
+        let contents = std::fs::read_to_string(file_path).unwrap();
+        std::fs::remove_file(file_path).unwrap();
+
+        assert_eq!(contents, get_board1().format_as_latex(&DigitSymbols::DIGITS));
+        assert!(matches!(get_board1().write_to_latex("/nonexistent/board.tex", &DigitSymbols::DIGITS),
+                         Err(FormatError::FileWriteError(_))));
+    }
+
+    #[test]
+    fn test_format_as_latex_escapes_symbols() {
+        let board = Board(vec![vec![1, 2, 3, 4, 5, 6, 7, 8, 9]; 9]);
+        let symbols = DigitSymbols::new("#$%&_{}~|").unwrap();
+
+        let latex = board.format_as_latex(&symbols);
+
+        assert!(latex.contains("|\\#|\\$|\\%|\\&|\\_|\\{|\\}|\\textasciitilde{}|\\textbar{}|.\n"));
+    }
+
+    #[test]
+    fn test_read_zero_based() {
+        let board = Board::read_from_str_with_symbols(&"0........\n".repeat(9), &DigitSymbols::ZERO_BASED).unwrap();
+
+        assert_eq!(board.0[0][0], 1);
+        assert_eq!(board.0[0][1], 0);
+    }
+
+    #[rstest]
+    #[case("ABCDEFGH", DigitSymbolsError::WrongNumberOfSymbols(8))]
+    #[case("ABCDEFGHA", DigitSymbolsError::DuplicateSymbol('A'))]
+    #[case("ABCD.FGHI", DigitSymbolsError::ReservedSymbol('.'))]
+    fn test_invalid_digit_symbols(#[case] symbols: &str, #[case] expected_error: DigitSymbolsError) {
+        assert_eq!(DigitSymbols::new(symbols), Err(expected_error));
+    }
+
+    #[test]
+    fn test_read_from_file_invalid_path() {
+        let file_path = "data/sudoku_invalid_path.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        assert!(matches!(board, Err(BoardReadError::FileReadError(_))));
+        assert!(board.unwrap_err().source().is_some());
+    }
+
+    #[test]
+    fn test_read_from_file_invalid_file() {
+        let file_path = "data/sudoku_invalid.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        assert!(matches!(board, Err(BoardReadError::FileReadError(_))));
+    }
+
+    #[test]
+    fn test_read_from_file_too_wide() {
+        let file_path = "data/sudoku_too_wide.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        assert!(matches!(board, Err(BoardReadError::InvalidSize)));
+    }
+
+    #[test]
+    fn test_read_from_file_too_long() {
+        let file_path = "data/sudoku_too_long.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        assert!(matches!(board, Err(BoardReadError::InvalidSize)));
+    }
+
+    #[test]
+    fn test_read_from_file_missing_character() {
+        let file_path = "data/sudoku_missing_character.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        assert!(matches!(board, Err(BoardReadError::InvalidSize)));
+    }
+
+    #[test]
+    fn test_read_from_file_invalid_character() {
+        let file_path = "data/sudoku_invalid_character.txt";
+
+        let board = Board::read_from_file(file_path);
+
+        assert!(matches!(board, Err(BoardReadError::InvalidCharacter('x'))));
+    }
+
+    #[test]
+    fn test_explain_unsolvable_solvable_board() {
+        let board = get_board1();
+
+        let explanation = explain_unsolvable(&board).unwrap();
+
+        assert_eq!(explanation, None);
+    }
+
+    #[test]
+    fn test_explain_unsolvable_duplicate_digit() {
+        let mut board = get_board1();
+        board.0[0][2] = 5;
+
+        let explanation = explain_unsolvable(&board).unwrap();
+
+        let explanation = explanation.unwrap();
+        assert_eq!(explanation.givens, vec![(0, 0, 5), (0, 2, 5)]);
+        assert_eq!(explanation.reason, Some(Violation::DuplicateDigit { digit: 5, unit: Unit::Row, idx: 0 }));
+    }
+
+    #[test]
+    fn test_solve_duplicate_digit() {
+        let mut board = get_board1();
+        board.0[0][2] = 5;
+
+        let result = solve_sudoku_with_exact_cover(&board);
+
+        match result {
+            Err(SolveError::ConflictingGivens { first, second, reason }) => {
+                assert_eq!((first, second), ((0, 0, 5), (0, 2, 5)));
+                assert_eq!(reason.to_string(), "digit 5 appears more than once in row 1");
+            }
+            _ => panic!("expected conflicting givens, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_explain_unsolvable_digit_without_place_in_row() {
+        // The first row misses digits 1 and 9, but the 1s below block digit 1 from both empty cells
+        let mut board = Board::empty();
+        board.0[0] = vec![2, 3, 4, 5, 6, 7, 8, 0, 0];
+        board.0[3][7] = 1;
+        board.0[6][8] = 1;
+
+        let explanation = explain_unsolvable(&board).unwrap();
+
+        let explanation = explanation.unwrap();
+        let mut expected_givens: Vec<(u8, u8, u8)> = (0..7).map(|col| (0, col, col + 2)).collect();
+        expected_givens.extend([(3, 7, 1), (6, 8, 1)]);
+        assert_eq!(explanation.givens, expected_givens);
+        assert_eq!(explanation.reason, Some(Violation::UnplaceableDigit { digit: 1, unit: Unit::Row, idx: 0 }));
+    }
+
+    #[test]
+    fn test_solve_error_source() {
+        let error = SolveError::from(InvalidProblemError::DuplicateItem("r0c0".to_string()));
+
+        assert_eq!(error.to_string(), "the board could not be encoded as an exact cover problem");
+        assert_eq!(error.source().unwrap().to_string(), "item r0c0 is declared more than once");
+    }
+
+    #[test]
+    fn test_solve_sudoku_with_memory_limit() {
+        let board = get_board1();
+
+        let solution = solve_sudoku_with_memory_limit(&board, 10_000_000).unwrap();
+        let error = solve_sudoku_with_memory_limit(&board, 1000).unwrap_err();
+
+        assert_eq!(solution, Some(get_board1_solved()));
+        assert_eq!(error.source().unwrap().to_string(), "the search exceeded the memory limit of 1000 bytes");
+    }
+
+    #[test]
+    fn test_fmt() {
+        let board = get_board1();
+
+        let fmt = format!("{}", board);
+
+        let expected_fmt = "\
+53. .7. ...
+6.. 195 ...
+.98 ... .6.
+
+8.. .6. ..3
+4.. 8.3 ..1
+7.. .2. ..6
+
+.6. ..7 28.
+... 419 ..5
+... .8. .79
+".to_string();
+        assert_eq!(fmt, expected_fmt)
+    }
+
+    #[test]
+    fn test_solve_sudoku_with_exact_cover() {
+        let board = get_board1();
+
+        let solution = solve_sudoku_with_exact_cover(&board).unwrap();
+
+        assert!(solution.is_some());
+        let expected_solution = get_board1_solved();
+        assert_eq!(solution.clone().unwrap(), expected_solution);
+        assert_valid_sudoku_solution(solution.clone().unwrap());
+    }
+
+    #[test]
+    fn test_solve_sudoku() {
+        let multiple = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+
+        let unique_result = solve_sudoku(&get_board1()).unwrap();
+        let multiple_result = solve_sudoku(&multiple).unwrap();
+        let solved_result = solve_sudoku(&get_board1_solved()).unwrap();
+
+        assert_eq!(unique_result, SolveResult { puzzle: get_board1(), solution: Some(get_board1_solved()), unique: true });
+        assert!(!multiple_result.unique);
+        assert!(verify_solution(&multiple, &multiple_result.solution.unwrap()).is_ok());
+        assert_eq!(solved_result.solution, Some(get_board1_solved()));
+        assert!(solved_result.unique);
+    }
+
+    #[test]
+    fn test_has_unique_solution() {
+        let multiple = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+        let mut conflicting = get_board1();
+        conflicting.set(0, 2, 5).unwrap();
+        let mut unsolved = get_board1_solved();
+        unsolved.set(0, 0, 1).unwrap();
+
+        assert_eq!(has_unique_solution(&get_board1()), Uniqueness::Unique);
+        assert_eq!(has_unique_solution(&get_board1_solved()), Uniqueness::Unique);
+        assert_eq!(has_unique_solution(&multiple), Uniqueness::Multiple);
+        assert_eq!(has_unique_solution(&Board::empty()), Uniqueness::Multiple);
+        assert_eq!(has_unique_solution(&conflicting), Uniqueness::None);
+        assert_eq!(has_unique_solution(&unsolved), Uniqueness::None);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let multiple = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+
+        let minimal = minimize(&get_board1());
+
+        assert_eq!(has_unique_solution(&minimal), Uniqueness::Unique);
+        assert!(minimal.iter_cells().filter(|(_, _, digit)| digit.is_some()).count() < 30);
+        for (i, j, digit) in minimal.iter_cells() {
+            let Some(digit) = digit else {
+                continue;
+            };
+            assert_eq!(get_board1().get(i, j), Some(digit));
+            let mut reduced = minimal.clone();
+            reduced.clear(i, j).unwrap();
+            assert_eq!(has_unique_solution(&reduced), Uniqueness::Multiple);
+        }
+        assert_eq!(minimize(&multiple), multiple);
+        assert_eq!(minimize(&minimal), minimal);
+    }
+
+    #[test]
+    fn test_hint() {
+        let hint = hint(&get_board1()).unwrap();
+
+        assert_eq!(get_board1_solved().get(hint.row as usize, hint.col as usize).unwrap().get(), hint.digit);
+        assert_eq!(hint.technique, Technique::NakedSingle);
+        assert_eq!(hint.cells, vec![(hint.row, hint.col)]);
+        assert!(hint.eliminations.is_empty());
+    }
+
+    #[test]
+    fn test_hint_after_eliminations() {
+        let board = Board::read_from_file("data/sudoku_evil.txt").unwrap();
+        let solution = solve_sudoku_with_exact_cover(&board).unwrap().unwrap();
+        let mut board_without_singles = board.clone();
+        while let Some(hint) = hint(&board_without_singles).filter(|hint| hint.eliminations.is_empty()) {
+            board_without_singles.set(hint.row as usize, hint.col as usize, hint.digit).unwrap();
+        }
+
+        let hint = hint(&board_without_singles).unwrap();
+
+        assert!(!hint.eliminations.is_empty());
+        assert_eq!(solution.get(hint.row as usize, hint.col as usize).unwrap().get(), hint.digit);
+    }
+
+    #[test]
+    fn test_hint_none() {
+        let mut conflicting = get_board1();
+        conflicting.set(0, 2, 5).unwrap();
+
+        assert_eq!(hint(&get_board1_solved()), None);
+        assert_eq!(hint(&conflicting), None);
+    }
+
+    #[rstest]
+    #[case("data/sudoku.txt", false)]
+    #[case("data/sudoku_evil.txt", false)]
+    #[case("data/sudoku_ai_escargot.txt", true)]
+    fn test_solve_with_steps(#[case] filepath: &str, #[case] needs_guesses: bool) {
+        let board = Board::read_from_file(filepath).unwrap();
+
+        let trace = solve_with_steps(&board).unwrap().unwrap();
+
+        assert_eq!(Some(trace.solution()), solve_sudoku_with_exact_cover(&board).unwrap());
+        assert_eq!(trace.moves.len(), board.empty_cells().len());
+        assert_eq!(trace.num_guesses() > 0, needs_guesses);
+    }
+
+    #[rstest]
+    #[case("data/sudoku.txt", false)]
+    #[case("data/sudoku_ai_escargot.txt", true)]
+    fn test_rate_by_techniques(#[case] filepath: &str, #[case] needs_guesses: bool) {
+        let board = Board::read_from_file(filepath).unwrap();
+
+        let effort = rate_by_techniques(&board).unwrap().unwrap();
+        let trace = solve_with_steps(&board).unwrap().unwrap();
+
+        assert_eq!(effort.num_bifurcations, trace.num_guesses() as u64);
+        assert_eq!(effort.num_bifurcations > 0, needs_guesses);
+        assert_eq!(effort.technique_counts.values().sum::<u64>(), effort.num_steps as u64);
+        let hardest_step = effort.hardest_step.unwrap();
+        assert!(hardest_step < effort.num_steps);
+        assert!(effort.technique_counts.contains_key(effort.hardest_technique.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn test_rate_by_techniques_hardest_step() {
+        let board = Board::read_from_file("data/sudoku_evil.txt").unwrap();
+        let steps = solve_logically(&board).steps;
+        let hardest = steps.iter().map(|step| step.technique).max().unwrap();
+
+        let effort = rate_by_techniques(&board).unwrap().unwrap();
+
+        assert_eq!(effort.hardest_step, steps.iter().position(|step| step.technique == hardest));
+        assert_eq!(effort.hardest_technique, Some(hardest.to_string()));
+        assert_eq!(rate_by_techniques(&Board::read_from_file("data/sudoku_unsolvable.txt").unwrap()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_solve_with_steps_unsolvable() {
+        let board = Board::read_from_file("data/sudoku_unsolvable.txt").unwrap();
+
+        assert_eq!(solve_with_steps(&board).unwrap(), None);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_solve_sudokus_keeps_order() {
+        let boards: Vec<Board> = ["data/sudoku_easy.txt", "data/sudoku_unsolvable.txt", "data/sudoku_hard.txt", "data/sudoku.txt"]
+            .iter()
+            .map(|filename| Board::read_from_file(filename).unwrap())
+            .collect();
+
+        let solutions = solve_sudokus(&boards);
+
+        assert_eq!(solutions.len(), boards.len());
+        for (board, solution) in boards.iter().zip(solutions) {
+            assert_eq!(solution.ok(), solve_sudoku_with_exact_cover(board).ok());
+        }
+    }
+
+    #[test]
+    fn test_format_with_givens() {
+        let solution = get_board1_solved();
+
+        let formatted = solution.format_with_givens(&get_board1(), &DigitSymbols::DIGITS);
+
+        assert!(formatted.starts_with("\x1b[1;34m5\x1b[0m\x1b[1;34m3\x1b[0m\x1b[32m4\x1b[0m "));
+        let without_colors = formatted.replace(GIVEN_COLOR, "").replace(FILLED_COLOR, "").replace(RESET_COLOR, "");
+        assert_eq!(without_colors, solution.to_string());
+        assert_eq!(formatted.matches(GIVEN_COLOR).count(), 31);
+        assert_eq!(formatted.matches(FILLED_COLOR).count(), 50);
+    }
+
+    #[test]
+    fn test_solve_result_format_colored() {
+        let result = solve_sudoku(&get_board1()).unwrap();
+        let unsolvable = SolveResult { puzzle: get_board1(), solution: None, unique: false };
+
+        let formatted = result.format_colored(&DigitSymbols::DIGITS);
+
+        assert_eq!(formatted, Some(get_board1_solved().format_with_givens(&get_board1(), &DigitSymbols::DIGITS)));
+        assert_eq!(unsolvable.format_colored(&DigitSymbols::DIGITS), None);
+    }
+
+    #[test]
+    fn test_to_pretty_string() {
+        let board = get_board1();
+
+        let pretty = board.to_pretty_string();
+
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "┌───────┬───────┬───────┐");
+        assert_eq!(lines[1], "│ 5 3   │   7   │       │");
+        assert_eq!(lines[4], "├───────┼───────┼───────┤");
+        assert_eq!(lines[12], "└───────┴───────┴───────┘");
+        assert_eq!(board.pretty().to_string(), pretty);
+    }
+
+    #[test]
+    fn test_transform_board() {
+        let board = get_board1();
+
+        assert_eq!(board.transpose().to_line()[..9], *"56.847...");
+        assert_eq!(board.rotate90().to_line()[..9], *"...748.65");
+        assert_eq!(board.mirror().to_line()[..9], *"....7..35");
+        assert_eq!(board.permute_digits([9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap().to_line()[..9], *"57..3....");
+        assert_eq!(board.swap_bands(0, 2).unwrap().to_line()[..27], board.to_line()[54..]);
+        assert_eq!(board.swap_stacks(1, 1).unwrap(), board);
+        assert_eq!(board.rotate90().rotate90().rotate90().rotate90(), board);
+        assert_eq!(board.mirror().mirror(), board);
+    }
+
+    #[rstest]
+    #[case(get_board1().swap_bands(0, 3), TransformationError::InvalidIndex(3))]
+    #[case(get_board1().swap_stacks(4, 1), TransformationError::InvalidIndex(4))]
+    #[case(get_board1().permute_digits([1, 1, 3, 4, 5, 6, 7, 8, 9]), TransformationError::InvalidDigits([1, 1, 3, 4, 5, 6, 7, 8, 9]))]
+    fn test_transform_board_invalid(#[case] result: Result<Board, TransformationError>, #[case] expected: TransformationError) {
+        assert_eq!(result, Err(expected));
+    }
+
+    #[rstest]
+    #[case(get_board1().transpose(), get_board1_solved().transpose())]
+    #[case(get_board1().rotate90(), get_board1_solved().rotate90())]
+    #[case(get_board1().mirror(), get_board1_solved().mirror())]
+    #[case(get_board1().permute_digits([2, 3, 4, 5, 6, 7, 8, 9, 1]).unwrap(), get_board1_solved().permute_digits([2, 3, 4, 5, 6, 7, 8, 9, 1]).unwrap())]
+    #[case(get_board1().swap_bands(1, 2).unwrap().swap_stacks(0, 1).unwrap(), get_board1_solved().swap_bands(1, 2).unwrap().swap_stacks(0, 1).unwrap())]
+    fn test_solve_transformed_board(#[case] board: Board, #[case] expected_solution: Board) {
+        assert_eq!(solve_sudoku_with_exact_cover(&board).unwrap(), Some(expected_solution));
+    }
+
+    #[test]
+    fn test_rate_filled_board_by_search_effort() {
+        let mut invalid = get_board1_solved();
+        invalid.set(0, 0, 1).unwrap();
+
+        assert_eq!(rate_by_search_effort(&get_board1_solved()).unwrap().num_solutions, 1);
+        assert_eq!(rate_by_search_effort(&invalid).unwrap().num_solutions, 0);
+    }
+
+    #[test]
+    fn test_solve_sudoku_with_observers_and_memory_limit() {
+        let board = Board::read_from_file("data/sudoku_ai_escargot.txt").unwrap();
+
+        let result = solve_sudoku_with_observers_and_memory_limit(&board, &mut [], Some(0));
+
+        assert!(matches!(result, Err(SolveError::SearchAborted(SearchError::MemoryLimitExceeded(0)))), "{:?}", result);
+    }
+
+    #[rstest]
+    #[case("r3c5", true, Violation::MultipleDigitsInCell { row: 3, col: 5 })]
+    #[case("r8c0", false, Violation::NoDigitForCell { row: 8, col: 0 })]
+    #[case("r2d7", true, Violation::DuplicateDigit { digit: 7, unit: Unit::Row, idx: 2 })]
+    #[case("c4d1", false, Violation::UnplaceableDigit { digit: 1, unit: Unit::Column, idx: 4 })]
+    #[case("b8d9", true, Violation::DuplicateDigit { digit: 9, unit: Unit::Block, idx: 8 })]
+    fn test_violated_rule(#[case] item_name: &str, #[case] covered_twice: bool, #[case] expected: Violation) {
+        assert_eq!(violated_rule(item_name, covered_twice), expected);
+    }
+
+    #[test]
+    fn test_sudoku_items_match_encoding() {
+        let exact_cover_problem = convert_to_exact_cover_problem(&Board::empty()).unwrap();
+
+        let items = sudoku_items();
+
+        assert_eq!(items.len(), 324);
+        assert!(exact_cover_problem.items().iter().all(|item_name| items.contains_key(*item_name)));
+    }
+
+    #[test]
+    fn test_solve_sudoku_with_backend() {
+        let backends: [&dyn SolverBackend; 3] = [&GeneralBackend, &BitsetBackend, &DancingCellsBackend];
+        for backend in backends {
+            let solution = solve_sudoku_with_backend(&get_board1(), backend).unwrap();
+
+            assert_eq!(solution, Some(get_board1_solved()), "{}", backend.name());
+        }
+    }
+
+    #[test]
+    fn test_solve_sudoku_with_backend_and_stats() {
+        let backends: [&dyn SolverBackend; 3] = [&GeneralBackend, &BitsetBackend, &DancingCellsBackend];
+        for backend in backends {
+            let (solution, stats) = solve_sudoku_with_backend_and_stats(&get_board1(), backend).unwrap();
+            assert_eq!(solution, Some(get_board1_solved()), "{}", backend.name());
+            assert!(stats.num_nodes > 0, "{}", backend.name());
+
+            let (solution, stats) = solve_sudoku_with_backend_and_stats(&get_board1_solved(), backend).unwrap();
+            assert_eq!(solution, Some(get_board1_solved()), "{}", backend.name());
+            assert_eq!(stats.num_nodes, 0, "{}", backend.name());
+        }
+    }
+
+    #[test]
+    fn test_exact_cover_problem_stats() {
+        let board = get_board1();
+
+        let stats = convert_to_exact_cover_problem(&board).unwrap().stats();
+
+        // Every cell, row, column and block constraint can be satisfied by exactly 9 of the 729 cell options
+        assert_eq!(stats.num_items, 324);
+        assert_eq!(stats.num_options, 729);
+        assert_eq!(stats.num_required_options, 31);
+        assert_eq!(stats.min_options_per_item, 9);
+        assert_eq!(stats.max_options_per_item, 9);
+    }
+
+    #[test]
+    fn test_set_row_col_and_box() {
+        let solved = get_board1_solved();
+        let mut board = Board::empty();
+
+        board.set_row(0, [5, 3, 4, 6, 7, 8, 9, 1, 2]).unwrap();
+        board.set_col(8, [2, 8, 7, 3, 1, 6, 4, 5, 9]).unwrap();
+        board.set_box(4, [7, 6, 1, 8, 5, 3, 9, 2, 4]).unwrap();
+
+        for i in 0..9 {
+            assert_eq!(board.0[0][i], solved.0[0][i]);
+            assert_eq!(board.0[i][8], solved.0[i][8]);
+            assert_eq!(board.0[3 + i / 3][3 + i % 3], solved.0[3 + i / 3][3 + i % 3]);
+        }
+    }
+
+    #[rstest]
+    #[case(9, [0; 9], BoardEditError::InvalidIndex(9))]
+    #[case(0, [1, 2, 3, 4, 5, 6, 7, 8, 10], BoardEditError::InvalidDigit(10))]
+    #[case(0, [1, 0, 0, 0, 0, 0, 0, 0, 1], BoardEditError::DuplicateDigit(1))]
+    fn test_set_row_invalid(#[case] i: usize, #[case] digits: [u8; 9], #[case] expected_error: BoardEditError) {
+        let mut board = get_board1();
+
+        let result = board.set_row(i, digits);
+
+        assert_eq!(result, Err(expected_error));
+        assert_eq!(board, get_board1());
+    }
+
+    #[test]
+    fn test_try_from_rows() {
+        let rows: Vec<Vec<u8>> = (0..9).map(|i| get_board1().0[i].clone()).collect();
+
+        let board = Board::try_from_rows(rows);
+
+        assert_eq!(board.unwrap(), get_board1());
+        assert_eq!(Board::empty().empty_cells().len(), 81);
+    }
+
+    #[rstest]
+    #[case(vec![vec![0; 9]; 8], BoardError::InvalidSize)]
+    #[case(vec![vec![0; 9], vec![0; 10], vec![0; 9], vec![0; 9], vec![0; 9], vec![0; 9], vec![0; 9], vec![0; 9], vec![0; 9]],
+           BoardError::InvalidSize)]
+    #[case(vec![vec![0; 9], vec![0; 9], vec![0, 0, 0, 0, 10, 0, 0, 0, 0], vec![0; 9], vec![0; 9], vec![0; 9], vec![0; 9],
+                vec![0; 9], vec![0; 9]],
+           BoardError::InvalidDigit { row: 2, col: 4, digit: 10 })]
+    fn test_try_from_rows_invalid(#[case] rows: Vec<Vec<u8>>, #[case] expected_error: BoardError) {
+        let result = Board::try_from_rows(rows);
+
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn test_try_from_array() {
+        let mut rows = [[0u8; 9]; 9];
+        rows[0][0] = 5;
+
+        let board = Board::try_from(rows).unwrap();
+        rows[8][8] = 11;
+
+        assert_eq!(board.get(0, 0), NonZeroU8::new(5));
+        assert_eq!(board.empty_cells().len(), 80);
+        assert_eq!(Board::try_from(rows), Err(BoardError::InvalidDigit { row: 8, col: 8, digit: 11 }));
+    }
+
+    #[test]
+    fn test_get_set_and_clear() {
+        let mut board = get_board1();
+
+        board.set(0, 2, 4).unwrap();
+        board.clear(0, 0).unwrap();
+
+        assert_eq!(board.get(0, 1), NonZeroU8::new(3));
+        assert_eq!(board.get(0, 2), NonZeroU8::new(4));
+        assert_eq!(board.get(0, 0), None);
+        assert_eq!(board.get(9, 0), None);
+    }
+
+    #[rstest]
+    #[case(9, 0, 1, BoardEditError::InvalidIndex(9))]
+    #[case(0, 10, 1, BoardEditError::InvalidIndex(10))]
+    #[case(0, 0, 0, BoardEditError::InvalidDigit(0))]
+    #[case(0, 0, 10, BoardEditError::InvalidDigit(10))]
+    fn test_set_invalid(#[case] i: usize, #[case] j: usize, #[case] digit: u8, #[case] expected_error: BoardEditError) {
+        let mut board = get_board1();
+
+        let result = board.set(i, j, digit);
+
+        assert_eq!(result, Err(expected_error));
+        assert_eq!(board, get_board1());
+    }
+
+    #[test]
+    fn test_clear_invalid() {
+        let mut board = get_board1();
+
+        let result = board.clear(0, 9);
+
+        assert_eq!(result, Err(BoardEditError::InvalidIndex(9)));
+    }
+
+    #[test]
+    fn test_iter_cells() {
+        let board = get_board1();
+
+        let cells: Vec<(usize, usize, Option<NonZeroU8>)> = board.iter_cells().collect();
+
+        assert_eq!(cells.len(), 81);
+        assert_eq!(cells[0], (0, 0, NonZeroU8::new(5)));
+        assert_eq!(cells[80], (8, 8, NonZeroU8::new(9)));
+        assert_eq!(cells.iter().filter(|(_, _, digit)| digit.is_none()).count(), board.empty_cells().len());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut board = get_board1();
+        board.set(0, 2, 5).unwrap();
+        board.set(8, 0, 8).unwrap();
+
+        let conflicts = board.validate().unwrap_err();
+
+        assert_eq!(conflicts, vec![
+            Conflict { digit: 5, unit: Unit::Row, idx: 0, first: (0, 0), second: (0, 2) },
+            Conflict { digit: 8, unit: Unit::Row, idx: 8, first: (8, 0), second: (8, 4) },
+            Conflict { digit: 8, unit: Unit::Column, idx: 0, first: (3, 0), second: (8, 0) },
+            Conflict { digit: 5, unit: Unit::Block, idx: 0, first: (0, 0), second: (0, 2) },
+        ]);
+        assert_eq!(conflicts[0].to_string(), "digit 5 appears more than once in row 1: in row 1, column 1 and row 1, column 3");
+        assert_eq!(get_board1().validate(), Ok(()));
+        assert_eq!(get_board1_solved().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_is_filled() {
+        assert!(!get_board1().is_filled());
+        assert!(get_board1_solved().is_filled());
+    }
+
+    #[test]
+    fn test_solve_filled_board() {
+        let board = get_board1_solved();
+
+        let solution = solve_sudoku_with_exact_cover(&board).unwrap();
+
+        assert_eq!(solution, Some(board));
+    }
+
+    #[test]
+    fn test_solve_filled_board_with_conflict() {
+        let mut board = get_board1_solved();
+        // Swapping two digits in a row keeps the row valid but breaks both columns
+        board.0[0].swap(0, 1);
+
+        let solution = solve_sudoku_with_exact_cover(&board).unwrap();
+
+        assert_eq!(solution, None);
+    }
+
+    #[test]
+    fn test_solve_with_candidates_full() {
+        let board = get_board1();
+
+        let solution = solve_with_candidates(&board, &CandidateGrid::full()).unwrap();
+
+        assert_eq!(solution, Some(get_board1_solved()));
+    }
+
+    #[test]
+    fn test_solve_with_candidates_excluding_solution_digit() {
+        let board = get_board1();
+        let mut candidates = CandidateGrid::full();
+        candidates.remove(0, 2, 4).unwrap();
+
+        let solution = solve_with_candidates(&board, &candidates).unwrap();
+
+        assert_eq!(solution, None);
+    }
+
+    #[test]
+    fn test_solve_with_candidates_excluding_given() {
+        let board = get_board1();
+        let mut candidates = CandidateGrid::full();
+        candidates.restrict(0, 0, &[1, 2]).unwrap();
+
+        let solution = solve_with_candidates(&board, &candidates).unwrap();
+
+        assert_eq!(solution, None);
+    }
+
+    #[test]
+    fn test_read_candidates_from_file() {
+        let text = std::fs::read_to_string("data/sukaku.txt").unwrap();
+
+        let candidates = CandidateGrid::read_from_file("data/sukaku.txt").unwrap();
+
+        assert_eq!(candidates, Board::read_from_file("data/sudoku_hard.txt").unwrap().candidates());
+        assert_eq!(candidates.to_string(), text);
+    }
+
+    #[test]
+    fn test_read_candidates_as_lists() {
+        let text = std::fs::read_to_string("data/sukaku.txt").unwrap();
+        let lists: Vec<String> = text.split_whitespace().map(|cell| cell.replace('.', "")).collect();
+
+        let candidates = CandidateGrid::read_from_str(&lists.join(" ")).unwrap();
+
+        assert_eq!(candidates, CandidateGrid::read_from_str(&text).unwrap());
+        assert_eq!(candidates.digits(0, 0), vec![3, 4, 5, 9]);
+    }
+
+    #[rstest]
+    #[case("123 456".to_string())]
+    #[case("123456789".repeat(80))]
+    fn test_read_candidates_invalid_size(#[case] s: String) {
+        assert!(matches!(CandidateGrid::read_from_str(&s), Err(BoardReadError::InvalidSize)));
+    }
+
+    #[test]
+    fn test_read_candidates_invalid_character() {
+        let s = "123 ".repeat(80) + "12x";
+
+        assert!(matches!(CandidateGrid::read_from_str(&s), Err(BoardReadError::InvalidCharacter('x'))));
+    }
+
+    #[test]
+    fn test_solve_sukaku() {
+        let candidates = CandidateGrid::read_from_file("data/sukaku.txt").unwrap();
+
+        let solution = solve_sukaku(&candidates).unwrap();
+
+        assert_eq!(solution, solve_sudoku_with_exact_cover(&Board::read_from_file("data/sudoku_hard.txt").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_solve_sukaku_without_solution() {
+        let mut candidates = CandidateGrid::full();
+        candidates.restrict(0, 0, &[1]).unwrap();
+        candidates.restrict(0, 1, &[1, 2]).unwrap();
+        candidates.restrict(0, 2, &[2]).unwrap();
+
+        assert_eq!(solve_sukaku(&candidates).unwrap(), None);
+    }
+
+    #[test]
+    fn test_solve_with_candidates_on_empty_board() {
+        let board = Board::empty();
+        let solved = get_board1_solved();
+        let mut candidates = CandidateGrid::full();
+        for i in 0..9 {
+            candidates.restrict(i, i, &[solved.0[i][i]]).unwrap();
+            candidates.restrict(i, 8 - i, &[solved.0[i][8 - i]]).unwrap();
+        }
+
+        let solution = solve_with_candidates(&board, &candidates).unwrap();
+
+        assert!(solution.is_some());
+        let solution = solution.unwrap();
+        for i in 0..9 {
+            assert_eq!(solution.0[i][i], solved.0[i][i]);
+            assert_eq!(solution.0[i][8 - i], solved.0[i][8 - i]);
+        }
+        assert_valid_sudoku_solution(solution);
+    }
+
+    #[test]
+    fn test_board_candidates() {
+        let candidates = get_board1().candidates();
+
+        assert_eq!(candidates.digits(0, 0), vec![5]);
+        assert_eq!(candidates.digits(0, 2), vec![1, 2, 4]);
+        assert_eq!(candidates.digits(4, 4), vec![5]);
+        assert_eq!(candidates.contradiction(), None);
+        assert_eq!(Board::empty().candidates(), CandidateGrid::full());
+    }
+
+    #[test]
+    fn test_candidate_grid_place() {
+        let mut candidates = CandidateGrid::full();
+
+        candidates.place(4, 4, 7).unwrap();
+
+        assert_eq!(candidates.digits(4, 4), vec![7]);
+        assert!(!candidates.contains(4, 0, 7) && !candidates.contains(0, 4, 7) && !candidates.contains(3, 5, 7));
+        assert!(candidates.contains(3, 0, 7) && candidates.contains(4, 0, 6));
+    }
+
+    #[test]
+    fn test_candidate_grid_invalid_arguments() {
+        let mut candidates = CandidateGrid::full();
+
+        assert_eq!(candidates.remove(9, 0, 1), Err(BoardEditError::InvalidIndex(9)));
+        assert_eq!(candidates.remove(0, 0, 16), Err(BoardEditError::InvalidDigit(16)));
+        assert_eq!(candidates.restrict(0, 12, &[1]), Err(BoardEditError::InvalidIndex(12)));
+        assert_eq!(candidates.restrict(0, 0, &[1, 0]), Err(BoardEditError::InvalidDigit(0)));
+        assert_eq!(candidates.place(4, 4, 10), Err(BoardEditError::InvalidDigit(10)));
+        assert_eq!(candidates, CandidateGrid::full());
+        assert!(!candidates.contains(9, 9, 1));
+        assert!(!candidates.contains(0, 0, 0) && !candidates.contains(0, 0, 20));
+    }
+
+    #[test]
+    fn test_candidate_grid_contradiction() {
+        let mut conflicting = get_board1();
+        conflicting.set(0, 2, 5).unwrap();
+        let mut no_digit_for_row = CandidateGrid::full();
+        for j in 0..9 {
+            no_digit_for_row.remove(2, j, 6).unwrap();
+        }
+        let mut no_digit_for_cell = no_digit_for_row.clone();
+        no_digit_for_cell.restrict(8, 8, &[]).unwrap();
+
+        assert_eq!(conflicting.candidates().contradiction(), Some(Violation::NoDigitForCell { row: 0, col: 0 }));
+        assert_eq!(no_digit_for_row.contradiction(), Some(Violation::UnplaceableDigit { digit: 6, unit: Unit::Row, idx: 2 }));
+        assert_eq!(no_digit_for_cell.contradiction(), Some(Violation::NoDigitForCell { row: 8, col: 8 }));
+    }
+
+    #[test]
+    fn test_candidate_grid_digits() {
+        let mut candidates = CandidateGrid::full();
+        candidates.restrict(4, 4, &[2, 7, 9]).unwrap();
+        candidates.remove(4, 4, 7).unwrap();
+
+        assert_eq!(candidates.digits(4, 4), vec![2, 9]);
+        assert_eq!(candidates.digits(0, 0), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[rstest]
+    #[case("sudoku_easy.txt")]
+    #[case("sudoku_medium.txt")]
+    #[case("sudoku_hard.txt")]
+    #[case("sudoku_hardest.txt")]
+    #[case("sudoku_evil.txt")]
+    #[case("sudoku_ai_escargot.txt")]
+    fn test_solve_sudoku_different_difficulties(#[case] filename: &str) {
+        let board = Board::read_from_file(&format!("data/{}", filename)).unwrap();
+
+        let solution = solve_sudoku_with_exact_cover(&board).unwrap();
+
+        assert!(solution.is_some());
+        let solution = solution.unwrap();
+        assert_valid_sudoku_solution(solution);
+    }
+
+    #[rstest]
+    #[case("sudoku_easy.txt")]
+    #[case("sudoku_medium.txt")]
+    #[case("sudoku_hard.txt")]
+    #[case("sudoku_ai_escargot.txt")]
+    fn test_solve_sudoku_with_forced_option_propagation(#[case] filename: &str) {
+        let board = Board::read_from_file(&format!("data/{}", filename)).unwrap();
+        let exact_cover_problem = convert_to_exact_cover_problem(&board).unwrap().with_forced_option_propagation(true);
+
+        let solution = exact_cover_problem.solve().map(|solution| convert_to_sudoku_solution(&exact_cover_problem, &solution));
+
+        assert!(solution.is_some());
+        assert_valid_sudoku_solution(solution.unwrap());
+    }
+
+    fn assert_valid_sudoku_solution(board: Board) {
+        // Check rows
+        for i in 0..9 {
+            let mut digits = [false; 9];
+            for j in 0..9 {
+                let digit = board.0[i][j];
+                assert_ne!(digit, 0, "Row {} has a cell with no digit", i);
+                assert!(!digits[(digit - 1) as usize], "Row {} has a duplicate digit {}", i, digit);
+                digits[(digit - 1) as usize] = true;
+            }
+        }
+
+        // Check columns
+        for j in 0..9 {
+            let mut digits = [false; 9];
+            for i in 0..9 {
+                let digit = board.0[i][j];
+                assert_ne!(digit, 0, "Column {} has a cell with no digit", j);
+                assert!(!digits[(digit - 1) as usize], "Column {} has a duplicate digit {}", j, digit);
+                digits[(digit - 1) as usize] = true;
+            }
+        }
+
+        // Check blocks
+        for block in 0..9 {
+            let mut digits = [false; 9];
+            for i in (block / 3 * 3)..(block / 3 * 3 + 3) {
+                for j in (block % 3 * 3)..(block % 3 * 3 + 3) {
+                    let digit = board.0[i][j];
+                    assert_ne!(digit, 0, "Block {} has a cell with no digit", block);
+                    assert!(!digits[(digit - 1) as usize], "Block {} has a duplicate digit {}", block, digit);
+                    digits[(digit - 1) as usize] = true;
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(CountEngine::Backtracking)]
+    #[case(CountEngine::Zdd)]
+    fn test_count_sudoku_solutions(#[case] engine: CountEngine) {
+        let board = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+        let num_solutions = convert_to_exact_cover_problem(&board).unwrap().count_all_solutions() as u128;
+
+        assert!(num_solutions > 1);
+        assert_eq!(count_sudoku_solutions(&board, engine).unwrap(), num_solutions);
+        assert_eq!(count_sudoku_solutions(&get_board1(), engine).unwrap(), 1);
+        assert_eq!(count_sudoku_solutions(&get_board1_solved(), engine).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let board = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+        let mut conflicting = get_board1();
+        conflicting.set(0, 2, 5).unwrap();
+
+        assert_eq!(count_solutions(&board, None) as u128, count_sudoku_solutions(&board, CountEngine::Zdd).unwrap());
+        assert_eq!(count_solutions(&board, Some(2)), 2);
+        assert_eq!(count_solutions(&Board::empty(), Some(100)), 100);
+        assert_eq!(count_solutions(&get_board1(), Some(100)), 1);
+        assert_eq!(count_solutions(&get_board1_solved(), None), 1);
+        assert_eq!(count_solutions(&conflicting, None), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be at least 1")]
+    fn test_count_solutions_with_zero_limit() {
+        count_solutions(&get_board1(), Some(0));
+    }
+
+    #[test]
+    fn test_solve_sudoku_lexicographically() {
+        let board = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+        let exact_cover_problem = convert_to_exact_cover_problem(&board).unwrap();
+        let mut solutions: Vec<Board> = Vec::new();
+        let _: std::ops::ControlFlow<()> = exact_cover_problem.for_each_solution(|selected_options| {
+            let solution = ExactCoverSolution { selected_options: selected_options.to_vec() };
+            solutions.push(convert_to_sudoku_solution(&exact_cover_problem, &solution));
+            std::ops::ControlFlow::Continue(())
+        });
+        let smallest_solution = solutions.into_iter().min_by_key(|solution| solution.0.clone()).unwrap();
+
+        let solution = solve_sudoku_lexicographically(&board).unwrap();
+
+        assert_eq!(solution, Some(smallest_solution));
+        assert_eq!(solve_sudoku_lexicographically(&get_board1()).unwrap(), Some(get_board1_solved()));
+    }
+
+    #[test]
+    fn test_rate_by_search_effort() {
+        let easy_board = Board::read_from_file("data/sudoku_easy.txt").unwrap();
+        let hardest_board = Board::read_from_file("data/sudoku_ai_escargot.txt").unwrap();
+
+        let easy_effort = rate_by_search_effort(&easy_board).unwrap();
+        let hardest_effort = rate_by_search_effort(&hardest_board).unwrap();
+
+        assert_eq!(easy_effort.num_solutions, 1);
+        assert_eq!(hardest_effort.num_solutions, 1);
+        assert!(easy_effort.num_forced_digits > hardest_effort.num_forced_digits);
+        assert!(easy_effort.difficulty() < hardest_effort.difficulty());
+    }
+
+    #[test]
+    fn test_rate_by_search_effort_is_reproducible() {
+        let board = Board::read_from_file("data/sudoku_ai_escargot.txt").unwrap();
+
+        let effort = rate_by_search_effort(&board).unwrap();
+
+        assert!(effort.num_backtracks > 0);
+        assert_eq!(rate_by_search_effort(&board).unwrap(), effort);
+    }
+
+    #[test]
+    fn test_rate_by_search_effort_multiple_solutions() {
+        let board = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+
+        let effort = rate_by_search_effort(&board).unwrap();
+
+        assert_eq!(effort.num_solutions, 2);
+        assert!(effort.num_nodes > 0);
+    }
+
+    #[test]
+    fn test_count_sudoku_solutions_with_cache() {
+        let dir = std::env::temp_dir().join(format!("rust-sudoku-cache-{}", std::process::id()));
+        let cache = SolutionCache::new(&dir);
+        let board = Board::read_from_file("data/sudoku_multiple_solutions.txt").unwrap();
+
+        let num_solutions = count_sudoku_solutions_with_cache(&board, CountEngine::Zdd, &cache).unwrap();
+        let cached_num_solutions = count_sudoku_solutions_with_cache(&board, CountEngine::Backtracking, &cache).unwrap();
+
+        assert_eq!(num_solutions, count_sudoku_solutions(&board, CountEngine::Zdd).unwrap());
+        assert_eq!(cached_num_solutions, num_solutions);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diagnose_encoding() {
+        assert_eq!(diagnose_encoding(&get_board1(), &get_board1_solved()).unwrap(), vec![]);
+
+        // Swapping two digits of the solution breaks the given in the top left cell and two of the columns
+        let mut wrong_solution = get_board1_solved();
+        wrong_solution.set_row(0, [3, 5, 4, 6, 7, 8, 9, 1, 2]).unwrap();
+        let issues = diagnose_encoding(&get_board1(), &wrong_solution).unwrap();
+
+        assert!(issues.contains(&EncodingIssue::MissingRequiredOption("r0c0d5".to_string())));
+        assert!(issues.contains(&EncodingIssue::UncoveredItem("c0d5".to_string())));
+        assert!(issues.iter().any(|issue| matches!(issue, EncodingIssue::OvercoveredItem { item, .. } if item == "c0d3")));
+    }
+}
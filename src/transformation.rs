@@ -0,0 +1,543 @@
+use thiserror::Error;
+
+use crate::sudoku::Board;
+
+/**
+ * A symmetry of Sudoku: a combination of transposing the board, permuting its bands and stacks, permuting the rows
+ * within each band and the columns within each stack, and relabeling the digits. Applying it to a valid board gives a
+ * valid board, and applying it to a puzzle gives a puzzle with the same number of solutions.
+ *
+ * Transformations are built from the basic operations below and combined with compose, so that scripts can walk the
+ * orbit of a puzzle under the whole group.
+ */
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Transformation {
+    /// Whether the board is transposed before the rows and columns are permuted
+    transpose: bool,
+    /// For each row of the result, the row of the (transposed) board it is taken from
+    row_order: [usize; 9],
+    /// For each column of the result, the column of the (transposed) board it is taken from
+    col_order: [usize; 9],
+    /// For each digit, the digit it is relabeled to. Index 0 maps empty cells to themselves
+    digit_map: [u8; 10],
+}
+
+// TransformationError is a custom error type for transformations that are not symmetries of Sudoku.
+#[derive(Debug, PartialEq, Error)]
+pub enum TransformationError {
+    #[error("band or stack {0} is out of range, it must be between 0 and 2")]
+    InvalidIndex(usize),
+    #[error("{0:?} is not a permutation of 0, 1 and 2")]
+    InvalidOrder([usize; 3]),
+    #[error("{0:?} is not a permutation of the digits 1 to 9")]
+    InvalidDigits([u8; 9]),
+}
+
+/// The order which leaves the rows or columns where they are
+const IDENTITY_ORDER: [usize; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+
+impl Transformation {
+    /**
+     * The transformation which leaves every board unchanged.
+     */
+    pub fn identity() -> Transformation {
+        return Transformation {
+            transpose: false,
+            row_order: IDENTITY_ORDER,
+            col_order: IDENTITY_ORDER,
+            digit_map: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        };
+    }
+
+    /**
+     * Mirror the board in its main diagonal, so that rows become columns.
+     */
+    pub fn transpose() -> Transformation {
+        return Transformation { transpose: true, ..Transformation::identity() };
+    }
+
+    /**
+     * Mirror the board in its vertical axis, so that the columns are in reverse order.
+     */
+    pub fn mirror() -> Transformation {
+        return Transformation { col_order: IDENTITY_ORDER.map(|col| 8 - col), ..Transformation::identity() };
+    }
+
+    /**
+     * Rotate the board a quarter turn clockwise, so that the first column becomes the first row read from the bottom.
+     */
+    pub fn rotate90() -> Transformation {
+        return Transformation::transpose().compose(&Transformation::mirror());
+    }
+
+    /**
+     * Swap two bands (groups of 3 rows), leaving the third where it is.
+     */
+    pub fn swap_bands(band: usize, other_band: usize) -> Result<Transformation, TransformationError> {
+        return Transformation::permute_bands(swapped_order(band, other_band)?);
+    }
+
+    /**
+     * Swap two stacks (groups of 3 columns), leaving the third where it is.
+     */
+    pub fn swap_stacks(stack: usize, other_stack: usize) -> Result<Transformation, TransformationError> {
+        return Transformation::permute_stacks(swapped_order(stack, other_stack)?);
+    }
+
+    /**
+     * Reorder the bands (groups of 3 rows), such that band b of the result is band order[b] of the board.
+     */
+    pub fn permute_bands(order: [usize; 3]) -> Result<Transformation, TransformationError> {
+        return Ok(Transformation { row_order: lines_in_order(order)?, ..Transformation::identity() });
+    }
+
+    /**
+     * Reorder the stacks (groups of 3 columns), such that stack s of the result is stack order[s] of the board.
+     */
+    pub fn permute_stacks(order: [usize; 3]) -> Result<Transformation, TransformationError> {
+        return Ok(Transformation { col_order: lines_in_order(order)?, ..Transformation::identity() });
+    }
+
+    /**
+     * Reorder the rows within a band, such that row k of the band in the result is row order[k] of the band.
+     */
+    pub fn permute_rows_in_band(band: usize, order: [usize; 3]) -> Result<Transformation, TransformationError> {
+        return Ok(Transformation { row_order: lines_in_group_in_order(band, order)?, ..Transformation::identity() });
+    }
+
+    /**
+     * Reorder the columns within a stack, such that column k of the stack in the result is column order[k] of it.
+     */
+    pub fn permute_columns_in_stack(stack: usize, order: [usize; 3]) -> Result<Transformation, TransformationError> {
+        return Ok(Transformation { col_order: lines_in_group_in_order(stack, order)?, ..Transformation::identity() });
+    }
+
+    /**
+     * Relabel the digits, such that digit d becomes digits[d - 1].
+     */
+    pub fn relabel_digits(digits: [u8; 9]) -> Result<Transformation, TransformationError> {
+        let mut digit_map = [0; 10];
+        let mut seen_digits = 0u16;
+        for (idx, digit) in digits.into_iter().enumerate() {
+            if !(1..=9).contains(&digit) || seen_digits & (1 << digit) != 0 {
+                return Err(TransformationError::InvalidDigits(digits));
+            }
+            seen_digits |= 1 << digit;
+            digit_map[idx + 1] = digit;
+        }
+        return Ok(Transformation { digit_map, ..Transformation::identity() });
+    }
+
+    /**
+     * Transform the board.
+     */
+    pub fn apply(&self, board: &Board) -> Board {
+        return Board::from_fn(|i, j| {
+            let (row, col) = self.source_cell(i, j);
+            self.digit_map[board.cell(row, col) as usize]
+        });
+    }
+
+    /**
+     * The transformation which undoes this one, i.e. composing them in either order gives the identity.
+     */
+    pub fn inverse(&self) -> Transformation {
+        let mut digit_map = [0; 10];
+        for (digit, mapped_digit) in self.digit_map.iter().enumerate() {
+            digit_map[*mapped_digit as usize] = digit as u8;
+        }
+        // Composing with this one must give the identity, see compose
+        let (row_order, col_order) = match self.transpose {
+            true => (invert(&self.col_order), invert(&self.row_order)),
+            false => (invert(&self.row_order), invert(&self.col_order)),
+        };
+        return Transformation { transpose: self.transpose, row_order, col_order, digit_map };
+    }
+
+    /**
+     * The transformation which applies this one first and then the other one.
+     */
+    pub fn compose(&self, other: &Transformation) -> Transformation {
+        // Cell (i, j) of the result is taken from the cell of this transformation's result which the other one takes
+        // it from. If the other one transposes, its row order picks from this one's columns and vice versa.
+        let (row_source, col_source) = match other.transpose {
+            true => (&self.col_order, &self.row_order),
+            false => (&self.row_order, &self.col_order),
+        };
+        return Transformation {
+            transpose: self.transpose != other.transpose,
+            row_order: other.row_order.map(|row| row_source[row]),
+            col_order: other.col_order.map(|col| col_source[col]),
+            digit_map: self.digit_map.map(|digit| other.digit_map[digit as usize]),
+        };
+    }
+
+    /**
+     * The transformation which turns the board into its canonical form: of all boards it can be transformed into, the
+     * one whose line of 81 cells (with 0 for empty cells) is lexicographically smallest. Every transformation of the
+     * board has the same canonical form, so boards are isomorphic exactly when their canonical forms are equal.
+     *
+     * Only transformations which relabel the digits in the order in which they first appear can give the smallest
+     * line, so it searches the 2 * 1296 * 1296 orders of the lines, row by row, and stops following an order as soon
+     * as its first rows are larger than the smallest line found. Boards with few givens or many symmetries prune
+     * poorly and take longer.
+     */
+    pub fn to_canonical_form(board: &Board) -> Transformation {
+        let mut search = CanonicalSearch { best_line: [u8::MAX; 81], best: Transformation::identity() };
+        for transpose in [false, true] {
+            let grid: [[u8; 9]; 9] = std::array::from_fn(|i| {
+                std::array::from_fn(|j| if transpose { board.cell(j, i) } else { board.cell(i, j) })
+            });
+            for col_order in all_line_orders() {
+                let state = CanonicalState { transpose, row_order: [0; 9], digit_map: [0; 10], num_labels: 0, line: [0; 81] };
+                search.search_rows(&grid, &col_order, &state, 0, 0);
+            }
+        }
+        return search.best;
+    }
+
+    /**
+     * The cell of the board that cell (i, j) of the result is taken from.
+     */
+    fn source_cell(&self, i: usize, j: usize) -> (usize, usize) {
+        return match self.transpose {
+            true => (self.col_order[j], self.row_order[i]),
+            false => (self.row_order[i], self.col_order[j]),
+        };
+    }
+}
+
+impl Default for Transformation {
+    fn default() -> Self {
+        return Transformation::identity();
+    }
+}
+
+/// The 6 orders of 3 bands, stacks or lines within one of them
+const ORDERS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+/**
+ * The smallest line found so far by Transformation::to_canonical_form, and the transformation which gives it.
+ */
+struct CanonicalSearch {
+    best_line: [u8; 81],
+    best: Transformation,
+}
+
+/**
+ * A partial transformation in Transformation::to_canonical_form, of which the first rows have been chosen.
+ */
+#[derive(Clone)]
+struct CanonicalState {
+    transpose: bool,
+    row_order: [usize; 9],
+    /// The label of every digit that has appeared so far, or 0 if it hasn't
+    digit_map: [u8; 10],
+    num_labels: u8,
+    /// The cells of the chosen rows, relabeled
+    line: [u8; 81],
+}
+
+impl CanonicalSearch {
+    /**
+     * Choose the row at position k of the result from the rows which are not yet used (a bitmask) and which keep the
+     * bands together, and follow every choice whose line isn't larger than the best one.
+     */
+    fn search_rows(&mut self, grid: &[[u8; 9]; 9], col_order: &[usize; 9], state: &CanonicalState, k: usize, used_rows: u16) {
+        if k == 9 {
+            if state.line < self.best_line {
+                self.best_line = state.line;
+                self.best = state.to_transformation(col_order);
+            }
+            return;
+        }
+        let starts_band = k.is_multiple_of(3);
+        let first_row = if starts_band { 0 } else { state.row_order[k - k % 3] / 3 * 3 };
+        let last_row = if starts_band { 9 } else { first_row + 3 };
+        for row in first_row..last_row {
+            // A band is started with any of its rows, as long as none of them is used yet
+            let band_rows = if starts_band { 0b111 << (row / 3 * 3) } else { 1 << row };
+            if used_rows & band_rows != 0 {
+                continue;
+            }
+            let mut next = state.clone();
+            next.row_order[k] = row;
+            for (j, col) in col_order.iter().enumerate() {
+                let digit = grid[row][*col] as usize;
+                if digit != 0 && next.digit_map[digit] == 0 {
+                    next.num_labels += 1;
+                    next.digit_map[digit] = next.num_labels;
+                }
+                next.line[k * 9 + j] = next.digit_map[digit];
+            }
+            let end = (k + 1) * 9;
+            if next.line[..end] <= self.best_line[..end] {
+                self.search_rows(grid, col_order, &next, k + 1, used_rows | (1 << row));
+            }
+        }
+    }
+}
+
+impl CanonicalState {
+    /**
+     * The transformation of a complete state. Digits that never appeared get the remaining labels in order.
+     */
+    fn to_transformation(&self, col_order: &[usize; 9]) -> Transformation {
+        let mut digit_map = self.digit_map;
+        let mut num_labels = self.num_labels;
+        for label in digit_map.iter_mut().skip(1) {
+            if *label == 0 {
+                num_labels += 1;
+                *label = num_labels;
+            }
+        }
+        return Transformation { transpose: self.transpose, row_order: self.row_order, col_order: *col_order, digit_map };
+    }
+}
+
+/**
+ * All 1296 orders of the 9 rows or columns which keep the bands or stacks together.
+ */
+fn all_line_orders() -> Vec<[usize; 9]> {
+    let mut orders: Vec<[usize; 9]> = Vec::new();
+    for groups in ORDERS {
+        for first in ORDERS {
+            for second in ORDERS {
+                for third in ORDERS {
+                    let within = [first, second, third];
+                    orders.push(IDENTITY_ORDER.map(|line| groups[line / 3] * 3 + within[line / 3][line % 3]));
+                }
+            }
+        }
+    }
+    return orders;
+}
+
+/**
+ * Check that the order is a permutation of 0, 1 and 2.
+ */
+fn validate_order(order: [usize; 3]) -> Result<(), TransformationError> {
+    let mut sorted = order;
+    sorted.sort();
+    if sorted != [0, 1, 2] {
+        return Err(TransformationError::InvalidOrder(order));
+    }
+    return Ok(());
+}
+
+/**
+ * The order of 3 bands or stacks in which the two given ones are swapped.
+ */
+fn swapped_order(group: usize, other_group: usize) -> Result<[usize; 3], TransformationError> {
+    for index in [group, other_group] {
+        if index >= 3 {
+            return Err(TransformationError::InvalidIndex(index));
+        }
+    }
+    let mut order = [0, 1, 2];
+    order.swap(group, other_group);
+    return Ok(order);
+}
+
+/**
+ * The order of the 9 rows or columns which reorders the groups of 3 by the given order.
+ */
+fn lines_in_order(order: [usize; 3]) -> Result<[usize; 9], TransformationError> {
+    validate_order(order)?;
+    return Ok(IDENTITY_ORDER.map(|line| order[line / 3] * 3 + line % 3));
+}
+
+/**
+ * The order of the 9 rows or columns which reorders the lines within one group of 3 by the given order.
+ */
+fn lines_in_group_in_order(group: usize, order: [usize; 3]) -> Result<[usize; 9], TransformationError> {
+    if group >= 3 {
+        return Err(TransformationError::InvalidIndex(group));
+    }
+    validate_order(order)?;
+    return Ok(IDENTITY_ORDER.map(|line| if line / 3 == group { group * 3 + order[line % 3] } else { line }));
+}
+
+fn invert(order: &[usize; 9]) -> [usize; 9] {
+    let mut inverse = [0; 9];
+    for (idx, line) in order.iter().enumerate() {
+        inverse[*line] = idx;
+    }
+    return inverse;
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::sudoku::verify_solution;
+
+    use super::*;
+
+    fn get_board1() -> Board {
+        return "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79".parse().unwrap();
+    }
+
+    fn get_board1_solved() -> Board {
+        return "534678912672195348198342567859761423426853791713924856961537284287419635345286179".parse().unwrap();
+    }
+
+    /**
+     * A transformation composed of the given number of basic operations, chosen by a simple linear congruential
+     * generator so that the tests are reproducible.
+     */
+    fn get_random_transformation(seed: u64, num_operations: usize) -> Transformation {
+        let orders = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+        let mut state = seed;
+        let mut next = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        let mut transformation = Transformation::identity();
+        for _ in 0..num_operations {
+            let order = orders[next(6) as usize];
+            let operation = match next(6) {
+                0 => Transformation::transpose(),
+                1 => Transformation::permute_bands(order).unwrap(),
+                2 => Transformation::permute_stacks(order).unwrap(),
+                3 => Transformation::permute_rows_in_band(next(3) as usize, order).unwrap(),
+                4 => Transformation::permute_columns_in_stack(next(3) as usize, order).unwrap(),
+                _ => {
+                    let mut digits = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+                    digits.rotate_left(next(9) as usize);
+                    digits.swap(0, next(9) as usize);
+                    Transformation::relabel_digits(digits).unwrap()
+                }
+            };
+            transformation = transformation.compose(&operation);
+        }
+        return transformation;
+    }
+
+    #[test]
+    fn test_transpose() {
+        let board = get_board1();
+
+        let transposed = Transformation::transpose().apply(&board);
+
+        assert_eq!(transposed.to_line()[..9], *"56.847...");
+    }
+
+    #[test]
+    fn test_permute_bands() {
+        let board = get_board1();
+
+        let permuted = Transformation::permute_bands([2, 0, 1]).unwrap().apply(&board);
+
+        assert_eq!(permuted.to_line()[..27], board.to_line()[54..]);
+        assert_eq!(permuted.to_line()[27..], board.to_line()[..54]);
+    }
+
+    #[test]
+    fn test_permute_columns_in_stack() {
+        let board = get_board1();
+
+        let permuted = Transformation::permute_columns_in_stack(0, [2, 1, 0]).unwrap().apply(&board);
+
+        assert_eq!(permuted.to_line()[..9], *".35.7....");
+    }
+
+    #[test]
+    fn test_relabel_digits() {
+        let board = get_board1();
+
+        let relabeled = Transformation::relabel_digits([9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap().apply(&board);
+
+        assert_eq!(relabeled.to_line()[..9], *"57..3....");
+    }
+
+    #[rstest]
+    #[case(Transformation::permute_bands([0, 1, 1]), TransformationError::InvalidOrder([0, 1, 1]))]
+    #[case(Transformation::permute_stacks([0, 1, 3]), TransformationError::InvalidOrder([0, 1, 3]))]
+    #[case(Transformation::permute_rows_in_band(3, [0, 1, 2]), TransformationError::InvalidIndex(3))]
+    #[case(Transformation::relabel_digits([1, 2, 3, 4, 5, 6, 7, 8, 8]), TransformationError::InvalidDigits([1, 2, 3, 4, 5, 6, 7, 8, 8]))]
+    #[case(Transformation::relabel_digits([0, 2, 3, 4, 5, 6, 7, 8, 9]), TransformationError::InvalidDigits([0, 2, 3, 4, 5, 6, 7, 8, 9]))]
+    fn test_invalid_transformation(#[case] result: Result<Transformation, TransformationError>, #[case] expected: TransformationError) {
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_transformed_solution_solves_transformed_puzzle() {
+        for seed in 0..50 {
+            let transformation = get_random_transformation(seed, 10);
+
+            let puzzle = transformation.apply(&get_board1());
+            let solution = transformation.apply(&get_board1_solved());
+
+            assert_eq!(verify_solution(&puzzle, &solution), Ok(()), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        for seed in 0..50 {
+            let transformation = get_random_transformation(seed, 10);
+
+            let inverse = transformation.inverse();
+
+            assert_eq!(inverse.apply(&transformation.apply(&get_board1())), get_board1(), "seed {}", seed);
+            assert_eq!(transformation.compose(&inverse), Transformation::identity(), "seed {}", seed);
+            assert_eq!(inverse.compose(&transformation), Transformation::identity(), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        for seed in 0..50 {
+            let first = get_random_transformation(seed, 5);
+            let second = get_random_transformation(seed + 1000, 5);
+
+            let composed = first.compose(&second);
+
+            assert_eq!(composed.apply(&get_board1()), second.apply(&first.apply(&get_board1())), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_canonical_form_of_transformed_board() {
+        let canonical_form = get_board1().canonical_form();
+
+        for seed in 0..5 {
+            let transformed = get_random_transformation(seed, 10).apply(&get_board1());
+
+            assert_eq!(transformed.canonical_form(), canonical_form, "seed {}", seed);
+            assert!(transformed.is_isomorphic_to(&get_board1()), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_canonical_form() {
+        let canonical_form = get_board1().canonical_form();
+
+        assert!(canonical_form.to_line() <= get_board1().to_line());
+        assert_eq!(canonical_form.canonical_form(), canonical_form);
+        assert_eq!(Transformation::to_canonical_form(&get_board1()).apply(&get_board1()), canonical_form);
+        assert_eq!(get_board1_solved().canonical_form().to_line()[..9], *"123456789");
+    }
+
+    #[test]
+    fn test_canonical_form_of_solution_solves_canonical_form_of_puzzle() {
+        let transformation = Transformation::to_canonical_form(&get_board1());
+
+        let solution = transformation.apply(&get_board1_solved());
+
+        assert_eq!(verify_solution(&get_board1().canonical_form(), &solution), Ok(()));
+    }
+
+    #[test]
+    fn test_is_not_isomorphic() {
+        let mut board = get_board1();
+        board.set(0, 2, 4).unwrap();
+        let mut other = get_board1();
+        other.set(4, 4, 5).unwrap();
+
+        assert!(!board.is_isomorphic_to(&other));
+        assert!(!get_board1().is_isomorphic_to(&board));
+        assert!(!get_board1().is_isomorphic_to(&get_board1_solved()));
+    }
+}
@@ -0,0 +1,284 @@
+use std::ops::ControlFlow;
+
+use exact_cover::ExactCoverSolution;
+use thiserror::Error;
+
+use crate::cell_set::CellSet;
+use crate::sudoku::{convert_to_exact_cover_problem_with_candidates, convert_to_sudoku_solution, Board, CandidateGrid, SolveError};
+
+/**
+ * An extra rule on top of the rules of Sudoku, such as the rule of a Sudoku variant. Constraints don't change the
+ * exact cover encoding: they remove candidates before the search, and the search backtracks as soon as the digits it
+ * placed break them. So new rules can be added without touching the encoder.
+ */
+pub trait Constraint {
+    /**
+     * A short name of the rule, e.g. "diagonal", used in error messages.
+     */
+    fn name(&self) -> &'static str;
+
+    /**
+     * Remove the candidates that the rule rules out given the other candidates, e.g. the digit of a cell with a
+     * single candidate from the cells that must differ from it. Returns whether any candidate was removed. Pruning
+     * is repeated until no constraint removes a candidate anymore, so it only needs to look one step ahead.
+     */
+    fn prune(&self, grid: &mut CandidateGrid) -> bool;
+
+    /**
+     * Check that the digits of a board, which may be partially filled, don't break the rule.
+     */
+    fn validate(&self, board: &Board) -> Result<(), ConstraintViolation>;
+}
+
+// ConstraintViolation is a custom error type for boards that break the rule of a constraint.
+#[derive(Debug, PartialEq, Error)]
+#[error("the {constraint} rule is broken by the cells in {}", format_cells(.cells))]
+pub struct ConstraintViolation {
+    /// The name of the broken rule
+    pub constraint: &'static str,
+    /// The cells that together break the rule
+    pub cells: CellSet,
+}
+
+fn format_cells(cells: &CellSet) -> String {
+    return cells.iter()
+        .map(|(row, col)| format!("row {}, column {}", row + 1, col + 1))
+        .collect::<Vec<String>>()
+        .join(" and ");
+}
+
+/**
+ * The rule of diagonal Sudoku (Sudoku X): both main diagonals contain every digit once.
+ */
+pub struct DiagonalConstraint;
+
+/**
+ * The rule of anti-knight Sudoku: cells a chess knight's move apart don't contain the same digit.
+ */
+pub struct AntiKnightConstraint;
+
+impl DiagonalConstraint {
+    /**
+     * The other cells on the same diagonals as the cell.
+     */
+    fn peers(row: usize, col: usize) -> CellSet {
+        let mut peers = CellSet::empty();
+        if row == col {
+            peers = peers | (0..9).map(|k| (k, k)).collect();
+        }
+        if row + col == 8 {
+            peers = peers | (0..9).map(|k| (k, 8 - k)).collect();
+        }
+        peers.remove(row, col);
+        return peers;
+    }
+}
+
+impl Constraint for DiagonalConstraint {
+    fn name(&self) -> &'static str {
+        return "diagonal";
+    }
+
+    fn prune(&self, grid: &mut CandidateGrid) -> bool {
+        return prune_peers(grid, DiagonalConstraint::peers);
+    }
+
+    fn validate(&self, board: &Board) -> Result<(), ConstraintViolation> {
+        return validate_peers(board, self.name(), DiagonalConstraint::peers);
+    }
+}
+
+impl AntiKnightConstraint {
+    /**
+     * The cells a knight's move away from the cell.
+     */
+    fn peers(row: usize, col: usize) -> CellSet {
+        const MOVES: [(isize, isize); 8] = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+        return MOVES.iter()
+            .map(|(di, dj)| (row as isize + di, col as isize + dj))
+            .filter(|(i, j)| (0..9).contains(i) && (0..9).contains(j))
+            .map(|(i, j)| (i as usize, j as usize))
+            .collect();
+    }
+}
+
+impl Constraint for AntiKnightConstraint {
+    fn name(&self) -> &'static str {
+        return "anti-knight";
+    }
+
+    fn prune(&self, grid: &mut CandidateGrid) -> bool {
+        return prune_peers(grid, AntiKnightConstraint::peers);
+    }
+
+    fn validate(&self, board: &Board) -> Result<(), ConstraintViolation> {
+        return validate_peers(board, self.name(), AntiKnightConstraint::peers);
+    }
+}
+
+/**
+ * Remove the digit of every cell with a single candidate from the cells that must have a different digit.
+ */
+fn prune_peers(grid: &mut CandidateGrid, peers: impl Fn(usize, usize) -> CellSet) -> bool {
+    let mut removed = false;
+    for i in 0..9 {
+        for j in 0..9 {
+            let digits = grid.digits(i, j);
+            if digits.len() != 1 {
+                continue;
+            }
+            for (pi, pj) in peers(i, j) {
+                if grid.contains(pi, pj, digits[0]) {
+                    grid.remove(pi, pj, digits[0]).unwrap();
+                    removed = true;
+                }
+            }
+        }
+    }
+    return removed;
+}
+
+/**
+ * Check that no filled cell has the same digit as a cell that must have a different digit.
+ */
+fn validate_peers(board: &Board, constraint: &'static str, peers: impl Fn(usize, usize) -> CellSet) -> Result<(), ConstraintViolation> {
+    for (i, j, digit) in board.iter_cells() {
+        let Some(digit) = digit else {
+            continue;
+        };
+        if let Some((pi, pj)) = peers(i, j).iter().find(|(pi, pj)| board.get(*pi, *pj) == Some(digit)) {
+            return Err(ConstraintViolation { constraint, cells: [(i, j), (pi, pj)].into_iter().collect() });
+        }
+    }
+    return Ok(());
+}
+
+/**
+ * Let the constraints remove candidates until none of them removes any more, since a candidate removed by one
+ * constraint can allow another one to remove more. Returns whether any candidate was removed.
+ */
+pub fn prune_candidates(grid: &mut CandidateGrid, constraints: &[&dyn Constraint]) -> bool {
+    let mut removed = false;
+    loop {
+        let mut removed_this_round = false;
+        for constraint in constraints.iter() {
+            removed_this_round |= constraint.prune(grid);
+        }
+        if !removed_this_round {
+            return removed;
+        }
+        removed = true;
+    }
+}
+
+/**
+ * Solve Sudoku with the extra rules of the constraints. The candidates are pruned by the constraints first, and the
+ * search of the pruned exact cover problem checks the constraints against the digits placed so far after every digit
+ * it places, so that it doesn't explore solutions of plain Sudoku that break them.
+ */
+pub fn solve_with_constraints(board: &Board, constraints: &[&dyn Constraint]) -> Result<Option<Board>, SolveError> {
+    if constraints.iter().any(|constraint| constraint.validate(board).is_err()) {
+        return Ok(None);
+    }
+    if board.is_filled() {
+        return Ok(board.is_valid_solution().then(|| board.clone()));
+    }
+
+    let mut candidates = board.candidates();
+    prune_candidates(&mut candidates, constraints);
+
+    let exact_cover_problem = convert_to_exact_cover_problem_with_candidates(board, &candidates)?;
+
+    let to_board = |selected_options: &[String]| {
+        let exact_cover_solution = ExactCoverSolution { selected_options: selected_options.to_vec() };
+        return convert_to_sudoku_solution(&exact_cover_problem, &exact_cover_solution);
+    };
+    let mut keep = |selected_options: &[String]| {
+        let board = to_board(selected_options);
+        return constraints.iter().all(|constraint| constraint.validate(&board).is_ok());
+    };
+    let result = exact_cover_problem.for_each_solution_pruned(&mut keep, |selected_options| ControlFlow::Break(to_board(selected_options)))?;
+    return Ok(result.break_value());
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::sudoku::solve_sudoku_with_exact_cover;
+
+    use super::*;
+
+    #[rstest]
+    #[case("..3...4.....867.5...53.......2...6.49..5..7..13...42.5.5.7...2.3.9..8...26.953...",
+           "683295417421867359795341862572189634946532781138674295854716923319428576267953148",
+           &DiagonalConstraint)]
+    #[case("..329.4.....3.....7...41.5....4.9.....58.29..9..5.3.7...4.5.7.353..2....16....5.4",
+           "683295417451387269729641358872469135315872946946513872294156783538724691167938524",
+           &AntiKnightConstraint)]
+    fn test_solve_with_constraints(#[case] board: &str, #[case] expected: &str, #[case] constraint: &dyn Constraint) {
+        let board: Board = board.parse().unwrap();
+
+        let solution = solve_with_constraints(&board, &[constraint]).unwrap();
+
+        assert_eq!(solution.unwrap().to_line(), expected);
+    }
+
+    #[test]
+    fn test_solve_with_constraints_without_constraints() {
+        let board: Board = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6...728....419..5....8..79".parse().unwrap();
+
+        let solution = solve_with_constraints(&board, &[]).unwrap();
+
+        assert_eq!(solution, solve_sudoku_with_exact_cover(&board).unwrap());
+    }
+
+    #[test]
+    fn test_solve_with_constraints_on_empty_board() {
+        let solution = solve_with_constraints(&Board::empty(), &[&DiagonalConstraint]).unwrap().unwrap();
+
+        assert!(solution.is_valid_solution());
+        assert_eq!(DiagonalConstraint.validate(&solution), Ok(()));
+    }
+
+    #[test]
+    fn test_solve_with_constraints_broken_by_givens() {
+        let mut board = Board::empty();
+        board.set(0, 0, 1).unwrap();
+        board.set(8, 8, 1).unwrap();
+
+        let solution = solve_with_constraints(&board, &[&DiagonalConstraint]).unwrap();
+
+        assert_eq!(solution, None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut board = Board::empty();
+        board.set(4, 4, 7).unwrap();
+        board.set(2, 5, 7).unwrap();
+
+        let anti_knight = AntiKnightConstraint.validate(&board);
+
+        assert_eq!(anti_knight, Err(ConstraintViolation { constraint: "anti-knight", cells: [(2, 5), (4, 4)].into_iter().collect() }));
+        assert_eq!(anti_knight.unwrap_err().to_string(),
+                   "the anti-knight rule is broken by the cells in row 3, column 6 and row 5, column 5");
+        assert_eq!(DiagonalConstraint.validate(&board), Ok(()));
+    }
+
+    #[test]
+    fn test_prune_candidates() {
+        let mut grid = CandidateGrid::full();
+        grid.restrict(0, 0, &[1]).unwrap();
+        grid.restrict(1, 1, &[1, 2]).unwrap();
+
+        let removed = prune_candidates(&mut grid, &[&DiagonalConstraint]);
+
+        // Removing 1 from (1, 1) leaves only 2, which is then removed from the rest of the diagonal
+        assert!(removed);
+        assert_eq!(grid.digits(1, 1), vec![2]);
+        assert_eq!(grid.digits(8, 8), vec![3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(grid.digits(0, 8), (1..10).collect::<Vec<u8>>());
+        assert!(!prune_candidates(&mut grid, &[&DiagonalConstraint]));
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::sudoku::{Board, DigitSymbols};
+
+/**
+ * A line of the board along which hidden words are read.
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WordLine {
+    /// Row i (0-8), read from left to right
+    Row(usize),
+    /// Column j (0-8), read from top to bottom
+    Column(usize),
+    /// The diagonal from the top left to the bottom right corner
+    Diagonal,
+    /// The diagonal from the top right to the bottom left corner
+    AntiDiagonal,
+}
+
+/**
+ * A dictionary word spelled by consecutive cells of a line of a Wordoku board.
+ */
+#[derive(Debug, PartialEq)]
+pub struct HiddenWord {
+    /// The word as it appears in the dictionary
+    pub word: String,
+    /// The line the word is spelled along
+    pub line: WordLine,
+    /// The position in the line (0-8) of the first letter of the word
+    pub start: usize,
+}
+
+impl WordLine {
+    /**
+     * All lines which are searched for hidden words: the rows, the columns and both diagonals.
+     */
+    fn all() -> Vec<WordLine> {
+        let mut lines: Vec<WordLine> = (0..9).map(WordLine::Row).collect();
+        lines.extend((0..9).map(WordLine::Column));
+        lines.push(WordLine::Diagonal);
+        lines.push(WordLine::AntiDiagonal);
+        return lines;
+    }
+
+    /**
+     * The (row, col) of the cell at position k (0-8) of the line.
+     */
+    fn cell(&self, k: usize) -> (usize, usize) {
+        return match self {
+            WordLine::Row(i) => (*i, k),
+            WordLine::Column(j) => (k, *j),
+            WordLine::Diagonal => (k, k),
+            WordLine::AntiDiagonal => (k, 8 - k),
+        };
+    }
+}
+
+impl Display for WordLine {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WordLine::Row(i) => write!(f, "row {}", i + 1),
+            WordLine::Column(j) => write!(f, "column {}", j + 1),
+            WordLine::Diagonal => write!(f, "diagonal"),
+            WordLine::AntiDiagonal => write!(f, "anti-diagonal"),
+        }
+    }
+}
+
+/**
+ * Find the dictionary words that are spelled along the rows, columns and diagonals of a Wordoku board, whose digits
+ * are written with the given letters. Words are read forwards only and compared ignoring case; empty cells never
+ * match. The hidden words are returned in the order of the lines, and by position within each line.
+ */
+pub fn find_hidden_words(board: &Board, symbols: &DigitSymbols, dictionary: &[String]) -> Vec<HiddenWord> {
+    let mut hidden_words: Vec<HiddenWord> = Vec::new();
+    for line in WordLine::all() {
+        let letters: Vec<Option<char>> = (0..9)
+            .map(|k| {
+                let (i, j) = line.cell(k);
+                match board.cell(i, j) {
+                    0 => None,
+                    digit => Some(symbols.symbol(digit).to_ascii_uppercase()),
+                }
+            })
+            .collect();
+
+        for start in 0..9 {
+            for word in dictionary.iter() {
+                let word_letters: Vec<char> = word.chars().map(|letter| letter.to_ascii_uppercase()).collect();
+                if word_letters.is_empty() || start + word_letters.len() > 9 {
+                    continue;
+                }
+                let matches = word_letters.iter().zip(letters[start..].iter())
+                    .all(|(letter, board_letter)| Some(*letter) == *board_letter);
+                if matches {
+                    hidden_words.push(HiddenWord {
+                        word: word.clone(),
+                        line,
+                        start,
+                    });
+                }
+            }
+        }
+    }
+    return hidden_words;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_symbols() -> DigitSymbols {
+        return DigitSymbols::new("YSORWDPLA").unwrap();
+    }
+
+    fn get_solution() -> Board {
+        return Board::read_from_str_with_symbols("\
+            WOR DPL AYS\n\
+            DPS YAW ORL\n\
+            YAL ORS WDP\n\
+            \n\
+            LWA PDY RSO\n\
+            RSD LWO PAY\n\
+            PYO ASR LWD\n\
+            \n\
+            ADY WOP SLR\n\
+            SLP RYA DOW\n\
+            ORW SLD YPA\n", &get_symbols()).unwrap();
+    }
+
+    fn get_dictionary() -> Vec<String> {
+        return vec!["wordplays".to_string(), "PLAY".to_string(), "SLOW".to_string(), "DOW".to_string(), "".to_string()];
+    }
+
+    #[test]
+    fn test_find_hidden_words() {
+        let hidden_words = find_hidden_words(&get_solution(), &get_symbols(), &get_dictionary());
+
+        assert_eq!(hidden_words, vec![
+            HiddenWord { word: "wordplays".to_string(), line: WordLine::Row(0), start: 0 },
+            HiddenWord { word: "PLAY".to_string(), line: WordLine::Row(0), start: 4 },
+            HiddenWord { word: "DOW".to_string(), line: WordLine::Row(7), start: 6 },
+        ]);
+    }
+
+    #[test]
+    fn test_find_hidden_words_skips_empty_cells() {
+        let mut board = get_solution();
+        board.set_row(0, [5, 3, 4, 6, 0, 8, 9, 1, 2]).unwrap();
+
+        let hidden_words = find_hidden_words(&board, &get_symbols(), &get_dictionary());
+
+        assert_eq!(hidden_words, vec![
+            HiddenWord { word: "DOW".to_string(), line: WordLine::Row(7), start: 6 },
+        ]);
+    }
+
+    #[test]
+    fn test_word_line_display() {
+        assert_eq!(WordLine::Row(0).to_string(), "row 1");
+        assert_eq!(WordLine::Column(8).to_string(), "column 9");
+        assert_eq!(WordLine::AntiDiagonal.to_string(), "anti-diagonal");
+    }
+}
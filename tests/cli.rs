@@ -0,0 +1,336 @@
+use assert_cmd::Command;
+use rstest::rstest;
+
+/**
+ * Run the binary with the given arguments and return its standard output. The locale variables are cleared so the
+ * output is in English unless --lang is given.
+ */
+fn run(args: &[&str]) -> String {
+    let (success, stdout) = run_with_status(args);
+    assert!(success);
+    return stdout;
+}
+
+/**
+ * Run the binary like run, but also return whether it exited successfully instead of requiring it.
+ */
+fn run_with_status(args: &[&str]) -> (bool, String) {
+    let output = Command::cargo_bin("rust-sudoku").unwrap()
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .args(args)
+        .output()
+        .unwrap();
+    return (output.status.success(), String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn test_solve_default_board() {
+    insta::assert_snapshot!(run(&[]));
+}
+
+#[test]
+fn test_solve_board() {
+    insta::assert_snapshot!(run(&["data/sudoku_ai_escargot.txt"]));
+}
+
+#[test]
+fn test_solve_board_with_colors() {
+    let colored = run(&["data/sudoku.txt", "--color", "always"]);
+    let plain = run(&["data/sudoku.txt", "--color", "never"]);
+
+    assert!(colored.contains("\x1b[1;34m5\x1b[0m\x1b[1;34m3\x1b[0m\x1b[32m4\x1b[0m"), "{}", colored);
+    assert!(!plain.contains('\x1b'));
+    assert_eq!(plain, run(&["data/sudoku.txt"]));
+}
+
+#[test]
+fn test_solve_board_with_invalid_color() {
+    let (success, output) = run_with_status(&["--color", "sometimes"]);
+
+    assert!(!success);
+    assert_eq!(output, "--color expects auto, always or never\n");
+}
+
+#[test]
+fn test_explain_unsolvable_board() {
+    let (success, output) = run_with_status(&["data/sudoku_unsolvable.txt"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_conflicting_givens() {
+    let (success, output) = run_with_status(&["data/sudoku_conflicting_givens.txt"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_conflicting_givens_in_dutch() {
+    let (success, output) = run_with_status(&["--lang", "nl", "data/sudoku_conflicting_givens.txt"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_solve_board_with_letters() {
+    insta::assert_snapshot!(run(&["--symbols", "ABCDEFGHI", "data/sudoku_letters.txt"]));
+}
+
+#[test]
+fn test_solve_wordoku_with_hidden_words() {
+    insta::assert_snapshot!(run(&["--symbols", "YSORWDPLA", "--words", "data/words.txt", "data/wordoku.txt"]));
+}
+
+#[test]
+fn test_memory_limit_exceeded() {
+    let (success, output) = run_with_status(&["--max-memory", "0", "data/sudoku_ai_escargot.txt"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_memory_limit_exceeded_with_trace() {
+    let trace_path = std::env::temp_dir().join(format!("rust-sudoku-trace-limit-{}.json", std::process::id()));
+
+    let (success, output) = run_with_status(&["--max-memory", "0", "--trace", trace_path.to_str().unwrap(), "data/sudoku_ai_escargot.txt"]);
+    std::fs::remove_file(&trace_path).unwrap();
+
+    assert!(!success);
+    assert_eq!(output, run_with_status(&["--max-memory", "0", "data/sudoku_ai_escargot.txt"]).1);
+}
+
+#[rstest]
+#[case("lots")]
+#[case("20000000000000")]
+fn test_invalid_max_memory(#[case] megabytes: &str) {
+    let (success, output) = run_with_status(&["data/sudoku.txt", "--max-memory", megabytes]);
+
+    assert!(!success);
+    assert_eq!(output, "--max-memory expects a number of megabytes\n");
+}
+
+#[test]
+fn test_solve_with_trace() {
+    let trace_path = std::env::temp_dir().join(format!("rust-sudoku-trace-{}.json", std::process::id()));
+
+    let output = run(&["--trace", trace_path.to_str().unwrap()]);
+    let trace = std::fs::read_to_string(&trace_path).unwrap();
+    std::fs::remove_file(&trace_path).unwrap();
+
+    assert_eq!(output, run(&[]));
+    assert!(trace.starts_with("{\"traceEvents\":["));
+    assert!(trace.contains("\"name\":\"solution\""));
+}
+
+#[test]
+fn test_invalid_board() {
+    let (success, output) = run_with_status(&["data/sudoku_invalid_character.txt"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_selftest() {
+    insta::assert_snapshot!(run(&["selftest"]));
+}
+
+#[test]
+fn test_count_solutions() {
+    insta::assert_snapshot!(run(&["count", "data/sudoku_multiple_solutions.txt"]));
+}
+
+#[test]
+fn test_count_solutions_with_zdd() {
+    insta::assert_snapshot!(run(&["count", "--engine", "zdd", "data/sudoku_multiple_solutions.txt"]));
+}
+
+#[test]
+fn test_rate_board() {
+    insta::assert_snapshot!(run(&["rate", "data/sudoku_easy.txt"]));
+}
+
+#[test]
+fn test_rate_board_as_json() {
+    insta::assert_snapshot!(run(&["rate", "data/sudoku_easy.txt", "--json"]));
+}
+
+#[test]
+fn test_rate_board_as_json_with_breakdown() {
+    let output = run(&["rate", "data/sudoku_ai_escargot.txt", "--json"]);
+
+    let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let search_effort = &report["search_effort"];
+    let logic_effort = &report["logic_effort"];
+    assert!(search_effort["num_nodes"].as_u64().unwrap() > 0, "{}", output);
+    assert!(logic_effort["num_bifurcations"].as_u64().unwrap() > 0, "{}", output);
+    let technique_counts = logic_effort["technique_counts"].as_object().unwrap();
+    let num_steps: u64 = technique_counts.values().map(|count| count.as_u64().unwrap()).sum();
+    assert_eq!(logic_effort["num_steps"].as_u64(), Some(num_steps), "{}", output);
+    assert!(logic_effort["hardest_step"].as_u64().unwrap() < num_steps, "{}", output);
+    assert!(technique_counts.contains_key(logic_effort["hardest_technique"].as_str().unwrap()), "{}", output);
+}
+
+#[rstest]
+#[case("534678912672195348198342567859761423426853791713924856961537284287419635345286179", "already solved")]
+#[case("534678912672195348198342567859761423426853791713924856961537284287419635345286197", "invalid")]
+fn test_rate_filled_board(#[case] board: &str, #[case] status: &str) {
+    let board_path = std::env::temp_dir().join(format!("rust-sudoku-filled-{}-{}.txt", std::process::id(), status));
+    std::fs::write(&board_path, board).unwrap();
+
+    let output = run(&["rate", board_path.to_str().unwrap()]);
+    let json_output = run(&["rate", board_path.to_str().unwrap(), "--json"]);
+    std::fs::remove_file(&board_path).unwrap();
+
+    let expected_message = match status {
+        "already solved" => "The board is already solved",
+        _ => "The board is filled in, but it is not a valid solution",
+    };
+    assert!(output.ends_with(&format!("{}\n", expected_message)), "{}", output);
+    assert!(!output.contains("Search effort"), "{}", output);
+    assert_eq!(json_output, format!("{{\n  \"board\": \"{}\",\n  \"status\": \"{}\"\n}}\n", board, status));
+}
+
+#[rstest]
+#[case(&["rate", "data/does_not_exist.txt"])]
+#[case(&["rate", "data/sudoku_conflicting_givens.txt", "--json"])]
+#[case(&["count", "--engine", "guessing"])]
+#[case(&["count", "data/does_not_exist.txt"])]
+#[case(&["export", "data/sudoku.txt"])]
+#[case(&["export", "--sdk", "--solution", "data/sudoku_unsolvable.txt"])]
+#[case(&["--lang", "xx"])]
+fn test_failing_commands_exit_with_an_error(#[case] args: &[&str]) {
+    let (success, output) = run_with_status(args);
+
+    assert!(!success, "{}", output);
+}
+
+#[test]
+fn test_export_latex_with_solution() {
+    insta::assert_snapshot!(run(&["export", "--latex", "--solution", "data/sudoku.txt"]));
+}
+
+#[test]
+fn test_verify_solutions() {
+    let (success, stdout) = run_with_status(&["verify", "--puzzles", "data/puzzles.sdm", "--solutions", "data/solutions.sdm"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(stdout);
+}
+
+#[test]
+fn test_solve_board_from_stdin() {
+    let output = Command::cargo_bin("rust-sudoku").unwrap()
+        .arg("-")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .write_stdin(std::fs::read_to_string("data/sudoku_ai_escargot.txt").unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), run(&["data/sudoku_ai_escargot.txt"]));
+}
+
+#[test]
+fn test_solve_default_board_ignores_piped_input() {
+    let output = Command::cargo_bin("rust-sudoku").unwrap()
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .write_stdin(std::fs::read_to_string("data/sudoku_ai_escargot.txt").unwrap())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), run(&["data/sudoku.txt"]));
+}
+
+#[test]
+fn test_solve_with_config_file() {
+    let output = run(&["--config", "data/config_letters.toml"]);
+
+    assert_eq!(output, run(&["--symbols", "ABCDEFGHI", "data/sudoku_letters.txt"]));
+}
+
+#[test]
+fn test_invalid_config_file() {
+    let (success, output) = run_with_status(&["--config", "data/config_invalid.toml"]);
+
+    assert!(!success);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_export_sdk() {
+    insta::assert_snapshot!(run(&["export", "--sdk"]));
+}
+
+#[test]
+fn test_compare_engines() {
+    let output = run(&["compare-engines", "data/puzzles.sdm"]);
+
+    for engine in ["general", "bitset", "dancing cells"] {
+        assert!(output.lines().any(|line| line.starts_with(engine) && line.contains("4/4")), "{}", output);
+    }
+    assert!(output.lines().next().unwrap().ends_with("Nodes"), "{}", output);
+    // Every engine reports the number of nodes of its own search
+    for line in output.lines().skip(1) {
+        let num_nodes: u64 = line.rsplit(' ').next().unwrap().parse().unwrap();
+        assert!(num_nodes > 0, "{}", output);
+    }
+    assert!(!output.contains("disagrees") && !output.contains("another solution"), "{}", output);
+}
+
+#[test]
+fn test_compare_engines_without_collection() {
+    let (success, output) = run_with_status(&["compare-engines"]);
+
+    assert!(!success);
+    assert_eq!(output, "compare-engines expects a file of puzzles, one per line\n");
+}
+
+#[test]
+fn test_solve_all() {
+    let output = run(&["solve-all", "data/puzzles.sdm", "--threads", "2"]);
+    let solutions = std::fs::read_to_string("data/solutions.sdm").unwrap();
+
+    assert_eq!(output.lines().count(), 4);
+    assert_eq!(output.lines().next(), solutions.lines().next());
+    assert!(output.lines().all(|line| line.len() == 81 && !line.contains('.')), "{}", output);
+}
+
+#[test]
+fn test_solve_all_reports_boards_by_number() {
+    let collection_path = std::env::temp_dir().join(format!("rust-sudoku-collection-{}.sdm", std::process::id()));
+    let puzzles = std::fs::read_to_string("data/puzzles.sdm").unwrap();
+    let first_puzzle = puzzles.lines().next().unwrap();
+    std::fs::write(&collection_path, format!("11{}\n{}\n{}\n", ".".repeat(79), "12x", first_puzzle)).unwrap();
+
+    let (success, output) = run_with_status(&["solve-all", collection_path.to_str().unwrap()]);
+    std::fs::remove_file(&collection_path).unwrap();
+
+    assert!(!success);
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines[0].starts_with("Board 1: error solving board: the givens in row 1, column 1 and row 1, column 2 conflict"), "{}", output);
+    assert!(lines[1].starts_with("Board 2: invalid puzzle: "), "{}", output);
+    assert_eq!(lines[2].len(), 81);
+}
+
+#[rstest]
+#[case(&["solve-all"], "solve-all expects a file of puzzles, one per line\n")]
+#[case(&["solve-all", "data/puzzles.sdm", "--threads", "0"], "--threads expects a positive number of threads\n")]
+fn test_solve_all_invalid_arguments(#[case] args: &[&str], #[case] expected_output: &str) {
+    let (success, output) = run_with_status(args);
+
+    assert!(!success);
+    assert_eq!(output, expected_output);
+}